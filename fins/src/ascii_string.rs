@@ -0,0 +1,84 @@
+use crate::*;
+
+/// Fixed length ASCII text as it appears in FINS frames, padded with spaces.
+#[derive(Copy, Clone, Eq, PartialEq)]
+#[repr(transparent)]
+pub struct RawAsciiString<const N: usize>(pub [u8; N]);
+
+unsafe impl<const N: usize> Raw for RawAsciiString<N> {
+    #[inline]
+    fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    #[inline]
+    fn as_bytes_mut(&mut self) -> &mut [u8] {
+        &mut self.0
+    }
+}
+
+impl<const N: usize> RawAsciiString<N> {
+    /// Decodes the text without trailing spaces and NUL characters.
+    pub fn deserialize(&self) -> Result<String, ProtocolViolation> {
+        if let Some(&b) = self.0.iter().find(|b| !b.is_ascii()) {
+            return Err(ProtocolViolation::InvalidAsciiCharacter(b));
+        }
+        let len = self
+            .0
+            .iter()
+            .rposition(|&b| b != b' ' && b != 0)
+            .map_or(0, |i| i + 1);
+        Ok(self.0[..len].iter().map(|&b| b as char).collect())
+    }
+
+    /// Encodes `text` padded with spaces. Returns `None` when `text` is not ASCII or does not fit.
+    pub fn serialize(text: &str) -> Option<Self> {
+        if !text.is_ascii() || text.len() > N {
+            return None;
+        }
+        let mut bytes = [b' '; N];
+        bytes[..text.len()].copy_from_slice(text.as_bytes());
+        Some(Self(bytes))
+    }
+
+    /// Encodes `text` like [`RawAsciiString::serialize`], failing with the name of the `field`
+    /// when `text` is not ASCII or does not fit.
+    pub fn serialize_field(text: &str, field: &str) -> crate::Result<Self> {
+        Self::serialize(text).ok_or_else(|| {
+            Error::InvalidValue(format!(
+                "{} {:?} should be at most {} ASCII characters",
+                field, text, N
+            ))
+        })
+    }
+}
+
+impl<const N: usize> std::fmt::Debug for RawAsciiString<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "RawAsciiString({:?})", String::from_utf8_lossy(&self.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let raw = RawAsciiString::<8>::serialize("CPU01").unwrap();
+        assert_eq!(&raw.0, b"CPU01   ");
+        assert_eq!(raw.deserialize().unwrap(), "CPU01");
+        assert_eq!(RawAsciiString(*b"AB\0\0").deserialize().unwrap(), "AB");
+    }
+
+    #[test]
+    fn rejects_invalid_text() {
+        assert!(RawAsciiString::<4>::serialize("TOO LONG").is_none());
+        assert!(RawAsciiString::<4>::serialize("é").is_none());
+        assert!(matches!(
+            RawAsciiString::<4>::serialize_field("TOO LONG", "name"),
+            Err(Error::InvalidValue(_))
+        ));
+        assert!(RawAsciiString([0xFF, b' ']).deserialize().is_err());
+    }
+}
@@ -0,0 +1,55 @@
+/// Decodes the items read from a bit area, which hold a bit each.
+pub fn decode_bit_items(bytes: &[u8]) -> Vec<bool> {
+    bytes.iter().map(|&byte| byte & 1 != 0).collect()
+}
+
+/// Encodes `bits` as the items written to a bit area.
+pub fn encode_bit_items(bits: &[bool]) -> Vec<u8> {
+    bits.iter().map(|&bit| bit as u8).collect()
+}
+
+/// The `count` bits starting at bit `first` of the words in `bytes`, as read from a word area.
+/// Bit 0 is the least significant bit of the first word, bit 16 that of the second word.
+pub fn word_bits(bytes: &[u8], first: usize, count: usize) -> Vec<bool> {
+    assert!((first + count) <= bytes.len() * 8);
+    (first..first + count)
+        .map(|bit| bytes[byte_index(bit)] >> (bit % 8) & 1 != 0)
+        .collect()
+}
+
+/// Replaces the bits starting at bit `first` of the words in `bytes` by `bits`, numbering the
+/// bits like [`word_bits`].
+pub fn set_word_bits(bytes: &mut [u8], first: usize, bits: &[bool]) {
+    assert!((first + bits.len()) <= bytes.len() * 8);
+    for (bit, &value) in (first..).zip(bits) {
+        let mask = 1 << (bit % 8);
+        if value {
+            bytes[byte_index(bit)] |= mask;
+        } else {
+            bytes[byte_index(bit)] &= !mask;
+        }
+    }
+}
+
+/// The index of the byte holding `bit` in big endian words.
+fn byte_index(bit: usize) -> usize {
+    bit / 16 * 2 + 1 - bit % 16 / 8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn word_bits_works() {
+        let mut bytes = [0x80, 0x01, 0x00, 0x02];
+        assert_eq!(word_bits(&bytes, 15, 3), [true, false, true]);
+        assert_eq!(word_bits(&bytes, 0, 1), [true]);
+
+        set_word_bits(&mut bytes, 14, &[true, false, false, true]);
+        assert_eq!(bytes, [0x40, 0x01, 0x00, 0x02]);
+
+        assert_eq!(decode_bit_items(&[0x00, 0x01]), [false, true]);
+        assert_eq!(encode_bit_items(&[true, false]), [0x01, 0x00]);
+    }
+}
@@ -0,0 +1,94 @@
+use std::io::Write;
+
+use crate::*;
+
+/// Broadcasts `bytes` to all nodes on the network. The nodes do not respond, instead each node
+/// counts the receptions which can be read with [`BroadcastTestResultsReadRequest`]. Send this
+/// command to [`MachineAddress::BROADCAST_NODE`].
+pub struct BroadcastTestDataSendRequest {
+    pub bytes: Vec<u8>,
+}
+
+impl BroadcastTestDataSendRequest {
+    pub const MAX_BYTE_COUNT: usize = 2000;
+}
+
+impl Command for BroadcastTestDataSendRequest {
+    const MRC: u8 = 0x08;
+    const SRC: u8 = 0x03;
+    const RESPONSE_REQUIRED: bool = false;
+
+    type Response = ();
+
+    fn encoded_len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    fn encode_body<W: Write>(&self, writer: &mut W) -> crate::Result<()> {
+        if self.bytes.len() > Self::MAX_BYTE_COUNT {
+            return Err(Error::InvalidValue(format!(
+                "broadcast test data of {} bytes exceeds {} bytes",
+                self.bytes.len(),
+                Self::MAX_BYTE_COUNT
+            )));
+        }
+
+        writer.write_all(&self.bytes)?;
+        Ok(())
+    }
+
+    fn decode_response(&self, bytes: &[u8]) -> Result<Self::Response, ProtocolViolation> {
+        decode_empty_response(bytes)
+    }
+
+    fn decode_body(bytes: &[u8]) -> Result<Self, ProtocolViolation> {
+        Ok(Self {
+            bytes: bytes.to_vec(),
+        })
+    }
+
+    fn encode_response<W: Write>(_response: &(), _writer: &mut W) -> crate::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_request_works() {
+        let mut output = vec![];
+        write_request(
+            &mut output,
+            &Route::local(MachineAddress::BROADCAST_NODE, 0xFB),
+            0x03,
+            &BroadcastTestDataSendRequest {
+                bytes: vec![0xAA, 0x55],
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            output,
+            [
+                0x81, // ICF: command without response
+                0x00, // RSV
+                0x02, // GCT: gateway count 2
+                0x00, 0xFF, 0x00, // dst addr: all nodes
+                0x00, 0xFB, 0x00, // src addr
+                0x03, // SID
+                0x08, 0x03, // request code: broadcast test data send
+                0xAA, 0x55, // data
+            ]
+        );
+
+        let request = BroadcastTestDataSendRequest {
+            bytes: vec![0; BroadcastTestDataSendRequest::MAX_BYTE_COUNT + 1],
+        };
+        assert!(matches!(
+            request.encode_body(&mut vec![]),
+            Err(Error::InvalidValue(_))
+        ));
+    }
+}
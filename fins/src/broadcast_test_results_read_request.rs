@@ -0,0 +1,37 @@
+use std::io::Write;
+
+use crate::*;
+
+/// Reads how many times the server received broadcast test data since the last read.
+pub struct BroadcastTestResultsReadRequest;
+
+impl Command for BroadcastTestResultsReadRequest {
+    const MRC: u8 = 0x08;
+    const SRC: u8 = 0x02;
+
+    type Response = BroadcastTestResultsReadResponse;
+
+    fn encoded_len(&self) -> usize {
+        0
+    }
+
+    fn encode_body<W: Write>(&self, _writer: &mut W) -> crate::Result<()> {
+        Ok(())
+    }
+
+    fn decode_response(&self, bytes: &[u8]) -> Result<Self::Response, ProtocolViolation> {
+        BroadcastTestResultsReadResponse::from_bytes(bytes)
+    }
+
+    fn decode_body(bytes: &[u8]) -> Result<Self, ProtocolViolation> {
+        assert_body_length(bytes.len(), 0)?;
+        Ok(Self)
+    }
+
+    fn encode_response<W: Write>(
+        response: &BroadcastTestResultsReadResponse,
+        writer: &mut W,
+    ) -> crate::Result<()> {
+        response.write_to(writer)
+    }
+}
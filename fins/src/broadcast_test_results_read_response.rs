@@ -0,0 +1,25 @@
+use std::io::Write;
+
+use crate::*;
+
+/// The body of a broadcast test results read response, following the response code.
+#[derive(Debug, Eq, PartialEq)]
+pub struct BroadcastTestResultsReadResponse {
+    pub reception_count: u16,
+}
+
+impl BroadcastTestResultsReadResponse {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProtocolViolation> {
+        let (reception_count, bytes) = split_raw::<u16be>(bytes)?;
+        assert_body_length(bytes.len(), 0)?;
+
+        Ok(Self {
+            reception_count: reception_count.to_u16(),
+        })
+    }
+
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_raw(&u16be::from_u16(self.reception_count))?;
+        Ok(())
+    }
+}
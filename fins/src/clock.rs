@@ -0,0 +1,122 @@
+use std::io::Write;
+
+use crate::*;
+
+/// The clock of a CPU unit. The year holds the rightmost two digits and the day of the week
+/// counts from 0 for Sunday.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Clock {
+    pub year: u8,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+    pub day_of_week: u8,
+}
+
+impl Clock {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProtocolViolation> {
+        let (clock, bytes) = split_raw::<RawClock>(bytes)?;
+        assert_body_length(bytes.len(), 0)?;
+        clock.deserialize()
+    }
+
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_raw(&self.serialize()?)?;
+        Ok(())
+    }
+
+    /// Encodes every field as 2 BCD digits. Fields have to be less than 100.
+    pub fn serialize(&self) -> crate::Result<RawClock> {
+        Ok(RawClock {
+            year: to_bcd_u8(self.year)?,
+            month: to_bcd_u8(self.month)?,
+            day: to_bcd_u8(self.day)?,
+            hour: to_bcd_u8(self.hour)?,
+            minute: to_bcd_u8(self.minute)?,
+            second: to_bcd_u8(self.second)?,
+            day_of_week: to_bcd_u8(self.day_of_week)?,
+        })
+    }
+}
+
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+#[repr(C, packed)]
+pub struct RawClock {
+    pub year: u8,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+    pub day_of_week: u8,
+}
+
+unsafe_impl_raw!(RawClock);
+
+impl RawClock {
+    pub fn deserialize(self) -> Result<Clock, ProtocolViolation> {
+        Ok(Clock {
+            year: from_bcd_u8(self.year)?,
+            month: from_bcd_u8(self.month)?,
+            day: from_bcd_u8(self.day)?,
+            hour: from_bcd_u8(self.hour)?,
+            minute: from_bcd_u8(self.minute)?,
+            second: from_bcd_u8(self.second)?,
+            day_of_week: from_bcd_u8(self.day_of_week)?,
+        })
+    }
+}
+
+fn from_bcd_u8(value: u8) -> Result<u8, ProtocolViolation> {
+    match from_bcd_u32(value as u32) {
+        Some(value) => Ok(value as u8),
+        None => Err(ProtocolViolation::InvalidBcd(value as u32)),
+    }
+}
+
+fn to_bcd_u8(value: u8) -> crate::Result<u8> {
+    match to_bcd_u32(value as u32) {
+        Some(bcd) if value < 100 => Ok(bcd as u8),
+        _ => Err(Error::InvalidValue(format!(
+            "clock field {} does not fit in 2 digits",
+            value
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bidir_works() {
+        let bytes = [0x24, 0x02, 0x29, 0x13, 0x45, 0x07, 0x04];
+        let clock = Clock {
+            year: 24,
+            month: 2,
+            day: 29,
+            hour: 13,
+            minute: 45,
+            second: 7,
+            day_of_week: 4,
+        };
+        assert_eq!(Clock::from_bytes(&bytes).unwrap(), clock);
+
+        let mut output = vec![];
+        clock.write_to(&mut output).unwrap();
+        assert_eq!(output, bytes);
+
+        assert!(matches!(
+            Clock::from_bytes(&[0x24, 0x1A, 0x29, 0x13, 0x45, 0x07, 0x04]),
+            Err(ProtocolViolation::InvalidBcd(0x1A))
+        ));
+
+        let clock = Clock { hour: 100, ..clock };
+        assert!(matches!(
+            clock.write_to(&mut vec![]),
+            Err(Error::InvalidValue(_))
+        ));
+    }
+}
@@ -0,0 +1,68 @@
+use std::io::Write;
+
+use crate::*;
+
+/// Reads the clock of the CPU unit.
+pub struct ClockReadRequest;
+
+impl Command for ClockReadRequest {
+    const MRC: u8 = 0x07;
+    const SRC: u8 = 0x01;
+
+    type Response = Clock;
+
+    fn encoded_len(&self) -> usize {
+        0
+    }
+
+    fn encode_body<W: Write>(&self, _writer: &mut W) -> crate::Result<()> {
+        Ok(())
+    }
+
+    fn decode_response(&self, bytes: &[u8]) -> Result<Self::Response, ProtocolViolation> {
+        Clock::from_bytes(bytes)
+    }
+
+    fn decode_body(bytes: &[u8]) -> Result<Self, ProtocolViolation> {
+        assert_body_length(bytes.len(), 0)?;
+        Ok(Self)
+    }
+
+    fn encode_response<W: Write>(response: &Clock, writer: &mut W) -> crate::Result<()> {
+        response.write_to(writer)
+    }
+}
+
+/// Sets the clock of the CPU unit.
+pub struct ClockWriteRequest {
+    pub clock: Clock,
+}
+
+impl Command for ClockWriteRequest {
+    const MRC: u8 = 0x07;
+    const SRC: u8 = 0x02;
+
+    type Response = ();
+
+    fn encoded_len(&self) -> usize {
+        ::std::mem::size_of::<RawClock>()
+    }
+
+    fn encode_body<W: Write>(&self, writer: &mut W) -> crate::Result<()> {
+        self.clock.write_to(writer)
+    }
+
+    fn decode_response(&self, bytes: &[u8]) -> Result<Self::Response, ProtocolViolation> {
+        decode_empty_response(bytes)
+    }
+
+    fn decode_body(bytes: &[u8]) -> Result<Self, ProtocolViolation> {
+        Ok(Self {
+            clock: Clock::from_bytes(bytes)?,
+        })
+    }
+
+    fn encode_response<W: Write>(_response: &(), _writer: &mut W) -> crate::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,243 @@
+use std::io::Write;
+
+use crate::*;
+
+/// A FINS command that can be sent to a server.
+///
+/// The command code and body length are known before serialization so that the frame, including
+/// any transport header carrying its length, can be written sequentially.
+pub trait Command {
+    /// Main Request Code
+    const MRC: u8;
+
+    /// Sub Request Code
+    const SRC: u8;
+
+    /// Whether the server sends a response to this command.
+    const RESPONSE_REQUIRED: bool = true;
+
+    /// The body of the response, following the response code.
+    type Response;
+
+    /// Number of bytes written by [`Command::encode_body`].
+    fn encoded_len(&self) -> usize;
+
+    /// Writes the command body, following the command code.
+    fn encode_body<W: Write>(&self, writer: &mut W) -> crate::Result<()>;
+
+    /// Decodes the response body, following the response code, to this command.
+    fn decode_response(&self, bytes: &[u8]) -> Result<Self::Response, ProtocolViolation>;
+
+    /// Decodes the command body, following the command code.
+    fn decode_body(bytes: &[u8]) -> Result<Self, ProtocolViolation>
+    where
+        Self: Sized;
+
+    /// Writes the response body, following the response code.
+    fn encode_response<W: Write>(response: &Self::Response, writer: &mut W) -> crate::Result<()>;
+}
+
+/// Number of bytes in the request frame for `command`, including the FINS header and command code.
+pub fn request_byte_size<C: Command>(command: &C) -> usize {
+    std::mem::size_of::<RawHeader>()
+        + std::mem::size_of::<RawRequestHeader>()
+        + command.encoded_len()
+}
+
+/// Writes the request frame for `command` sent along `route`.
+pub fn write_request<W: Write, C: Command>(
+    writer: &mut W,
+    route: &Route,
+    service_id: u8,
+    command: &C,
+) -> crate::Result<()> {
+    let icf = if C::RESPONSE_REQUIRED {
+        InformationControlField::RequestWithResponse
+    } else {
+        InformationControlField::RequestWithoutResponse
+    };
+    writer.write_raw(&route.header(icf, service_id).serialize())?;
+    writer.write_raw(&RawRequestHeader {
+        mrc: C::MRC,
+        src: C::SRC,
+    })?;
+    command.encode_body(writer)?;
+    Ok(())
+}
+
+/// A response frame with its body decoded.
+#[derive(Debug)]
+pub struct Response<T> {
+    pub header: Header,
+    pub end_code: EndCode,
+    pub body: T,
+}
+
+/// Decodes the response `frame` to `command`. Responses that do not complete normally are returned
+/// as [`Error::EndCode`].
+pub fn read_response<C: Command>(
+    command: &C,
+    frame: &[u8],
+) -> crate::Result<Response<C::Response>> {
+    let (header, bytes) = split_raw::<RawHeader>(frame)?;
+    let header = header.deserialize()?;
+    if header.icf.is_request() {
+        return Err(ProtocolViolation::UnexpectedRequest.into());
+    }
+
+    let (
+        RawResponseHeader {
+            mrc,
+            src,
+            mres,
+            sres,
+        },
+        bytes,
+    ) = split_raw::<RawResponseHeader>(bytes)?;
+    assert_command_code([mrc, src], [C::MRC, C::SRC])?;
+
+    let end_code = EndCode::new(mres, sres);
+    if !end_code.is_normal_completion() {
+        return Err(Error::EndCode(end_code));
+    }
+
+    Ok(Response {
+        header,
+        end_code,
+        body: command.decode_response(bytes)?,
+    })
+}
+
+/// A request frame of which the body has not been decoded yet.
+#[derive(Debug)]
+pub struct Request<'a> {
+    pub header: Header,
+    pub command_code: [u8; 2],
+    pub body: &'a [u8],
+}
+
+impl<'a> Request<'a> {
+    pub fn from_bytes(frame: &'a [u8]) -> Result<Self, ProtocolViolation> {
+        let (header, bytes) = split_raw::<RawHeader>(frame)?;
+        let header = header.deserialize()?;
+        if !header.icf.is_request() {
+            return Err(ProtocolViolation::UnexpectedResponse);
+        }
+        let (RawRequestHeader { mrc, src }, body) = split_raw::<RawRequestHeader>(bytes)?;
+
+        Ok(Self {
+            header,
+            command_code: [mrc, src],
+            body,
+        })
+    }
+
+    /// Decodes the body as command `C`.
+    pub fn decode<C: Command>(&self) -> Result<C, ProtocolViolation> {
+        assert_command_code(self.command_code, [C::MRC, C::SRC])?;
+        C::decode_body(self.body)
+    }
+}
+
+/// Writes the response frame to `request`. The body is only written on normal completion.
+pub fn write_response<W: Write, C: Command>(
+    writer: &mut W,
+    request: &Request,
+    response: &Result<C::Response, EndCode>,
+) -> crate::Result<()> {
+    match response {
+        Ok(response) => {
+            write_response_header(writer, request, EndCode::NORMAL_COMPLETION)?;
+            C::encode_response(response, writer)
+        }
+        Err(end_code) => write_response_header(writer, request, *end_code),
+    }
+}
+
+/// Writes the header and response code of the response frame to `request`.
+pub fn write_response_header<W: Write>(
+    writer: &mut W,
+    request: &Request,
+    end_code: EndCode,
+) -> crate::Result<()> {
+    writer.write_raw(&request.header.response().serialize())?;
+    writer.write_raw(&RawResponseHeader {
+        mrc: request.command_code[0],
+        src: request.command_code[1],
+        mres: end_code.mres,
+        sres: end_code.sres,
+    })?;
+    Ok(())
+}
+
+/// Decodes a response body that should be empty.
+pub(crate) fn decode_empty_response(bytes: &[u8]) -> Result<(), ProtocolViolation> {
+    assert_body_length(bytes.len(), 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_response_checks_command_code() {
+        let command = NameReadRequest;
+        let mut frame = vec![0xC0, 0x00, 0x02, 0x00, 0xFB, 0x00, 0x00, 0xD3, 0x00, 0x01];
+        frame.extend_from_slice(&[0x26, 0x03, 0x00, 0x00]);
+        frame.extend_from_slice(b"LINE4   ");
+
+        let response = read_response(&command, &frame).unwrap();
+        assert_eq!(response.header.sid, 0x01);
+        assert_eq!(response.body.name, "LINE4");
+
+        frame[11] = 0x02;
+        assert!(matches!(
+            read_response(&command, &frame),
+            Err(Error::ProtocolViolation(
+                ProtocolViolation::UnexpectedCommandCode {
+                    actual: [0x26, 0x02],
+                    expected: [0x26, 0x03],
+                }
+            ))
+        ));
+    }
+
+    #[test]
+    fn write_response_works() {
+        let frame = [
+            0x80, 0x00, 0x02, 0x00, 0xD3, 0x00, 0x00, 0xFB, 0x00, 0x07, 0x26, 0x03,
+        ];
+        let request = Request::from_bytes(&frame).unwrap();
+        request.decode::<NameReadRequest>().unwrap();
+        assert!(request.decode::<NameDeleteRequest>().is_err());
+
+        let mut output = vec![];
+        write_response::<_, NameReadRequest>(
+            &mut output,
+            &request,
+            &Ok(NameReadResponse {
+                name: "LINE4".to_string(),
+            }),
+        )
+        .unwrap();
+
+        let response = read_response(&NameReadRequest, &output).unwrap();
+        assert_eq!(response.header, request.header.response());
+        assert_eq!(response.body.name, "LINE4");
+    }
+
+    #[test]
+    fn read_response_returns_end_code() {
+        let frame = [
+            0xC0, 0x00, 0x02, 0x00, 0xFB, 0x00, 0x00, 0xD3, 0x00, 0x01, 0x26, 0x03, 0x22, 0x03,
+        ];
+
+        assert!(matches!(
+            read_response(&NameReadRequest, &frame),
+            Err(Error::EndCode(EndCode {
+                mres: 0x22,
+                sres: 0x03
+            }))
+        ));
+    }
+}
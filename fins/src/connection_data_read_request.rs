@@ -0,0 +1,71 @@
+use std::io::Write;
+
+use crate::*;
+
+/// Reads the model numbers of up to `unit_count` units starting at `unit_address`. The CPU unit
+/// has unit address 0x00 and CPU bus units have 0x10 plus their unit number.
+pub struct ConnectionDataReadRequest {
+    pub unit_address: u8,
+    pub unit_count: u8,
+}
+
+impl ConnectionDataReadRequest {
+    pub const MAX_UNIT_COUNT: u8 = 0x19;
+}
+
+impl Command for ConnectionDataReadRequest {
+    const MRC: u8 = 0x05;
+    const SRC: u8 = 0x02;
+
+    type Response = ConnectionDataReadResponse;
+
+    fn encoded_len(&self) -> usize {
+        ::std::mem::size_of::<RawConnectionDataReadRequestBody>()
+    }
+
+    fn encode_body<W: Write>(&self, writer: &mut W) -> crate::Result<()> {
+        if !(1..=Self::MAX_UNIT_COUNT).contains(&self.unit_count) {
+            return Err(Error::InvalidValue(format!(
+                "unit count {} should be between 1 and {}",
+                self.unit_count,
+                Self::MAX_UNIT_COUNT
+            )));
+        }
+
+        writer.write_raw(&RawConnectionDataReadRequestBody {
+            unit_address: self.unit_address,
+            unit_count: self.unit_count,
+        })?;
+
+        Ok(())
+    }
+
+    fn decode_response(&self, bytes: &[u8]) -> Result<Self::Response, ProtocolViolation> {
+        ConnectionDataReadResponse::from_bytes(bytes)
+    }
+
+    fn decode_body(bytes: &[u8]) -> Result<Self, ProtocolViolation> {
+        let (body, bytes) = split_raw::<RawConnectionDataReadRequestBody>(bytes)?;
+        assert_body_length(bytes.len(), 0)?;
+
+        Ok(Self {
+            unit_address: body.unit_address,
+            unit_count: body.unit_count,
+        })
+    }
+
+    fn encode_response<W: Write>(
+        response: &ConnectionDataReadResponse,
+        writer: &mut W,
+    ) -> crate::Result<()> {
+        response.write_to(writer)
+    }
+}
+
+#[repr(C, packed)]
+struct RawConnectionDataReadRequestBody {
+    unit_address: u8,
+    unit_count: u8,
+}
+
+unsafe_impl_raw!(RawConnectionDataReadRequestBody);
@@ -0,0 +1,146 @@
+use std::convert::TryFrom;
+use std::io::Write;
+
+use crate::*;
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct UnitModel {
+    pub unit_address: u8,
+    pub model: String,
+}
+
+/// The body of a connection data read response, following the response code.
+#[derive(Debug, Eq, PartialEq)]
+pub struct ConnectionDataReadResponse {
+    pub units: Vec<UnitModel>,
+    /// Whether `units` include the last mounted unit.
+    pub includes_last_unit: bool,
+}
+
+impl ConnectionDataReadResponse {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProtocolViolation> {
+        let (unit_count, mut bytes) = split_raw::<u8>(bytes)?;
+        let includes_last_unit = unit_count & 0x80 != 0;
+        let unit_count = (unit_count & 0x7F) as usize;
+        assert_body_length(
+            bytes.len(),
+            unit_count * std::mem::size_of::<RawUnitModel>(),
+        )?;
+
+        let mut units = Vec::with_capacity(unit_count);
+        for _ in 0..unit_count {
+            let (
+                RawUnitModel {
+                    unit_address,
+                    model,
+                },
+                rest,
+            ) = split_raw::<RawUnitModel>(bytes)?;
+            bytes = rest;
+            units.push(UnitModel {
+                unit_address,
+                model: model.deserialize()?,
+            });
+        }
+
+        Ok(Self {
+            units,
+            includes_last_unit,
+        })
+    }
+
+    /// Writes the units. There can be at most [`ConnectionDataReadRequest::MAX_UNIT_COUNT`] units
+    /// of which the models have to be at most 20 ASCII characters.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> crate::Result<()> {
+        let max_count = ConnectionDataReadRequest::MAX_UNIT_COUNT;
+        let unit_count = match u8::try_from(self.units.len()) {
+            Ok(count) if count <= max_count => count,
+            _ => {
+                return Err(Error::InvalidValue(format!(
+                    "{} units exceed {} units",
+                    self.units.len(),
+                    max_count
+                )))
+            }
+        };
+        writer.write_raw(&if self.includes_last_unit {
+            unit_count | 0x80
+        } else {
+            unit_count
+        })?;
+        for unit in &self.units {
+            writer.write_raw(&RawUnitModel {
+                unit_address: unit.unit_address,
+                model: RawAsciiString::serialize_field(&unit.model, "model")?,
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+#[repr(C, packed)]
+struct RawUnitModel {
+    unit_address: u8,
+    model: RawAsciiString<20>,
+}
+
+unsafe_impl_raw!(RawUnitModel);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_bytes_works() {
+        let mut bytes = vec![0x82];
+        bytes.push(0x00);
+        bytes.extend_from_slice(b"CJ2M-CPU33          ");
+        bytes.push(0x10);
+        bytes.extend_from_slice(b"CJ1W-ETN21\0\0\0\0\0\0\0\0\0\0");
+
+        assert_eq!(
+            ConnectionDataReadResponse::from_bytes(&bytes).unwrap(),
+            ConnectionDataReadResponse {
+                units: vec![
+                    UnitModel {
+                        unit_address: 0x00,
+                        model: "CJ2M-CPU33".to_string(),
+                    },
+                    UnitModel {
+                        unit_address: 0x10,
+                        model: "CJ1W-ETN21".to_string(),
+                    },
+                ],
+                includes_last_unit: true,
+            }
+        );
+    }
+
+    #[test]
+    fn write_to_checks_units() {
+        let unit = |model: &str| UnitModel {
+            unit_address: 0x00,
+            model: model.to_string(),
+        };
+        let mut response = ConnectionDataReadResponse {
+            units: (0..ConnectionDataReadRequest::MAX_UNIT_COUNT)
+                .map(|_| unit("CJ2M-CPU33"))
+                .collect(),
+            includes_last_unit: false,
+        };
+        assert!(response.write_to(&mut vec![]).is_ok());
+
+        response.units.push(unit("CJ2M-CPU33"));
+        assert!(matches!(
+            response.write_to(&mut vec![]),
+            Err(Error::InvalidValue(_))
+        ));
+
+        response.units = vec![unit("A MODEL LONGER THAN 20")];
+        assert!(matches!(
+            response.write_to(&mut vec![]),
+            Err(Error::InvalidValue(_))
+        ));
+    }
+}
@@ -0,0 +1,46 @@
+use std::io::Write;
+
+use crate::*;
+
+/// Reads the model and version of the CPU unit along with its area data.
+pub struct ControllerDataReadRequest;
+
+impl Command for ControllerDataReadRequest {
+    const MRC: u8 = 0x05;
+    const SRC: u8 = 0x01;
+
+    type Response = ControllerDataReadResponse;
+
+    fn encoded_len(&self) -> usize {
+        1
+    }
+
+    fn encode_body<W: Write>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_raw(&CONTROLLER_MODEL_AND_VERSION)?;
+        Ok(())
+    }
+
+    fn decode_response(&self, bytes: &[u8]) -> Result<Self::Response, ProtocolViolation> {
+        ControllerDataReadResponse::from_bytes(bytes)
+    }
+
+    fn decode_body(bytes: &[u8]) -> Result<Self, ProtocolViolation> {
+        let (parameter, bytes) = split_raw::<u8>(bytes)?;
+        assert_body_length(bytes.len(), 0)?;
+        if parameter != CONTROLLER_MODEL_AND_VERSION {
+            return Err(ProtocolViolation::InvalidControllerDataParameter(parameter));
+        }
+        Ok(Self)
+    }
+
+    fn encode_response<W: Write>(
+        response: &ControllerDataReadResponse,
+        writer: &mut W,
+    ) -> crate::Result<()> {
+        response.write_to(writer)
+    }
+}
+
+/// Selects the model, version and area data. Other parameters select data that differs between
+/// series of CPU units.
+const CONTROLLER_MODEL_AND_VERSION: u8 = 0x00;
@@ -0,0 +1,48 @@
+use std::io::Write;
+
+use crate::*;
+
+/// The body of a controller data read response, following the response code.
+#[derive(Debug, Eq, PartialEq)]
+pub struct ControllerDataReadResponse {
+    pub model: String,
+    pub version: String,
+    /// The sizes of the program and memory areas, the layout depends on the series of the CPU
+    /// unit.
+    pub area_data: [u8; 12],
+}
+
+impl ControllerDataReadResponse {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProtocolViolation> {
+        let (body, bytes) = split_raw::<RawControllerDataReadResponseBody>(bytes)?;
+        assert_body_length(bytes.len(), 0)?;
+
+        Ok(Self {
+            model: body.model.deserialize()?,
+            version: body.version.deserialize()?,
+            area_data: body.area_data,
+        })
+    }
+
+    /// Writes the data. The model and version have to be at most 20 ASCII characters.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_raw(&RawControllerDataReadResponseBody {
+            model: RawAsciiString::serialize_field(&self.model, "model")?,
+            version: RawAsciiString::serialize_field(&self.version, "version")?,
+            system: [0; 40],
+            area_data: self.area_data,
+        })?;
+
+        Ok(())
+    }
+}
+
+#[repr(C, packed)]
+struct RawControllerDataReadResponseBody {
+    model: RawAsciiString<20>,
+    version: RawAsciiString<20>,
+    system: [u8; 40],
+    area_data: [u8; 12],
+}
+
+unsafe_impl_raw!(RawControllerDataReadResponseBody);
@@ -0,0 +1,37 @@
+use std::io::Write;
+
+use crate::*;
+
+/// Reads the operating status, mode and errors of the CPU unit.
+pub struct ControllerStatusReadRequest;
+
+impl Command for ControllerStatusReadRequest {
+    const MRC: u8 = 0x06;
+    const SRC: u8 = 0x01;
+
+    type Response = ControllerStatusReadResponse;
+
+    fn encoded_len(&self) -> usize {
+        0
+    }
+
+    fn encode_body<W: Write>(&self, _writer: &mut W) -> crate::Result<()> {
+        Ok(())
+    }
+
+    fn decode_response(&self, bytes: &[u8]) -> Result<Self::Response, ProtocolViolation> {
+        ControllerStatusReadResponse::from_bytes(bytes)
+    }
+
+    fn decode_body(bytes: &[u8]) -> Result<Self, ProtocolViolation> {
+        assert_body_length(bytes.len(), 0)?;
+        Ok(Self)
+    }
+
+    fn encode_response<W: Write>(
+        response: &ControllerStatusReadResponse,
+        writer: &mut W,
+    ) -> crate::Result<()> {
+        response.write_to(writer)
+    }
+}
@@ -0,0 +1,150 @@
+use std::io::Write;
+
+use crate::*;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum OperatingStatus {
+    Stop,
+    Run,
+    /// The CPU unit waits for a start signal.
+    Standby,
+}
+
+impl OperatingStatus {
+    pub const fn serialize(&self) -> u8 {
+        match self {
+            OperatingStatus::Stop => 0x00,
+            OperatingStatus::Run => 0x01,
+            OperatingStatus::Standby => 0x80,
+        }
+    }
+
+    pub const fn deserialize(value: u8) -> Result<Self, ProtocolViolation> {
+        match value {
+            0x00 => Ok(OperatingStatus::Stop),
+            0x01 => Ok(OperatingStatus::Run),
+            0x80 => Ok(OperatingStatus::Standby),
+            unknown => Err(ProtocolViolation::InvalidOperatingStatus(unknown)),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum OperatingMode {
+    Program,
+    Debug,
+    Monitor,
+    Run,
+}
+
+impl OperatingMode {
+    pub const fn serialize(&self) -> u8 {
+        match self {
+            OperatingMode::Program => 0x00,
+            OperatingMode::Debug => 0x01,
+            OperatingMode::Monitor => 0x02,
+            OperatingMode::Run => 0x04,
+        }
+    }
+
+    pub const fn deserialize(value: u8) -> Result<Self, ProtocolViolation> {
+        match value {
+            0x00 => Ok(OperatingMode::Program),
+            0x01 => Ok(OperatingMode::Debug),
+            0x02 => Ok(OperatingMode::Monitor),
+            0x04 => Ok(OperatingMode::Run),
+            unknown => Err(ProtocolViolation::InvalidOperatingMode(unknown)),
+        }
+    }
+}
+
+/// The body of a controller status read response, following the response code.
+#[derive(Debug, Eq, PartialEq)]
+pub struct ControllerStatusReadResponse {
+    pub status: OperatingStatus,
+    pub mode: OperatingMode,
+    /// Fatal error flags, 0 when there is no fatal error.
+    pub fatal_error: u16,
+    /// Non-fatal error flags, 0 when there is no non-fatal error.
+    pub non_fatal_error: u16,
+    /// The MSG(195) messages that are present, bit 0 being message 0.
+    pub messages: u8,
+    /// The highest priority FAL/FALS error, 0 when there is none.
+    pub fal_number: u16,
+    /// The message of the FAL/FALS error, empty when there is none.
+    pub error_message: String,
+}
+
+impl ControllerStatusReadResponse {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProtocolViolation> {
+        let (body, bytes) = split_raw::<RawControllerStatusReadResponseBody>(bytes)?;
+        assert_body_length(bytes.len(), 0)?;
+
+        Ok(Self {
+            status: OperatingStatus::deserialize(body.status)?,
+            mode: OperatingMode::deserialize(body.mode)?,
+            fatal_error: body.fatal_error.to_u16(),
+            non_fatal_error: body.non_fatal_error.to_u16(),
+            messages: body.messages.to_u16() as u8,
+            fal_number: body.fal_number.to_u16(),
+            error_message: body.error_message.deserialize()?,
+        })
+    }
+
+    /// Writes the status. The error message should be at most 16 ASCII characters.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_raw(&RawControllerStatusReadResponseBody {
+            status: self.status.serialize(),
+            mode: self.mode.serialize(),
+            fatal_error: u16be::from_u16(self.fatal_error),
+            non_fatal_error: u16be::from_u16(self.non_fatal_error),
+            messages: u16be::from_u16(self.messages as u16),
+            fal_number: u16be::from_u16(self.fal_number),
+            error_message: RawAsciiString::serialize(&self.error_message)
+                .expect("error message should be at most 16 ASCII characters"),
+        })?;
+
+        Ok(())
+    }
+}
+
+#[repr(C, packed)]
+struct RawControllerStatusReadResponseBody {
+    status: u8,
+    mode: u8,
+    fatal_error: u16be,
+    non_fatal_error: u16be,
+    messages: u16be,
+    fal_number: u16be,
+    error_message: RawAsciiString<16>,
+}
+
+unsafe_impl_raw!(RawControllerStatusReadResponseBody);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bidir_works() {
+        let mut bytes = vec![0x01, 0x02, 0x00, 0x00, 0x80, 0x00, 0x00, 0x03, 0x41, 0x05];
+        bytes.extend_from_slice(b"LOW PRESSURE    ");
+        let response = ControllerStatusReadResponse {
+            status: OperatingStatus::Run,
+            mode: OperatingMode::Monitor,
+            fatal_error: 0x0000,
+            non_fatal_error: 0x8000,
+            messages: 0b0000_0011,
+            fal_number: 0x4105,
+            error_message: "LOW PRESSURE".to_string(),
+        };
+        assert_eq!(
+            ControllerStatusReadResponse::from_bytes(&bytes).unwrap(),
+            response
+        );
+
+        let mut output = vec![];
+        response.write_to(&mut output).unwrap();
+        assert_eq!(output, bytes);
+    }
+}
@@ -0,0 +1,98 @@
+use std::io::Write;
+
+use crate::*;
+
+/// Reads the average, maximum and minimum cycle time.
+pub struct CycleTimeReadRequest;
+
+impl Command for CycleTimeReadRequest {
+    const MRC: u8 = 0x06;
+    const SRC: u8 = 0x20;
+
+    type Response = CycleTimeReadResponse;
+
+    fn encoded_len(&self) -> usize {
+        1
+    }
+
+    fn encode_body<W: Write>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_raw(&CYCLE_TIME_READ)?;
+        Ok(())
+    }
+
+    fn decode_response(&self, bytes: &[u8]) -> Result<Self::Response, ProtocolViolation> {
+        CycleTimeReadResponse::from_bytes(bytes)
+    }
+
+    fn decode_body(bytes: &[u8]) -> Result<Self, ProtocolViolation> {
+        decode_cycle_time_parameter(bytes, CYCLE_TIME_READ)?;
+        Ok(Self)
+    }
+
+    fn encode_response<W: Write>(
+        response: &CycleTimeReadResponse,
+        writer: &mut W,
+    ) -> crate::Result<()> {
+        response.write_to(writer)
+    }
+}
+
+/// Resets the cycle time history.
+pub struct CycleTimeInitializeRequest;
+
+impl Command for CycleTimeInitializeRequest {
+    const MRC: u8 = 0x06;
+    const SRC: u8 = 0x20;
+
+    type Response = ();
+
+    fn encoded_len(&self) -> usize {
+        1
+    }
+
+    fn encode_body<W: Write>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_raw(&CYCLE_TIME_INITIALIZE)?;
+        Ok(())
+    }
+
+    fn decode_response(&self, bytes: &[u8]) -> Result<Self::Response, ProtocolViolation> {
+        decode_empty_response(bytes)
+    }
+
+    fn decode_body(bytes: &[u8]) -> Result<Self, ProtocolViolation> {
+        decode_cycle_time_parameter(bytes, CYCLE_TIME_INITIALIZE)?;
+        Ok(Self)
+    }
+
+    fn encode_response<W: Write>(_response: &(), _writer: &mut W) -> crate::Result<()> {
+        Ok(())
+    }
+}
+
+const CYCLE_TIME_INITIALIZE: u8 = 0x00;
+const CYCLE_TIME_READ: u8 = 0x01;
+
+fn decode_cycle_time_parameter(bytes: &[u8], expected: u8) -> Result<(), ProtocolViolation> {
+    let (parameter, bytes) = split_raw::<u8>(bytes)?;
+    assert_body_length(bytes.len(), 0)?;
+    if parameter != expected {
+        return Err(ProtocolViolation::InvalidCycleTimeParameter(parameter));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_body_works() {
+        let mut output = vec![];
+        CycleTimeReadRequest.encode_body(&mut output).unwrap();
+        assert_eq!(output, [0x01]);
+
+        let mut output = vec![];
+        CycleTimeInitializeRequest.encode_body(&mut output).unwrap();
+        assert_eq!(output, [0x00]);
+    }
+}
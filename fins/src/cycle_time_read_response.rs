@@ -0,0 +1,109 @@
+use std::convert::TryFrom;
+use std::io::Write;
+use std::time::Duration;
+
+use crate::*;
+
+/// The body of a cycle time read response, following the response code.
+#[derive(Debug, Eq, PartialEq)]
+pub struct CycleTimeReadResponse {
+    pub average: Duration,
+    pub max: Duration,
+    pub min: Duration,
+}
+
+impl CycleTimeReadResponse {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProtocolViolation> {
+        let (body, bytes) = split_raw::<RawCycleTimeReadResponseBody>(bytes)?;
+        assert_body_length(bytes.len(), 0)?;
+        let RawCycleTimeReadResponseBody { average, max, min } = body;
+
+        Ok(Self {
+            average: cycle_time_from_bcd(average)?,
+            max: cycle_time_from_bcd(max)?,
+            min: cycle_time_from_bcd(min)?,
+        })
+    }
+
+    /// Writes the cycle times, truncated to 0.1 ms. Cycle times have to be less than 10^8 tenths
+    /// of a millisecond.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_raw(&RawCycleTimeReadResponseBody {
+            average: cycle_time_to_bcd(self.average)?,
+            max: cycle_time_to_bcd(self.max)?,
+            min: cycle_time_to_bcd(self.min)?,
+        })?;
+
+        Ok(())
+    }
+}
+
+/// Cycle times are 8-digit BCD values in units of 0.1 ms.
+fn cycle_time_from_bcd(value: u32be) -> Result<Duration, ProtocolViolation> {
+    let value = value.to_u32();
+    match from_bcd_u32(value) {
+        Some(tenths) => Ok(Duration::from_micros(tenths as u64 * 100)),
+        None => Err(ProtocolViolation::InvalidBcd(value)),
+    }
+}
+
+fn cycle_time_to_bcd(value: Duration) -> crate::Result<u32be> {
+    u32::try_from(value.as_micros() / 100)
+        .ok()
+        .and_then(to_bcd_u32)
+        .map(u32be::from_u32)
+        .ok_or_else(|| {
+            Error::InvalidValue(format!("cycle time {:?} does not fit in 8 digits", value))
+        })
+}
+
+#[repr(C, packed)]
+struct RawCycleTimeReadResponseBody {
+    average: u32be,
+    max: u32be,
+    min: u32be,
+}
+
+unsafe_impl_raw!(RawCycleTimeReadResponseBody);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_bytes_works() {
+        let bytes = [
+            0x00, 0x00, 0x06, 0x50, // average: 65 ms
+            0x00, 0x00, 0x10, 0x05, // max: 100.5 ms
+            0x00, 0x00, 0x02, 0x95, // min: 29.5 ms
+        ];
+        let response = CycleTimeReadResponse {
+            average: Duration::from_millis(65),
+            max: Duration::from_micros(100_500),
+            min: Duration::from_micros(29_500),
+        };
+        assert_eq!(CycleTimeReadResponse::from_bytes(&bytes).unwrap(), response);
+
+        let mut output = vec![];
+        response.write_to(&mut output).unwrap();
+        assert_eq!(output, bytes);
+
+        assert!(matches!(
+            CycleTimeReadResponse::from_bytes(&[0x00, 0x00, 0x06, 0x5A, 0, 0, 0, 0, 0, 0, 0, 0]),
+            Err(ProtocolViolation::InvalidBcd(0x0000_065A))
+        ));
+    }
+
+    #[test]
+    fn write_to_checks_cycle_times() {
+        let response = CycleTimeReadResponse {
+            average: Duration::from_millis(65),
+            max: Duration::from_secs(10_000),
+            min: Duration::from_micros(29_500),
+        };
+        assert!(matches!(
+            response.write_to(&mut vec![]),
+            Err(Error::InvalidValue(_))
+        ));
+    }
+}
@@ -0,0 +1,115 @@
+/// The response code of a FINS response, consisting of the main response code (MRES) and the
+/// sub-response code (SRES) including their flag bits.
+#[derive(Copy, Clone, Eq, PartialEq)]
+pub struct EndCode {
+    pub mres: u8,
+    pub sres: u8,
+}
+
+impl EndCode {
+    pub const NORMAL_COMPLETION: Self = Self::new(0x00, 0x00);
+    pub const UNDEFINED_COMMAND: Self = Self::new(0x04, 0x01);
+    pub const COMMAND_TOO_LONG: Self = Self::new(0x10, 0x01);
+    pub const COMMAND_TOO_SHORT: Self = Self::new(0x10, 0x02);
+    pub const COMMAND_FORMAT_ERROR: Self = Self::new(0x10, 0x04);
+    pub const NO_AREA_TYPE: Self = Self::new(0x11, 0x01);
+    pub const ADDRESS_OUT_OF_RANGE: Self = Self::new(0x11, 0x03);
+    pub const ADDRESS_RANGE_EXCEEDED: Self = Self::new(0x11, 0x04);
+    pub const INCORRECT_PARAMETER_CODE: Self = Self::new(0x11, 0x0C);
+    pub const MEMORY_ERROR: Self = Self::new(0x25, 0x02);
+
+    pub const fn new(mres: u8, sres: u8) -> Self {
+        Self { mres, sres }
+    }
+
+    /// The main response code without the network relay error flag.
+    pub const fn main_code(&self) -> u8 {
+        self.mres & 0x7F
+    }
+
+    /// The sub-response code without the CPU error flags.
+    pub const fn sub_code(&self) -> u8 {
+        self.sres & 0x3F
+    }
+
+    /// Whether the command was executed. The CPU error flags do not affect the outcome of the
+    /// command.
+    pub const fn is_normal_completion(&self) -> bool {
+        self.main_code() == 0x00 && self.sub_code() == 0x00
+    }
+
+    pub const fn is_network_relay_error(&self) -> bool {
+        self.mres & 0x80 != 0
+    }
+
+    pub const fn is_fatal_cpu_error(&self) -> bool {
+        self.sres & 0x80 != 0
+    }
+
+    pub const fn is_non_fatal_cpu_error(&self) -> bool {
+        self.sres & 0x40 != 0
+    }
+
+    /// Describes the class of the main response code.
+    pub const fn description(&self) -> &'static str {
+        match self.main_code() {
+            0x00 => "normal completion",
+            0x01 => "local node error",
+            0x02 => "destination node error",
+            0x03 => "communications controller error",
+            0x04 => "not executable",
+            0x05 => "routing error",
+            0x10 => "command format error",
+            0x11 => "parameter error",
+            0x20 => "read not possible",
+            0x21 => "write not possible",
+            0x22 => "not executable in current mode",
+            0x23 => "no unit",
+            0x24 => "start/stop not possible",
+            0x25 => "unit error",
+            0x26 => "command error",
+            0x30 => "access right error",
+            0x40 => "abort",
+            _ => "unknown error",
+        }
+    }
+}
+
+impl std::fmt::Debug for EndCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "EndCode(0x{:02X}{:02X})", self.mres, self.sres)
+    }
+}
+
+impl std::fmt::Display for EndCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "FINS end code 0x{:02X}{:02X} ({})",
+            self.main_code(),
+            self.sub_code(),
+            self.description()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_do_not_affect_completion() {
+        let end_code = EndCode::new(0x00, 0x40);
+        assert!(end_code.is_normal_completion());
+        assert!(end_code.is_non_fatal_cpu_error());
+        assert!(!end_code.is_fatal_cpu_error());
+
+        let end_code = EndCode::new(0x81, 0x03);
+        assert!(!end_code.is_normal_completion());
+        assert!(end_code.is_network_relay_error());
+        assert_eq!(
+            end_code.to_string(),
+            "FINS end code 0x0103 (local node error)"
+        );
+    }
+}
@@ -4,6 +4,10 @@ use crate::*;
 pub enum Error {
     ProtocolViolation(ProtocolViolation),
     Io(std::io::Error),
+    /// The server responded with an end code other than normal completion.
+    EndCode(EndCode),
+    /// A value can not be encoded, for example because it does not fit in its field.
+    InvalidValue(String),
 }
 
 impl std::fmt::Display for Error {
@@ -11,6 +15,8 @@ impl std::fmt::Display for Error {
         match self {
             Self::ProtocolViolation(e) => e.fmt(f),
             Self::Io(e) => e.fmt(f),
+            Self::EndCode(e) => e.fmt(f),
+            Self::InvalidValue(e) => write!(f, "Invalid FINS value: {}", e),
         }
     }
 }
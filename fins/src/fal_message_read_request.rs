@@ -0,0 +1,80 @@
+use std::io::Write;
+
+use crate::*;
+
+/// Reads the error message of FAL/FALS number `number`.
+pub struct FalMessageReadRequest {
+    pub number: u16,
+}
+
+impl Command for FalMessageReadRequest {
+    const MRC: u8 = 0x09;
+    const SRC: u8 = 0x20;
+
+    type Response = FalMessageReadResponse;
+
+    fn encoded_len(&self) -> usize {
+        ::std::mem::size_of::<u16be>()
+    }
+
+    fn encode_body<W: Write>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_raw(&u16be::from_u16(fal_message_parameter(self.number)?))?;
+        Ok(())
+    }
+
+    fn decode_response(&self, bytes: &[u8]) -> Result<Self::Response, ProtocolViolation> {
+        FalMessageReadResponse::from_bytes(bytes)
+    }
+
+    fn decode_body(bytes: &[u8]) -> Result<Self, ProtocolViolation> {
+        let (parameter, bytes) = split_raw::<u16be>(bytes)?;
+        assert_body_length(bytes.len(), 0)?;
+        let parameter = parameter.to_u16();
+        if parameter & MESSAGE_KIND_MASK != FAL_MESSAGE_READ {
+            return Err(ProtocolViolation::InvalidMessageParameter(parameter));
+        }
+
+        Ok(Self {
+            number: parameter & !MESSAGE_KIND_MASK,
+        })
+    }
+
+    fn encode_response<W: Write>(
+        response: &FalMessageReadResponse,
+        writer: &mut W,
+    ) -> crate::Result<()> {
+        response.write_to(writer)
+    }
+}
+
+/// The parameter that selects FAL/FALS number `number`, which has to leave the bits of the kind
+/// of message clear.
+pub(crate) fn fal_message_parameter(number: u16) -> crate::Result<u16> {
+    if number & MESSAGE_KIND_MASK != 0 {
+        return Err(Error::InvalidValue(format!(
+            "FAL number {} should be below 0x{:04X}",
+            number, MESSAGE_KIND_MASK
+        )));
+    }
+    Ok(FAL_MESSAGE_READ | number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_body_works() {
+        let mut output = vec![];
+        FalMessageReadRequest { number: 0x0123 }
+            .encode_body(&mut output)
+            .unwrap();
+
+        assert_eq!(output, [0x81, 0x23]);
+
+        assert!(matches!(
+            FalMessageReadRequest { number: 0x4000 }.encode_body(&mut vec![]),
+            Err(Error::InvalidValue(_))
+        ));
+    }
+}
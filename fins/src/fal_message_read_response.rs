@@ -0,0 +1,76 @@
+use std::io::Write;
+
+use crate::*;
+
+/// The body of a FAL/FALS read response, following the response code.
+#[derive(Debug, Eq, PartialEq)]
+pub struct FalMessageReadResponse {
+    pub number: u16,
+    /// The error message, empty when there is no error.
+    pub text: String,
+}
+
+impl FalMessageReadResponse {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProtocolViolation> {
+        let (body, bytes) = split_raw::<RawFalMessageReadResponseBody>(bytes)?;
+        assert_body_length(bytes.len(), 0)?;
+        let parameter = body.parameter.to_u16();
+        if parameter & MESSAGE_KIND_MASK != FAL_MESSAGE_READ {
+            return Err(ProtocolViolation::InvalidMessageParameter(parameter));
+        }
+
+        Ok(Self {
+            number: parameter & !MESSAGE_KIND_MASK,
+            text: body.text.deserialize()?,
+        })
+    }
+
+    /// Writes the message. The text has to be at most 16 ASCII characters.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_raw(&RawFalMessageReadResponseBody {
+            parameter: u16be::from_u16(fal_message_parameter(self.number)?),
+            text: RawAsciiString::serialize_field(&self.text, "FAL message")?,
+        })?;
+
+        Ok(())
+    }
+}
+
+#[repr(C, packed)]
+struct RawFalMessageReadResponseBody {
+    parameter: u16be,
+    text: RawAsciiString<16>,
+}
+
+unsafe_impl_raw!(RawFalMessageReadResponseBody);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_bytes_works() {
+        let mut bytes = vec![0x80, 0x07];
+        bytes.extend_from_slice(b"LOW AIR PRESSURE");
+
+        assert_eq!(
+            FalMessageReadResponse::from_bytes(&bytes).unwrap(),
+            FalMessageReadResponse {
+                number: 7,
+                text: "LOW AIR PRESSURE".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn write_to_checks_text() {
+        let response = FalMessageReadResponse {
+            number: 7,
+            text: "AIR PRESSURE TOO LOW".to_string(),
+        };
+        assert!(matches!(
+            response.write_to(&mut vec![]),
+            Err(Error::InvalidValue(_))
+        ));
+    }
+}
@@ -0,0 +1,165 @@
+use crate::ProtocolViolation;
+
+pub use fins_derive::FinsStruct;
+
+/// The order in which the words of values of more than one word are stored.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum WordOrder {
+    /// The least significant word first, as CS/CJ series units store DINT and REAL values.
+    LowFirst,
+    HighFirst,
+}
+
+/// A value stored in one or more consecutive words.
+pub trait FinsValue: Sized {
+    const WORD_COUNT: usize;
+
+    /// Decodes the value from the first `WORD_COUNT` words of `bytes`.
+    fn decode_words(bytes: &[u8], word_order: WordOrder) -> Self;
+
+    /// Encodes the value into the first `WORD_COUNT` words of `bytes`.
+    fn encode_words(&self, bytes: &mut [u8], word_order: WordOrder);
+}
+
+macro_rules! impl_fins_value {
+    ($($ty:ty),*) => {$(
+        impl FinsValue for $ty {
+            const WORD_COUNT: usize = std::mem::size_of::<$ty>() / 2;
+
+            fn decode_words(bytes: &[u8], word_order: WordOrder) -> Self {
+                let mut value = [0; std::mem::size_of::<$ty>()];
+                value.copy_from_slice(&bytes[..std::mem::size_of::<$ty>()]);
+                order_words(&mut value, word_order);
+                <$ty>::from_be_bytes(value)
+            }
+
+            fn encode_words(&self, bytes: &mut [u8], word_order: WordOrder) {
+                let mut value = self.to_be_bytes();
+                order_words(&mut value, word_order);
+                bytes[..value.len()].copy_from_slice(&value);
+            }
+        }
+    )*};
+}
+
+impl_fins_value!(i16, u16, i32, u32, f32, i64, u64, f64);
+
+impl<T: FinsValue, const N: usize> FinsValue for [T; N] {
+    const WORD_COUNT: usize = T::WORD_COUNT * N;
+
+    fn decode_words(bytes: &[u8], word_order: WordOrder) -> Self {
+        std::array::from_fn(|i| T::decode_words(&bytes[i * T::WORD_COUNT * 2..], word_order))
+    }
+
+    fn encode_words(&self, bytes: &mut [u8], word_order: WordOrder) {
+        for (i, value) in self.iter().enumerate() {
+            value.encode_words(&mut bytes[i * T::WORD_COUNT * 2..], word_order);
+        }
+    }
+}
+
+/// Converts the big endian bytes of a value to words in `word_order` and back.
+fn order_words(bytes: &mut [u8], word_order: WordOrder) {
+    if word_order == WordOrder::LowFirst {
+        let word_count = bytes.len() / 2;
+        for i in 0..word_count / 2 {
+            let j = word_count - 1 - i;
+            bytes.swap(2 * i, 2 * j);
+            bytes.swap(2 * i + 1, 2 * j + 1);
+        }
+    }
+}
+
+/// A struct stored in consecutive words, like a user defined data type in the PLC, which is
+/// read and written as a whole. Derive it to map the fields onto words:
+///
+/// ```
+/// use fins::FinsStruct;
+///
+/// #[derive(FinsStruct)]
+/// struct Assign {
+///     step: i16, // D1500
+///     #[fins(offset = 2)]
+///     target: i32, // D1502 and D1503
+///     #[fins(bit = 0)]
+///     busy: bool, // D1504.00
+///     done: bool, // D1504.01
+///     #[fins(offset = 8)]
+///     sequence_number: u16, // D1508
+/// }
+///
+/// assert_eq!(Assign::WORD_COUNT, 9);
+/// ```
+///
+/// Fields are INT (`i16`), UINT or WORD (`u16`), DINT (`i32`), UDINT or DWORD (`u32`), REAL
+/// (`f32`), their 64-bit counterparts, arrays of those and bits (`bool`). A field without `offset`
+/// follows the field before it and a bit without `bit` follows the bit before it in the same
+/// word. Values of more than one word are stored low word first, unless `word_order =
+/// "high_first"` is given for the struct or the field.
+pub trait FinsStruct: Sized {
+    /// Number of words the struct occupies.
+    const WORD_COUNT: usize;
+
+    /// Decodes the struct from the bytes read from its words.
+    fn decode(bytes: &[u8]) -> Result<Self, ProtocolViolation>;
+
+    /// Encodes the fields into the bytes of `WORD_COUNT` words. Bits that are not fields keep their
+    /// value, so that words read from the unit can be updated.
+    fn encode_into(&self, bytes: &mut [u8]);
+
+    /// The bytes to write to the words of the struct. Bits that are not fields are 0.
+    fn encode(&self) -> Vec<u8> {
+        let mut bytes = vec![0; Self::WORD_COUNT * 2];
+        self.encode_into(&mut bytes);
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, FinsStruct)]
+    struct Assign {
+        step: i16,
+        #[fins(offset = 2)]
+        target: i32,
+        #[fins(word_order = "high_first")]
+        speed: f32,
+        #[fins(bit = 1)]
+        busy: bool,
+        done: bool,
+        #[fins(bit = 15)]
+        error: bool,
+        counts: [u16; 2],
+        #[fins(offset = 9)]
+        sequence_number: u16,
+    }
+
+    #[test]
+    fn derive_works() {
+        assert_eq!(Assign::WORD_COUNT, 10);
+        let assign = Assign {
+            step: -2,
+            target: 0x0001_0002,
+            speed: 1.5,
+            busy: true,
+            done: false,
+            error: true,
+            counts: [3, 4],
+            sequence_number: 0x1508,
+        };
+        let bytes = [
+            0xFF, 0xFE, 0x00, 0x00, 0x00, 0x02, 0x00, 0x01, 0x3F, 0xC0, 0x00, 0x00, 0x80, 0x02,
+            0x00, 0x03, 0x00, 0x04, 0x15, 0x08,
+        ];
+        assert_eq!(assign.encode(), bytes);
+        assert_eq!(Assign::decode(&bytes).unwrap(), assign);
+        assert!(Assign::decode(&bytes[2..]).is_err());
+
+        let mut words = [0x55; 20];
+        assign.encode_into(&mut words);
+        assert_eq!(words[2..4], [0x55, 0x55]);
+        assert_eq!(words[12..14], [0xD5, 0x53]);
+    }
+}
@@ -29,6 +29,7 @@ impl RawHeader {
     pub const fn deserialize(self) -> Result<Header, ProtocolViolation> {
         Ok(Header {
             icf: try_const!(self.icf.deserialize()),
+            use_gateway: self.icf.use_gateway(),
             gct: self.gct,
             destination: self.destination.deserialize(),
             source: self.source.deserialize(),
@@ -37,10 +38,12 @@ impl RawHeader {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub struct Header {
     pub icf: InformationControlField,
 
+    pub use_gateway: bool,
+
     pub gct: u8,
 
     pub destination: MachineAddress,
@@ -53,7 +56,7 @@ pub struct Header {
 impl Header {
     pub const fn serialize(&self) -> RawHeader {
         RawHeader {
-            icf: self.icf.serialize(),
+            icf: self.icf.serialize_with_gateway(self.use_gateway),
             rsv: 0x00,
             gct: self.gct,
             destination: self.destination.serialize(),
@@ -61,6 +64,30 @@ impl Header {
             sid: self.sid,
         }
     }
+
+    pub fn write_to<W: std::io::Write>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_raw(&self.serialize())?;
+        Ok(())
+    }
+
+    /// Reads the header at the start of `frame`.
+    pub fn from_bytes(frame: &[u8]) -> Result<Self, ProtocolViolation> {
+        let (header, _) = split_raw::<RawHeader>(frame)?;
+        header.deserialize()
+    }
+
+    /// The header of the response to a request with this header. The addresses are swapped and
+    /// the service id is echoed.
+    pub const fn response(&self) -> Self {
+        Self {
+            icf: InformationControlField::ResponseWithResponse,
+            use_gateway: self.use_gateway,
+            gct: self.gct,
+            destination: self.source,
+            source: self.destination,
+            sid: self.sid,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -71,6 +98,7 @@ mod tests {
     fn header_to_bytes_works() {
         let input = Header {
             icf: InformationControlField::RequestWithResponse,
+            use_gateway: true,
             gct: 0x02,
             destination: MachineAddress {
                 network: 0x03,
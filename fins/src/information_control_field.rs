@@ -24,8 +24,15 @@ impl InformationControlField {
         matches!(self, Self::RequestWithResponse | Self::ResponseWithResponse)
     }
 
+    /// Serializes with the gateway bit set.
     pub const fn serialize(&self) -> RawInformationControlField {
-        let bits = 0b10000000 | (!self.is_request() as u8) << 6 | !self.requires_response() as u8;
+        self.serialize_with_gateway(true)
+    }
+
+    pub const fn serialize_with_gateway(&self, use_gateway: bool) -> RawInformationControlField {
+        let bits = (use_gateway as u8) << 7
+            | (!self.is_request() as u8) << 6
+            | !self.requires_response() as u8;
         RawInformationControlField(bits)
     }
 }
@@ -35,9 +42,13 @@ impl InformationControlField {
 pub struct RawInformationControlField(u8);
 
 impl RawInformationControlField {
+    pub const fn use_gateway(self) -> bool {
+        test_bits_u8(self.0, 1 << 7)
+    }
+
     pub const fn deserialize(self) -> Result<InformationControlField, ProtocolViolation> {
         let bits = self.0;
-        if bits & 0b10111110 != 0b10000000 {
+        if bits & 0b00111110 != 0 {
             return Err(ProtocolViolation::InvalidInformationControlField(self));
         }
         let is_request = !test_bits_u8(bits, 1 << 6);
@@ -81,4 +92,18 @@ mod tests {
             RawInformationControlField(0b11000001),
         );
     }
+
+    #[test]
+    fn gateway_bit_works() {
+        let raw = InformationControlField::RequestWithResponse.serialize_with_gateway(false);
+        assert_eq!(raw, RawInformationControlField(0b00000000));
+        assert!(!raw.use_gateway());
+        assert_eq!(
+            raw.deserialize().unwrap(),
+            InformationControlField::RequestWithResponse
+        );
+        assert!(RawInformationControlField(0b10000010)
+            .deserialize()
+            .is_err());
+    }
 }
@@ -1,22 +1,103 @@
 #![macro_use]
 
+// Lets the code generated by `#[derive(FinsStruct)]` refer to `::fins` within this crate.
+extern crate self as fins;
+
+mod ascii_string;
+mod bits;
+mod broadcast_test_data_send_request;
+mod broadcast_test_results_read_request;
+mod broadcast_test_results_read_response;
+mod clock;
+mod clock_read_request;
+mod command;
+mod connection_data_read_request;
+mod connection_data_read_response;
+mod controller_data_read_request;
+mod controller_data_read_response;
+mod controller_status_read_request;
+mod controller_status_read_response;
+mod cycle_time_read_request;
+mod cycle_time_read_response;
+mod end_code;
 mod error;
+mod fal_message_read_request;
+mod fal_message_read_response;
+mod fins_struct;
 mod header;
 mod information_control_field;
+mod loopback_test_request;
+mod loopback_test_response;
 mod machine_address;
 mod memory_address;
 mod memory_area_code;
+mod memory_area_fill_request;
 mod memory_area_read_request;
+mod memory_area_read_response;
+mod memory_area_transfer_request;
+mod memory_area_write_request;
+mod message_clear_request;
+mod message_read_request;
+mod message_read_response;
+mod multiple_memory_area_read_request;
+mod name_read_request;
+mod name_read_response;
+mod name_set_request;
+mod parameter_area_clear_request;
+mod parameter_area_code;
+mod parameter_area_read_request;
+mod parameter_area_read_response;
+mod parameter_area_write_request;
 mod protocol_violation;
+mod route;
 
+pub use ascii_string::*;
+pub use bits::*;
+pub use broadcast_test_data_send_request::*;
+pub use broadcast_test_results_read_request::*;
+pub use broadcast_test_results_read_response::*;
+pub use clock::*;
+pub use clock_read_request::*;
+pub use command::*;
+pub use connection_data_read_request::*;
+pub use connection_data_read_response::*;
+pub use controller_data_read_request::*;
+pub use controller_data_read_response::*;
+pub use controller_status_read_request::*;
+pub use controller_status_read_response::*;
+pub use cycle_time_read_request::*;
+pub use cycle_time_read_response::*;
+pub use end_code::*;
 pub use error::*;
+pub use fal_message_read_request::*;
+pub use fal_message_read_response::*;
+pub use fins_struct::*;
 pub use header::*;
 pub use information_control_field::*;
+pub use loopback_test_request::*;
+pub use loopback_test_response::*;
 pub use machine_address::*;
 pub use memory_address::*;
 pub use memory_area_code::*;
+pub use memory_area_fill_request::*;
 pub use memory_area_read_request::*;
+pub use memory_area_read_response::*;
+pub use memory_area_transfer_request::*;
+pub use memory_area_write_request::*;
+pub use message_clear_request::*;
+pub use message_read_request::*;
+pub use message_read_response::*;
+pub use multiple_memory_area_read_request::*;
+pub use name_read_request::*;
+pub use name_read_response::*;
+pub use name_set_request::*;
+pub use parameter_area_clear_request::*;
+pub use parameter_area_code::*;
+pub use parameter_area_read_request::*;
+pub use parameter_area_read_response::*;
+pub use parameter_area_write_request::*;
 pub use protocol_violation::*;
+pub use route::*;
 
 use fins_util::*;
 
@@ -30,22 +111,6 @@ pub struct RawRequestHeader {
     pub src: u8,
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
-pub enum RequestHeader {
-    MemoryAreaRead,
-}
-
-impl RequestHeader {
-    pub fn to_raw(self) -> RawRequestHeader {
-        match self {
-            Self::MemoryAreaRead => RawRequestHeader {
-                mrc: 0x01,
-                src: 0x01,
-            },
-        }
-    }
-}
-
 unsafe_impl_raw!(RawRequestHeader);
 
 #[derive(Debug, Default)]
@@ -65,3 +130,19 @@ pub struct RawResponseHeader {
 }
 
 unsafe_impl_raw!(RawResponseHeader);
+
+/// Splits a `T` off the front of a response body.
+pub(crate) fn split_raw<T: Raw>(bytes: &[u8]) -> Result<(T, &[u8]), ProtocolViolation> {
+    let size = std::mem::size_of::<T>();
+    if bytes.len() < size {
+        return Err(ProtocolViolation::BodyTooShort {
+            actual: bytes.len(),
+            expected: size,
+        });
+    }
+    let (mut head, tail) = bytes.split_at(size);
+    let raw = head
+        .read_raw::<T>()
+        .expect("slice is at least as long as T");
+    Ok((raw, tail))
+}
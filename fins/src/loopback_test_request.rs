@@ -0,0 +1,90 @@
+use std::io::Write;
+
+use crate::*;
+
+/// Asks the server to echo `bytes` back. The response is checked to match.
+pub struct LoopbackTestRequest {
+    pub bytes: Vec<u8>,
+}
+
+impl LoopbackTestRequest {
+    pub const MAX_BYTE_COUNT: usize = 1998;
+}
+
+impl Command for LoopbackTestRequest {
+    const MRC: u8 = 0x08;
+    const SRC: u8 = 0x01;
+
+    type Response = LoopbackTestResponse;
+
+    fn encoded_len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    fn encode_body<W: Write>(&self, writer: &mut W) -> crate::Result<()> {
+        if self.bytes.len() > Self::MAX_BYTE_COUNT {
+            return Err(Error::InvalidValue(format!(
+                "loopback test data of {} bytes exceeds {} bytes",
+                self.bytes.len(),
+                Self::MAX_BYTE_COUNT
+            )));
+        }
+
+        writer.write_all(&self.bytes)?;
+        Ok(())
+    }
+
+    fn decode_response(&self, bytes: &[u8]) -> Result<Self::Response, ProtocolViolation> {
+        if bytes != &self.bytes[..] {
+            return Err(ProtocolViolation::LoopbackMismatch);
+        }
+        LoopbackTestResponse::from_bytes(bytes)
+    }
+
+    fn decode_body(bytes: &[u8]) -> Result<Self, ProtocolViolation> {
+        Ok(Self {
+            bytes: bytes.to_vec(),
+        })
+    }
+
+    fn encode_response<W: Write>(
+        response: &LoopbackTestResponse,
+        writer: &mut W,
+    ) -> crate::Result<()> {
+        response.write_to(writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_response_compares_echo() {
+        let request = LoopbackTestRequest {
+            bytes: vec![0x01, 0x02, 0x03],
+        };
+
+        assert_eq!(
+            request.decode_response(&[0x01, 0x02, 0x03]).unwrap(),
+            LoopbackTestResponse {
+                bytes: vec![0x01, 0x02, 0x03]
+            }
+        );
+        assert!(matches!(
+            request.decode_response(&[0x01, 0x02]),
+            Err(ProtocolViolation::LoopbackMismatch)
+        ));
+    }
+
+    #[test]
+    fn encode_body_checks_length() {
+        let request = LoopbackTestRequest {
+            bytes: vec![0; LoopbackTestRequest::MAX_BYTE_COUNT + 1],
+        };
+        assert!(matches!(
+            request.encode_body(&mut vec![]),
+            Err(Error::InvalidValue(_))
+        ));
+    }
+}
@@ -0,0 +1,22 @@
+use std::io::Write;
+
+use crate::*;
+
+/// The body of a loopback test response, following the response code.
+#[derive(Debug, Eq, PartialEq)]
+pub struct LoopbackTestResponse {
+    pub bytes: Vec<u8>,
+}
+
+impl LoopbackTestResponse {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProtocolViolation> {
+        Ok(Self {
+            bytes: bytes.to_vec(),
+        })
+    }
+
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_all(&self.bytes)?;
+        Ok(())
+    }
+}
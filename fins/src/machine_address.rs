@@ -25,7 +25,7 @@ impl RawMachineAddress {
     }
 }
 
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub struct MachineAddress {
     pub network: u8,
     pub node: u8,
@@ -33,6 +33,40 @@ pub struct MachineAddress {
 }
 
 impl MachineAddress {
+    /// Network address 0 refers to the local network.
+    pub const LOCAL_NETWORK: u8 = 0x00;
+
+    /// Node address 0xFF broadcasts to all nodes on the network.
+    pub const BROADCAST_NODE: u8 = 0xFF;
+
+    /// Unit address of the CPU unit.
+    pub const CPU_UNIT: u8 = 0x00;
+
+    /// The CPU unit of `node` on `network`.
+    pub const fn cpu(network: u8, node: u8) -> Self {
+        Self {
+            network,
+            node,
+            unit: Self::CPU_UNIT,
+        }
+    }
+
+    /// Number of CPU bus units, numbered 0 to 15.
+    pub const CPU_BUS_UNIT_COUNT: u8 = 16;
+
+    /// The CPU bus unit with unit number `unit_number` of `node` on `network`. Returns `None` when
+    /// the unit number exceeds 15.
+    pub const fn cpu_bus_unit(network: u8, node: u8, unit_number: u8) -> Option<Self> {
+        if unit_number >= Self::CPU_BUS_UNIT_COUNT {
+            return None;
+        }
+        Some(Self {
+            network,
+            node,
+            unit: 0x10 + unit_number,
+        })
+    }
+
     pub const fn serialize(&self) -> RawMachineAddress {
         let &Self {
             network,
@@ -8,6 +8,8 @@ pub struct RawMemoryAddress {
     bits: u8,
 }
 
+unsafe_impl_raw!(RawMemoryAddress);
+
 impl RawMemoryAddress {
     pub const fn deserialize(self) -> Result<MemoryAddress, ProtocolViolation> {
         let RawMemoryAddress {
@@ -38,14 +40,97 @@ impl MemoryAddress {
             bits: self.bits,
         }
     }
+
+    /// The address `count` items after this one. Returns `None` when that address lies beyond the
+    /// end of the area.
+    pub fn offset_by(&self, count: usize) -> Option<Self> {
+        let word_count = self.area_code.word_count();
+        let (offset, bits) = if self.area_code.is_bit_area() {
+            let bit = self.offset as usize * 16 + self.bits as usize + count;
+            if bit > word_count * 16 {
+                return None;
+            }
+            (bit / 16, (bit % 16) as u8)
+        } else {
+            (self.offset as usize + count, self.bits)
+        };
+        if offset > word_count {
+            return None;
+        }
+        Some(MemoryAddress {
+            area_code: self.area_code,
+            offset: offset as u16,
+            bits,
+        })
+    }
 }
 
 impl std::fmt::Debug for MemoryAddress {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        if self.bits == 0 {
-            write!(f, "{:?}{}", self.area_code, self.offset)
+        if self.bits == 0 && !self.area_code.is_bit_area() {
+            write!(f, "{}{}", self.area_code, self.offset)
+        } else {
+            write!(f, "{}{}.{}", self.area_code, self.offset, self.bits)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ParseMemoryAddressError(pub String);
+
+impl std::fmt::Display for ParseMemoryAddressError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Invalid memory address {:?}", self.0)
+    }
+}
+
+impl std::error::Error for ParseMemoryAddressError {}
+
+/// Parses addresses in the notation of the programming software, like `D100`, `CIO10.03` or
+/// `E2_100`. Addresses with a bit number refer to the bit area.
+impl std::str::FromStr for MemoryAddress {
+    type Err = ParseMemoryAddressError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let error = || ParseMemoryAddressError(text.to_string());
+        let upper = text.trim().to_ascii_uppercase();
+        let (word, bits) = match upper.split_once('.') {
+            Some((word, bits)) => (word, Some(bits)),
+            None => (upper.as_str(), None),
+        };
+
+        let (area_code, offset) = if let Some(offset) = word.strip_prefix("CIO") {
+            (MemoryAreaCode::Cio, offset)
+        } else if let Some((bank, offset)) = word.strip_prefix('E').and_then(|w| w.split_once('_'))
+        {
+            let bank = u8::from_str_radix(bank, 16).map_err(|_| error())?;
+            (MemoryAreaCode::em(bank).ok_or_else(error)?, offset)
         } else {
-            write!(f, "{:?}{}.{}", self.area_code, self.offset, self.bits)
+            let area_code = match word.chars().next() {
+                Some('W') => MemoryAreaCode::W,
+                Some('H') => MemoryAreaCode::H,
+                Some('A') => MemoryAreaCode::A,
+                Some('D') => MemoryAreaCode::D,
+                _ => return Err(error()),
+            };
+            (area_code, &word[1..])
+        };
+        let offset = offset.parse().map_err(|_| error())?;
+
+        match bits {
+            None => Ok(MemoryAddress {
+                area_code,
+                offset,
+                bits: 0,
+            }),
+            Some(bits) => match bits.parse() {
+                Ok(bits @ 0..=15) => Ok(MemoryAddress {
+                    area_code: area_code.bit_area(),
+                    offset,
+                    bits,
+                }),
+                _ => Err(error()),
+            },
         }
     }
 }
@@ -75,4 +160,41 @@ mod tests {
     fn layout_is_nice() {
         assert_eq!(std::mem::size_of::<MemoryAddress>(), 4);
     }
+
+    #[test]
+    fn offset_by_works() {
+        let address = |text: &str| text.parse::<MemoryAddress>().unwrap();
+        assert_eq!(address("D100").offset_by(899), Some(address("D999")));
+        assert_eq!(address("W10.14").offset_by(3), Some(address("W11.01")));
+        assert_eq!(address("W511").offset_by(1), Some(address("W512")));
+        assert_eq!(address("W511").offset_by(2), None);
+        assert_eq!(address("W511.15").offset_by(2), None);
+    }
+
+    #[test]
+    fn from_str_works() {
+        for (text, area_code, offset, bits) in [
+            ("D100", MemoryAreaCode::D, 100, 0),
+            ("cio10.03", MemoryAreaCode::CioBit, 10, 3),
+            ("W5", MemoryAreaCode::W, 5, 0),
+            ("EC_32767", MemoryAreaCode::E12, 32767, 0),
+        ] {
+            let address = text.parse::<MemoryAddress>().unwrap();
+            assert_eq!(
+                address,
+                MemoryAddress {
+                    area_code,
+                    offset,
+                    bits
+                }
+            );
+        }
+        assert_eq!(
+            format!("{:?}", "E2_100.0".parse::<MemoryAddress>().unwrap()),
+            "E2_100.0"
+        );
+        for text in ["X100", "D", "D100.16", "ED_0", "D70000"] {
+            assert!(text.parse::<MemoryAddress>().is_err(), "{}", text);
+        }
+    }
 }
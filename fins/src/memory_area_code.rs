@@ -7,25 +7,249 @@ pub struct RawMemoryAreaCode(pub u8);
 unsafe_impl_raw!(RawMemoryAreaCode);
 
 impl RawMemoryAreaCode {
+    pub const CIO_BIT: Self = Self(0x30);
+    pub const W_BIT: Self = Self(0x31);
+    pub const H_BIT: Self = Self(0x32);
+    pub const A_BIT: Self = Self(0x33);
+    pub const D_BIT: Self = Self(0x02);
+    /// EM bank 0 bits, banks 1 to 12 follow.
+    pub const E_BIT: Self = Self(0x20);
+    pub const CIO: Self = Self(0xB0);
+    pub const W: Self = Self(0xB1);
+    pub const H: Self = Self(0xB2);
+    pub const A: Self = Self(0xB3);
     pub const D: Self = Self(0x82);
+    /// EM bank 0 words, banks 1 to 12 follow.
+    pub const E: Self = Self(0xA0);
 
     pub const fn deserialize(self) -> Result<MemoryAreaCode, ProtocolViolation> {
         match self {
+            RawMemoryAreaCode::CIO_BIT => Ok(MemoryAreaCode::CioBit),
+            RawMemoryAreaCode::W_BIT => Ok(MemoryAreaCode::WBit),
+            RawMemoryAreaCode::H_BIT => Ok(MemoryAreaCode::HBit),
+            RawMemoryAreaCode::A_BIT => Ok(MemoryAreaCode::ABit),
+            RawMemoryAreaCode::D_BIT => Ok(MemoryAreaCode::DBit),
+            RawMemoryAreaCode(code @ 0x20..=0x2C) => Ok(EM_BIT_AREAS[(code - 0x20) as usize]),
+            RawMemoryAreaCode::CIO => Ok(MemoryAreaCode::Cio),
+            RawMemoryAreaCode::W => Ok(MemoryAreaCode::W),
+            RawMemoryAreaCode::H => Ok(MemoryAreaCode::H),
+            RawMemoryAreaCode::A => Ok(MemoryAreaCode::A),
             RawMemoryAreaCode::D => Ok(MemoryAreaCode::D),
+            RawMemoryAreaCode(code @ 0xA0..=0xAC) => Ok(EM_WORD_AREAS[(code - 0xA0) as usize]),
             unknown => Err(ProtocolViolation::InvalidMemoryAreaCode(unknown)),
         }
     }
 }
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// The memory areas of CS/CJ-series CPU units. Word areas are accessed in items of 2 bytes, bit
+/// areas in items of 1 byte. The EM banks are separate variants to keep addresses small.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 pub enum MemoryAreaCode {
+    CioBit,
+    WBit,
+    HBit,
+    ABit,
+    DBit,
+    E0Bit,
+    E1Bit,
+    E2Bit,
+    E3Bit,
+    E4Bit,
+    E5Bit,
+    E6Bit,
+    E7Bit,
+    E8Bit,
+    E9Bit,
+    E10Bit,
+    E11Bit,
+    E12Bit,
+    Cio,
+    W,
+    H,
+    A,
     D,
+    E0,
+    E1,
+    E2,
+    E3,
+    E4,
+    E5,
+    E6,
+    E7,
+    E8,
+    E9,
+    E10,
+    E11,
+    E12,
 }
 
+const EM_WORD_AREAS: [MemoryAreaCode; MemoryAreaCode::EM_BANK_COUNT as usize] = [
+    MemoryAreaCode::E0,
+    MemoryAreaCode::E1,
+    MemoryAreaCode::E2,
+    MemoryAreaCode::E3,
+    MemoryAreaCode::E4,
+    MemoryAreaCode::E5,
+    MemoryAreaCode::E6,
+    MemoryAreaCode::E7,
+    MemoryAreaCode::E8,
+    MemoryAreaCode::E9,
+    MemoryAreaCode::E10,
+    MemoryAreaCode::E11,
+    MemoryAreaCode::E12,
+];
+
+const EM_BIT_AREAS: [MemoryAreaCode; MemoryAreaCode::EM_BANK_COUNT as usize] = [
+    MemoryAreaCode::E0Bit,
+    MemoryAreaCode::E1Bit,
+    MemoryAreaCode::E2Bit,
+    MemoryAreaCode::E3Bit,
+    MemoryAreaCode::E4Bit,
+    MemoryAreaCode::E5Bit,
+    MemoryAreaCode::E6Bit,
+    MemoryAreaCode::E7Bit,
+    MemoryAreaCode::E8Bit,
+    MemoryAreaCode::E9Bit,
+    MemoryAreaCode::E10Bit,
+    MemoryAreaCode::E11Bit,
+    MemoryAreaCode::E12Bit,
+];
+
 impl MemoryAreaCode {
+    pub const EM_BANK_COUNT: u8 = 13;
+
+    /// The word area of EM bank `bank`.
+    pub const fn em(bank: u8) -> Option<Self> {
+        if bank < Self::EM_BANK_COUNT {
+            Some(EM_WORD_AREAS[bank as usize])
+        } else {
+            None
+        }
+    }
+
+    /// The EM bank of this area, if it is an EM area.
+    pub const fn em_bank(&self) -> Option<u8> {
+        let mut bank = 0;
+        while bank < Self::EM_BANK_COUNT {
+            if EM_WORD_AREAS[bank as usize] as u8 == *self as u8
+                || EM_BIT_AREAS[bank as usize] as u8 == *self as u8
+            {
+                return Some(bank);
+            }
+            bank += 1;
+        }
+        None
+    }
+
     pub const fn serialize(&self) -> RawMemoryAreaCode {
         match self {
+            MemoryAreaCode::CioBit => RawMemoryAreaCode::CIO_BIT,
+            MemoryAreaCode::WBit => RawMemoryAreaCode::W_BIT,
+            MemoryAreaCode::HBit => RawMemoryAreaCode::H_BIT,
+            MemoryAreaCode::ABit => RawMemoryAreaCode::A_BIT,
+            MemoryAreaCode::DBit => RawMemoryAreaCode::D_BIT,
+            MemoryAreaCode::Cio => RawMemoryAreaCode::CIO,
+            MemoryAreaCode::W => RawMemoryAreaCode::W,
+            MemoryAreaCode::H => RawMemoryAreaCode::H,
+            MemoryAreaCode::A => RawMemoryAreaCode::A,
             MemoryAreaCode::D => RawMemoryAreaCode::D,
+            em => match em.em_bank() {
+                Some(bank) if em.is_bit_area() => {
+                    RawMemoryAreaCode(RawMemoryAreaCode::E_BIT.0 + bank)
+                }
+                Some(bank) => RawMemoryAreaCode(RawMemoryAreaCode::E.0 + bank),
+                None => unreachable!(),
+            },
+        }
+    }
+
+    pub const fn is_bit_area(&self) -> bool {
+        (*self as u8) < (MemoryAreaCode::Cio as u8)
+    }
+
+    /// The word area that contains this area.
+    pub const fn word_area(&self) -> Self {
+        match self {
+            MemoryAreaCode::CioBit => MemoryAreaCode::Cio,
+            MemoryAreaCode::WBit => MemoryAreaCode::W,
+            MemoryAreaCode::HBit => MemoryAreaCode::H,
+            MemoryAreaCode::ABit => MemoryAreaCode::A,
+            MemoryAreaCode::DBit => MemoryAreaCode::D,
+            area => match area.em_bank() {
+                Some(bank) => EM_WORD_AREAS[bank as usize],
+                None => *area,
+            },
+        }
+    }
+
+    /// The bit area that contains this area.
+    pub const fn bit_area(&self) -> Self {
+        match self {
+            MemoryAreaCode::Cio => MemoryAreaCode::CioBit,
+            MemoryAreaCode::W => MemoryAreaCode::WBit,
+            MemoryAreaCode::H => MemoryAreaCode::HBit,
+            MemoryAreaCode::A => MemoryAreaCode::ABit,
+            MemoryAreaCode::D => MemoryAreaCode::DBit,
+            area => match area.em_bank() {
+                Some(bank) => EM_BIT_AREAS[bank as usize],
+                None => *area,
+            },
+        }
+    }
+
+    /// Number of words in this area on the largest CS/CJ series CPU units. Smaller units respond
+    /// to addresses beyond their areas with an end code.
+    pub const fn word_count(&self) -> usize {
+        match self.word_area() {
+            MemoryAreaCode::Cio => 6144,
+            MemoryAreaCode::W => 512,
+            MemoryAreaCode::H => 1536,
+            MemoryAreaCode::A => 11536,
+            _ => 32768,
+        }
+    }
+
+    /// Number of bytes per item read or written.
+    pub const fn item_size(&self) -> usize {
+        if self.is_bit_area() {
+            1
+        } else {
+            2
+        }
+    }
+}
+
+/// Formats the area prefix as used in addresses, like `D` in `D100` or `E2_` in `E2_100`.
+impl std::fmt::Display for MemoryAreaCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self.word_area() {
+            MemoryAreaCode::Cio => write!(f, "CIO"),
+            MemoryAreaCode::W => write!(f, "W"),
+            MemoryAreaCode::H => write!(f, "H"),
+            MemoryAreaCode::A => write!(f, "A"),
+            MemoryAreaCode::D => write!(f, "D"),
+            em => write!(f, "E{:X}_", em.em_bank().unwrap()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bidir_works() {
+        for code in (0x00..=0xFF).map(RawMemoryAreaCode) {
+            if let Ok(area_code) = code.deserialize() {
+                assert_eq!(area_code.serialize(), code);
+                assert_eq!(area_code.word_area().bit_area(), area_code.bit_area());
+            }
         }
+        assert_eq!(
+            RawMemoryAreaCode(0xA3).deserialize().unwrap(),
+            MemoryAreaCode::E3
+        );
+        assert_eq!(MemoryAreaCode::E3Bit.em_bank(), Some(3));
+        assert!(RawMemoryAreaCode(0xAD).deserialize().is_err());
     }
 }
@@ -0,0 +1,59 @@
+use std::io::Write;
+
+use crate::*;
+
+/// Writes `word` to `count` consecutive words starting at `address`. Bit areas can not be filled.
+pub struct MemoryAreaFillRequest {
+    pub address: MemoryAddress,
+    pub count: u16,
+    pub word: u16,
+}
+
+impl Command for MemoryAreaFillRequest {
+    const MRC: u8 = 0x01;
+    const SRC: u8 = 0x03;
+
+    type Response = ();
+
+    fn encoded_len(&self) -> usize {
+        ::std::mem::size_of::<RawMemoryAreaFillRequestBody>()
+    }
+
+    fn encode_body<W: Write>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_raw(&RawMemoryAreaFillRequestBody {
+            address: self.address.serialize(),
+            count: u16be::from_u16(self.count),
+            word: u16be::from_u16(self.word),
+        })?;
+
+        Ok(())
+    }
+
+    fn decode_response(&self, bytes: &[u8]) -> Result<Self::Response, ProtocolViolation> {
+        decode_empty_response(bytes)
+    }
+
+    fn decode_body(bytes: &[u8]) -> Result<Self, ProtocolViolation> {
+        let (body, bytes) = split_raw::<RawMemoryAreaFillRequestBody>(bytes)?;
+        assert_body_length(bytes.len(), 0)?;
+
+        Ok(Self {
+            address: body.address.deserialize()?,
+            count: body.count.to_u16(),
+            word: body.word.to_u16(),
+        })
+    }
+
+    fn encode_response<W: Write>(_response: &(), _writer: &mut W) -> crate::Result<()> {
+        Ok(())
+    }
+}
+
+#[repr(C, packed)]
+struct RawMemoryAreaFillRequestBody {
+    address: RawMemoryAddress,
+    count: u16be,
+    word: u16be,
+}
+
+unsafe_impl_raw!(RawMemoryAreaFillRequestBody);
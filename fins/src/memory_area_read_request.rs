@@ -2,45 +2,53 @@ use std::io::Write;
 
 use crate::*;
 
+/// Reads `count` consecutive items starting at `address`. Items are words for word areas and bytes
+/// holding a single bit for bit areas.
 pub struct MemoryAreaReadRequest {
-    pub server_node: u8,
-    pub client_node: u8,
     pub address: MemoryAddress,
     pub count: u16,
-    pub service_id: u8,
 }
 
-impl MemoryAreaReadRequest {
-    pub fn write_to<W: Write>(&self, writer: &mut W) -> crate::Result<()> {
-        writer.write_raw(&RawMemoryAreaReadRequest {
-            fins_header: Header {
-                icf: InformationControlField::RequestWithResponse,
-                gct: 0x02,
-                destination: MachineAddress {
-                    network: 0,
-                    node: self.server_node,
-                    unit: 0,
-                },
-                source: MachineAddress {
-                    network: 0,
-                    node: self.client_node,
-                    unit: 0,
-                },
-                sid: self.service_id,
-            }
-            .serialize(),
-            request_header: RequestHeader::MemoryAreaRead.to_raw(),
-            request_body: RawMemoryAreaReadRequestBody {
-                address: self.address.serialize(),
-                count: u16be::from_u16(self.count),
-            },
+impl Command for MemoryAreaReadRequest {
+    const MRC: u8 = 0x01;
+    const SRC: u8 = 0x01;
+
+    type Response = Vec<u8>;
+
+    fn encoded_len(&self) -> usize {
+        ::std::mem::size_of::<RawMemoryAreaReadRequestBody>()
+    }
+
+    fn encode_body<W: Write>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_raw(&RawMemoryAreaReadRequestBody {
+            address: self.address.serialize(),
+            count: u16be::from_u16(self.count),
         })?;
 
         Ok(())
     }
 
-    pub const fn byte_size() -> usize {
-        ::std::mem::size_of::<RawMemoryAreaReadRequest>()
+    fn decode_response(&self, bytes: &[u8]) -> Result<Self::Response, ProtocolViolation> {
+        assert_body_length(
+            bytes.len(),
+            self.count as usize * self.address.area_code.item_size(),
+        )?;
+        Ok(bytes.to_vec())
+    }
+
+    fn decode_body(bytes: &[u8]) -> Result<Self, ProtocolViolation> {
+        let (body, bytes) = split_raw::<RawMemoryAreaReadRequestBody>(bytes)?;
+        assert_body_length(bytes.len(), 0)?;
+
+        Ok(Self {
+            address: body.address.deserialize()?,
+            count: body.count.to_u16(),
+        })
+    }
+
+    fn encode_response<W: Write>(response: &Vec<u8>, writer: &mut W) -> crate::Result<()> {
+        writer.write_all(response)?;
+        Ok(())
     }
 }
 
@@ -50,11 +58,4 @@ struct RawMemoryAreaReadRequestBody {
     count: u16be,
 }
 
-#[repr(C, packed)]
-struct RawMemoryAreaReadRequest {
-    fins_header: RawHeader,
-    request_header: RawRequestHeader,
-    request_body: RawMemoryAreaReadRequestBody,
-}
-
-unsafe_impl_raw!(RawMemoryAreaReadRequest);
+unsafe_impl_raw!(RawMemoryAreaReadRequestBody);
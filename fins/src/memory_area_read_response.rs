@@ -2,61 +2,116 @@ use std::io::Write;
 
 use crate::*;
 
+/// A complete memory area read response frame.
+///
+/// Unlike [`read_response`], decoding does not fail on an abnormal end code so that both clients
+/// and servers can represent every response.
+#[derive(Debug, Eq, PartialEq)]
 pub struct MemoryAreaReadResponse {
-    server_node: u8,
-    client_node: u8,
-    address: MemoryAddress,
-    count: u16,
+    pub header: Header,
+    pub end_code: EndCode,
+    /// The words read, in big endian byte order. Empty unless the end code allows data to follow.
+    pub bytes: Vec<u8>,
 }
 
 impl MemoryAreaReadResponse {
     pub fn write_to<W: Write>(&self, writer: &mut W) -> crate::Result<()> {
-        writer.write_raw(&RawMemoryAreaReadResponse {
-            fins_header: Header {
-                icf: InformationControlField::ResponseWithResponse,
-                gct: 0x02,
-                destination: MachineAddress {
-                    network: 0,
-                    node: self.server_node,
-                    unit: 0,
-                },
-                source: MachineAddress {
-                    network: 0,
-                    node: self.client_node,
-                    unit: 0,
-                },
-                sid: 0,
-            }
-            .serialize(),
-            Response_header: RawResponseHeader {
-                mrc: 0x01,
-                src: 0x01,
-            },
-            Response_body: RawMemoryAreaReadResponseBody {
-                address: self.address.serialize(),
-                count: u16be::from_u16(self.count),
-            },
+        writer.write_raw(&self.header.serialize())?;
+        writer.write_raw(&RawResponseHeader {
+            mrc: MemoryAreaReadRequest::MRC,
+            src: MemoryAreaReadRequest::SRC,
+            mres: self.end_code.mres,
+            sres: self.end_code.sres,
         })?;
+        writer.write_all(&self.bytes)?;
 
         Ok(())
     }
 
-    pub const fn byte_size() -> usize {
-        ::std::mem::size_of::<RawMemoryAreaReadResponse>()
+    pub fn from_bytes(frame: &[u8]) -> Result<Self, ProtocolViolation> {
+        let (header, bytes) = split_raw::<RawHeader>(frame)?;
+        let header = header.deserialize()?;
+        if header.icf.is_request() {
+            return Err(ProtocolViolation::UnexpectedRequest);
+        }
+
+        let (
+            RawResponseHeader {
+                mrc,
+                src,
+                mres,
+                sres,
+            },
+            bytes,
+        ) = split_raw::<RawResponseHeader>(bytes)?;
+        assert_command_code(
+            [mrc, src],
+            [MemoryAreaReadRequest::MRC, MemoryAreaReadRequest::SRC],
+        )?;
+
+        Ok(Self {
+            header,
+            end_code: EndCode::new(mres, sres),
+            bytes: bytes.to_vec(),
+        })
     }
-}
 
-#[repr(C, packed)]
-struct RawMemoryAreaReadResponseBody {
-    address: RawMemoryAddress,
-    count: u16be,
-}
+    pub fn byte_size(&self) -> usize {
+        ::std::mem::size_of::<RawHeader>()
+            + ::std::mem::size_of::<RawResponseHeader>()
+            + self.bytes.len()
+    }
 
-#[repr(C, packed)]
-struct RawMemoryAreaReadResponse {
-    fins_header: RawHeader,
-    Response_header: RawResponseHeader,
-    Response_body: RawMemoryAreaReadResponseBody,
+    /// The words read, ignoring a trailing odd byte.
+    pub fn words(&self) -> impl Iterator<Item = u16> + '_ {
+        self.bytes
+            .chunks_exact(2)
+            .map(|word| u16::from_be_bytes([word[0], word[1]]))
+    }
 }
 
-unsafe_impl_raw!(RawMemoryAreaReadResponse);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bidir_works() {
+        let response = MemoryAreaReadResponse {
+            header: Route::local(0xD3, 0xFB)
+                .reverse()
+                .header(InformationControlField::ResponseWithResponse, 0x05),
+            end_code: EndCode::NORMAL_COMPLETION,
+            bytes: vec![0x12, 0x34, 0xAB, 0xCD],
+        };
+
+        let mut frame = vec![];
+        response.write_to(&mut frame).unwrap();
+        assert_eq!(
+            frame,
+            [
+                0xC0, 0x00, 0x02, 0x00, 0xFB, 0x00, 0x00, 0xD3, 0x00, 0x05, 0x01, 0x01, 0x00, 0x00,
+                0x12, 0x34, 0xAB, 0xCD
+            ]
+        );
+        assert_eq!(frame.len(), response.byte_size());
+        assert_eq!(
+            MemoryAreaReadResponse::from_bytes(&frame).unwrap(),
+            response
+        );
+        assert_eq!(response.words().collect::<Vec<_>>(), [0x1234, 0xABCD]);
+    }
+
+    #[test]
+    fn from_bytes_checks_command_code() {
+        let frame = [
+            0xC0, 0x00, 0x02, 0x00, 0xFB, 0x00, 0x00, 0xD3, 0x00, 0x05, 0x01, 0x02, 0x00, 0x00,
+        ];
+        assert!(matches!(
+            MemoryAreaReadResponse::from_bytes(&frame),
+            Err(ProtocolViolation::UnexpectedCommandCode {
+                actual: [0x01, 0x02],
+                expected: [0x01, 0x01],
+            })
+        ));
+    }
+}
@@ -0,0 +1,59 @@
+use std::io::Write;
+
+use crate::*;
+
+/// Copies `count` consecutive words starting at `source` to `destination`.
+pub struct MemoryAreaTransferRequest {
+    pub source: MemoryAddress,
+    pub destination: MemoryAddress,
+    pub count: u16,
+}
+
+impl Command for MemoryAreaTransferRequest {
+    const MRC: u8 = 0x01;
+    const SRC: u8 = 0x05;
+
+    type Response = ();
+
+    fn encoded_len(&self) -> usize {
+        ::std::mem::size_of::<RawMemoryAreaTransferRequestBody>()
+    }
+
+    fn encode_body<W: Write>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_raw(&RawMemoryAreaTransferRequestBody {
+            source: self.source.serialize(),
+            destination: self.destination.serialize(),
+            count: u16be::from_u16(self.count),
+        })?;
+
+        Ok(())
+    }
+
+    fn decode_response(&self, bytes: &[u8]) -> Result<Self::Response, ProtocolViolation> {
+        decode_empty_response(bytes)
+    }
+
+    fn decode_body(bytes: &[u8]) -> Result<Self, ProtocolViolation> {
+        let (body, bytes) = split_raw::<RawMemoryAreaTransferRequestBody>(bytes)?;
+        assert_body_length(bytes.len(), 0)?;
+
+        Ok(Self {
+            source: body.source.deserialize()?,
+            destination: body.destination.deserialize()?,
+            count: body.count.to_u16(),
+        })
+    }
+
+    fn encode_response<W: Write>(_response: &(), _writer: &mut W) -> crate::Result<()> {
+        Ok(())
+    }
+}
+
+#[repr(C, packed)]
+struct RawMemoryAreaTransferRequestBody {
+    source: RawMemoryAddress,
+    destination: RawMemoryAddress,
+    count: u16be,
+}
+
+unsafe_impl_raw!(RawMemoryAreaTransferRequestBody);
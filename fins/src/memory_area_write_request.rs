@@ -0,0 +1,103 @@
+use std::io::Write;
+
+use crate::*;
+
+/// Writes `bytes` to consecutive items starting at `address`. Items are words for word areas and
+/// bytes holding a single bit for bit areas.
+pub struct MemoryAreaWriteRequest {
+    pub address: MemoryAddress,
+    pub bytes: Vec<u8>,
+}
+
+impl MemoryAreaWriteRequest {
+    /// Number of items written.
+    pub fn count(&self) -> u16 {
+        (self.bytes.len() / self.address.area_code.item_size()) as u16
+    }
+}
+
+impl Command for MemoryAreaWriteRequest {
+    const MRC: u8 = 0x01;
+    const SRC: u8 = 0x02;
+
+    type Response = ();
+
+    fn encoded_len(&self) -> usize {
+        ::std::mem::size_of::<RawMemoryAreaWriteRequestBody>() + self.bytes.len()
+    }
+
+    fn encode_body<W: Write>(&self, writer: &mut W) -> crate::Result<()> {
+        assert!(self
+            .bytes
+            .len()
+            .is_multiple_of(self.address.area_code.item_size()));
+
+        writer.write_raw(&RawMemoryAreaWriteRequestBody {
+            address: self.address.serialize(),
+            count: u16be::from_u16(self.count()),
+        })?;
+        writer.write_all(&self.bytes)?;
+
+        Ok(())
+    }
+
+    fn decode_response(&self, bytes: &[u8]) -> Result<Self::Response, ProtocolViolation> {
+        decode_empty_response(bytes)
+    }
+
+    fn decode_body(bytes: &[u8]) -> Result<Self, ProtocolViolation> {
+        let (body, bytes) = split_raw::<RawMemoryAreaWriteRequestBody>(bytes)?;
+        let address = body.address.deserialize()?;
+        assert_body_length(
+            bytes.len(),
+            body.count.to_u16() as usize * address.area_code.item_size(),
+        )?;
+
+        Ok(Self {
+            address,
+            bytes: bytes.to_vec(),
+        })
+    }
+
+    fn encode_response<W: Write>(_response: &(), _writer: &mut W) -> crate::Result<()> {
+        Ok(())
+    }
+}
+
+#[repr(C, packed)]
+struct RawMemoryAreaWriteRequestBody {
+    address: RawMemoryAddress,
+    count: u16be,
+}
+
+unsafe_impl_raw!(RawMemoryAreaWriteRequestBody);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bidir_works() {
+        let request = MemoryAreaWriteRequest {
+            address: MemoryAddress {
+                area_code: MemoryAreaCode::D,
+                offset: 100,
+                bits: 0,
+            },
+            bytes: vec![0x12, 0x34, 0x56, 0x78],
+        };
+
+        let mut output = vec![];
+        request.encode_body(&mut output).unwrap();
+        assert_eq!(
+            output,
+            [0x82, 0x00, 0x64, 0x00, 0x00, 0x02, 0x12, 0x34, 0x56, 0x78]
+        );
+        assert_eq!(output.len(), request.encoded_len());
+
+        let decoded = MemoryAreaWriteRequest::decode_body(&output).unwrap();
+        assert_eq!(decoded.address, request.address);
+        assert_eq!(decoded.bytes, request.bytes);
+        assert!(MemoryAreaWriteRequest::decode_body(&output[..9]).is_err());
+    }
+}
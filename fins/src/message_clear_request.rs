@@ -0,0 +1,45 @@
+use std::io::Write;
+
+use crate::*;
+
+/// Clears the MSG(195) messages whose bits are set in `messages`, bit 0 being message 0.
+pub struct MessageClearRequest {
+    pub messages: u8,
+}
+
+impl Command for MessageClearRequest {
+    const MRC: u8 = 0x09;
+    const SRC: u8 = 0x20;
+
+    type Response = ();
+
+    fn encoded_len(&self) -> usize {
+        ::std::mem::size_of::<u16be>()
+    }
+
+    fn encode_body<W: Write>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_raw(&u16be::from_u16(MESSAGE_CLEAR | self.messages as u16))?;
+        Ok(())
+    }
+
+    fn decode_response(&self, bytes: &[u8]) -> Result<Self::Response, ProtocolViolation> {
+        decode_empty_response(bytes)
+    }
+
+    fn decode_body(bytes: &[u8]) -> Result<Self, ProtocolViolation> {
+        let (parameter, bytes) = split_raw::<u16be>(bytes)?;
+        assert_body_length(bytes.len(), 0)?;
+        let parameter = parameter.to_u16();
+        if parameter & MESSAGE_KIND_MASK != MESSAGE_CLEAR {
+            return Err(ProtocolViolation::InvalidMessageParameter(parameter));
+        }
+
+        Ok(Self {
+            messages: parameter as u8,
+        })
+    }
+
+    fn encode_response<W: Write>(_response: &(), _writer: &mut W) -> crate::Result<()> {
+        Ok(())
+    }
+}
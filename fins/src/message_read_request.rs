@@ -0,0 +1,71 @@
+use std::io::Write;
+
+use crate::*;
+
+/// Reads the MSG(195) messages whose bits are set in `messages`, bit 0 being message 0.
+pub struct MessageReadRequest {
+    pub messages: u8,
+}
+
+impl Command for MessageReadRequest {
+    const MRC: u8 = 0x09;
+    const SRC: u8 = 0x20;
+
+    type Response = MessageReadResponse;
+
+    fn encoded_len(&self) -> usize {
+        ::std::mem::size_of::<u16be>()
+    }
+
+    fn encode_body<W: Write>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_raw(&u16be::from_u16(MESSAGE_READ | self.messages as u16))?;
+        Ok(())
+    }
+
+    fn decode_response(&self, bytes: &[u8]) -> Result<Self::Response, ProtocolViolation> {
+        MessageReadResponse::from_bytes(bytes)
+    }
+
+    fn decode_body(bytes: &[u8]) -> Result<Self, ProtocolViolation> {
+        let (parameter, bytes) = split_raw::<u16be>(bytes)?;
+        assert_body_length(bytes.len(), 0)?;
+        let parameter = parameter.to_u16();
+        if parameter & MESSAGE_KIND_MASK != MESSAGE_READ {
+            return Err(ProtocolViolation::InvalidMessageParameter(parameter));
+        }
+
+        Ok(Self {
+            messages: parameter as u8,
+        })
+    }
+
+    fn encode_response<W: Write>(
+        response: &MessageReadResponse,
+        writer: &mut W,
+    ) -> crate::Result<()> {
+        response.write_to(writer)
+    }
+}
+
+/// Bits 14 and 15 of the parameter select between the commands that share code 09 20.
+pub(crate) const MESSAGE_KIND_MASK: u16 = 0xC000;
+pub(crate) const MESSAGE_READ: u16 = 0x0000;
+pub(crate) const MESSAGE_CLEAR: u16 = 0x4000;
+pub(crate) const FAL_MESSAGE_READ: u16 = 0x8000;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_body_works() {
+        let mut output = vec![];
+        MessageReadRequest {
+            messages: 0b1000_0101,
+        }
+        .encode_body(&mut output)
+        .unwrap();
+
+        assert_eq!(output, [0x00, 0x85]);
+    }
+}
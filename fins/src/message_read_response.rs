@@ -0,0 +1,128 @@
+use std::io::Write;
+
+use crate::*;
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct Message {
+    pub number: u8,
+    /// The message text, empty when no message is registered.
+    pub text: String,
+}
+
+/// The body of a message read response, following the response code.
+#[derive(Debug, Eq, PartialEq)]
+pub struct MessageReadResponse {
+    pub messages: Vec<Message>,
+}
+
+impl MessageReadResponse {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProtocolViolation> {
+        let (parameter, mut bytes) = split_raw::<u16be>(bytes)?;
+        let parameter = parameter.to_u16();
+        if parameter & MESSAGE_KIND_MASK != MESSAGE_READ {
+            return Err(ProtocolViolation::InvalidMessageParameter(parameter));
+        }
+        let numbers = parameter as u8;
+        assert_body_length(bytes.len(), numbers.count_ones() as usize * 32)?;
+
+        let mut messages = Vec::with_capacity(numbers.count_ones() as usize);
+        for number in (0..8).filter(|number| numbers & (1 << number) != 0) {
+            let (text, rest) = split_raw::<RawAsciiString<32>>(bytes)?;
+            bytes = rest;
+            messages.push(Message {
+                number,
+                text: text.deserialize()?,
+            });
+        }
+
+        Ok(Self { messages })
+    }
+
+    /// Writes the messages in ascending order of their numbers. Numbers have to be distinct and
+    /// below 8, and texts at most 32 ASCII characters.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> crate::Result<()> {
+        let mut messages = self.messages.iter().collect::<Vec<_>>();
+        messages.sort_by_key(|message| message.number);
+        let mut numbers = 0u8;
+        for message in &messages {
+            let bit = 1u8.checked_shl(message.number as u32).unwrap_or(0);
+            if bit == 0 || numbers & bit != 0 {
+                return Err(Error::InvalidValue(format!(
+                    "message number {} should be below 8 and appear once",
+                    message.number
+                )));
+            }
+            numbers |= bit;
+        }
+        let texts = messages
+            .iter()
+            .map(|message| RawAsciiString::<32>::serialize_field(&message.text, "message"))
+            .collect::<crate::Result<Vec<_>>>()?;
+
+        writer.write_raw(&u16be::from_u16(MESSAGE_READ | numbers as u16))?;
+        for text in texts {
+            writer.write_raw(&text)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_bytes_works() {
+        let mut bytes = vec![0x00, 0b0000_0110];
+        bytes.extend_from_slice(b"CONVEYOR 2 JAMMED               ");
+        bytes.extend_from_slice(&[b' '; 32]);
+
+        let response = MessageReadResponse {
+            messages: vec![
+                Message {
+                    number: 1,
+                    text: "CONVEYOR 2 JAMMED".to_string(),
+                },
+                Message {
+                    number: 2,
+                    text: String::new(),
+                },
+            ],
+        };
+        assert_eq!(MessageReadResponse::from_bytes(&bytes).unwrap(), response);
+
+        let mut output = vec![];
+        response.write_to(&mut output).unwrap();
+        assert_eq!(output, bytes);
+    }
+
+    #[test]
+    fn write_to_checks_messages() {
+        let message = |number, text: &str| Message {
+            number,
+            text: text.to_string(),
+        };
+        for messages in [
+            vec![message(8, "")],
+            vec![message(1, ""), message(1, "")],
+            vec![message(0, "A MESSAGE THAT IS LONGER THAN 32 CHARACTERS")],
+        ] {
+            assert!(matches!(
+                MessageReadResponse { messages }.write_to(&mut vec![]),
+                Err(Error::InvalidValue(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn from_bytes_checks_length() {
+        assert!(matches!(
+            MessageReadResponse::from_bytes(&[0x00, 0b0000_0011, b' ']),
+            Err(ProtocolViolation::UnexpectedBodyLength {
+                actual: 1,
+                expected: 64
+            })
+        ));
+    }
+}
@@ -0,0 +1,132 @@
+use std::io::Write;
+
+use crate::*;
+
+/// Maximum number of items a CS/CJ series CPU unit reads with a single multiple memory area read.
+pub const MULTIPLE_MEMORY_AREA_READ_MAX_COUNT: usize = 167;
+
+/// Reads a single item at each of `addresses`, which may lie in different areas.
+pub struct MultipleMemoryAreaReadRequest {
+    pub addresses: Vec<MemoryAddress>,
+}
+
+/// An item read by a [`MultipleMemoryAreaReadRequest`]. Holds 2 bytes for word areas and 1 byte
+/// for bit areas.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MemoryAreaItem {
+    pub area_code: MemoryAreaCode,
+    pub bytes: Vec<u8>,
+}
+
+impl Command for MultipleMemoryAreaReadRequest {
+    const MRC: u8 = 0x01;
+    const SRC: u8 = 0x04;
+
+    type Response = Vec<MemoryAreaItem>;
+
+    fn encoded_len(&self) -> usize {
+        self.addresses.len() * ::std::mem::size_of::<RawMemoryAddress>()
+    }
+
+    fn encode_body<W: Write>(&self, writer: &mut W) -> crate::Result<()> {
+        for address in &self.addresses {
+            writer.write_raw(&address.serialize())?;
+        }
+
+        Ok(())
+    }
+
+    fn decode_response(&self, mut bytes: &[u8]) -> Result<Self::Response, ProtocolViolation> {
+        let mut items = Vec::with_capacity(self.addresses.len());
+        for address in &self.addresses {
+            let (area_code, rest) = split_raw::<RawMemoryAreaCode>(bytes)?;
+            let area_code = area_code.deserialize()?;
+            if area_code != address.area_code {
+                return Err(ProtocolViolation::UnexpectedMemoryAreaCode {
+                    actual: area_code,
+                    expected: address.area_code,
+                });
+            }
+            let item_size = area_code.item_size();
+            if rest.len() < item_size {
+                return Err(ProtocolViolation::BodyTooShort {
+                    actual: rest.len(),
+                    expected: item_size,
+                });
+            }
+            let (item, rest) = rest.split_at(item_size);
+            items.push(MemoryAreaItem {
+                area_code,
+                bytes: item.to_vec(),
+            });
+            bytes = rest;
+        }
+        assert_body_length(bytes.len(), 0)?;
+        Ok(items)
+    }
+
+    fn decode_body(mut bytes: &[u8]) -> Result<Self, ProtocolViolation> {
+        let mut addresses =
+            Vec::with_capacity(bytes.len() / std::mem::size_of::<RawMemoryAddress>());
+        while !bytes.is_empty() {
+            let (address, rest) = split_raw::<RawMemoryAddress>(bytes)?;
+            addresses.push(address.deserialize()?);
+            bytes = rest;
+        }
+
+        Ok(Self { addresses })
+    }
+
+    fn encode_response<W: Write>(
+        response: &Vec<MemoryAreaItem>,
+        writer: &mut W,
+    ) -> crate::Result<()> {
+        for item in response {
+            writer.write_raw(&item.area_code.serialize())?;
+            writer.write_all(&item.bytes)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bidir_works() {
+        let request = MultipleMemoryAreaReadRequest {
+            addresses: vec!["D100".parse().unwrap(), "W2.03".parse().unwrap()],
+        };
+
+        let mut output = vec![];
+        request.encode_body(&mut output).unwrap();
+        assert_eq!(output, [0x82, 0x00, 0x64, 0x00, 0x31, 0x00, 0x02, 0x03]);
+        assert_eq!(output.len(), request.encoded_len());
+        let decoded = MultipleMemoryAreaReadRequest::decode_body(&output).unwrap();
+        assert_eq!(decoded.addresses, request.addresses);
+        assert!(MultipleMemoryAreaReadRequest::decode_body(&output[..7]).is_err());
+
+        let items = vec![
+            MemoryAreaItem {
+                area_code: MemoryAreaCode::D,
+                bytes: vec![0x12, 0x34],
+            },
+            MemoryAreaItem {
+                area_code: MemoryAreaCode::WBit,
+                bytes: vec![0x01],
+            },
+        ];
+        let mut output = vec![];
+        MultipleMemoryAreaReadRequest::encode_response(&items, &mut output).unwrap();
+        assert_eq!(output, [0x82, 0x12, 0x34, 0x31, 0x01]);
+        assert_eq!(request.decode_response(&output).unwrap(), items);
+
+        output[3] = 0x30;
+        assert!(matches!(
+            request.decode_response(&output),
+            Err(ProtocolViolation::UnexpectedMemoryAreaCode { .. })
+        ));
+        assert!(request.decode_response(&output[..4]).is_err());
+    }
+}
@@ -0,0 +1,65 @@
+use std::io::Write;
+
+use crate::*;
+
+/// Reads the name of the CPU unit.
+pub struct NameReadRequest;
+
+impl Command for NameReadRequest {
+    const MRC: u8 = 0x26;
+    const SRC: u8 = 0x03;
+
+    type Response = NameReadResponse;
+
+    fn encoded_len(&self) -> usize {
+        0
+    }
+
+    fn encode_body<W: Write>(&self, _writer: &mut W) -> crate::Result<()> {
+        Ok(())
+    }
+
+    fn decode_response(&self, bytes: &[u8]) -> Result<Self::Response, ProtocolViolation> {
+        NameReadResponse::from_bytes(bytes)
+    }
+
+    fn decode_body(bytes: &[u8]) -> Result<Self, ProtocolViolation> {
+        assert_body_length(bytes.len(), 0)?;
+        Ok(Self)
+    }
+
+    fn encode_response<W: Write>(response: &NameReadResponse, writer: &mut W) -> crate::Result<()> {
+        response.write_to(writer)
+    }
+}
+
+/// Deletes the name of the CPU unit.
+pub struct NameDeleteRequest;
+
+impl Command for NameDeleteRequest {
+    const MRC: u8 = 0x26;
+    const SRC: u8 = 0x02;
+
+    type Response = ();
+
+    fn encoded_len(&self) -> usize {
+        0
+    }
+
+    fn encode_body<W: Write>(&self, _writer: &mut W) -> crate::Result<()> {
+        Ok(())
+    }
+
+    fn decode_response(&self, bytes: &[u8]) -> Result<Self::Response, ProtocolViolation> {
+        decode_empty_response(bytes)
+    }
+
+    fn decode_body(bytes: &[u8]) -> Result<Self, ProtocolViolation> {
+        assert_body_length(bytes.len(), 0)?;
+        Ok(Self)
+    }
+
+    fn encode_response<W: Write>(_response: &(), _writer: &mut W) -> crate::Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,42 @@
+use std::io::Write;
+
+use crate::*;
+
+/// The body of a name read response, following the response code.
+#[derive(Debug, Eq, PartialEq)]
+pub struct NameReadResponse {
+    pub name: String,
+}
+
+impl NameReadResponse {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProtocolViolation> {
+        let (name, bytes) = split_raw::<RawAsciiString<8>>(bytes)?;
+        assert_body_length(bytes.len(), 0)?;
+
+        Ok(Self {
+            name: name.deserialize()?,
+        })
+    }
+
+    /// Writes the name, which has to be at most 8 ASCII characters.
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> crate::Result<()> {
+        let name = RawAsciiString::<8>::serialize_field(&self.name, "name")?;
+        writer.write_raw(&name)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_bytes_works() {
+        assert_eq!(
+            NameReadResponse::from_bytes(b"LINE4   ").unwrap(),
+            NameReadResponse {
+                name: "LINE4".to_string()
+            }
+        );
+    }
+}
@@ -0,0 +1,59 @@
+use std::io::Write;
+
+use crate::*;
+
+/// Registers `name` as the name of the CPU unit. The name can be up to 8 ASCII characters.
+pub struct NameSetRequest {
+    pub name: String,
+}
+
+impl Command for NameSetRequest {
+    const MRC: u8 = 0x26;
+    const SRC: u8 = 0x01;
+
+    type Response = ();
+
+    fn encoded_len(&self) -> usize {
+        ::std::mem::size_of::<RawAsciiString<8>>()
+    }
+
+    fn encode_body<W: Write>(&self, writer: &mut W) -> crate::Result<()> {
+        let name = RawAsciiString::<8>::serialize_field(&self.name, "name")?;
+        writer.write_raw(&name)?;
+        Ok(())
+    }
+
+    fn decode_response(&self, bytes: &[u8]) -> Result<Self::Response, ProtocolViolation> {
+        decode_empty_response(bytes)
+    }
+
+    fn decode_body(bytes: &[u8]) -> Result<Self, ProtocolViolation> {
+        let (name, bytes) = split_raw::<RawAsciiString<8>>(bytes)?;
+        assert_body_length(bytes.len(), 0)?;
+
+        Ok(Self {
+            name: name.deserialize()?,
+        })
+    }
+
+    fn encode_response<W: Write>(_response: &(), _writer: &mut W) -> crate::Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_body_works() {
+        let mut output = vec![];
+        NameSetRequest {
+            name: "LINE4".to_string(),
+        }
+        .encode_body(&mut output)
+        .unwrap();
+
+        assert_eq!(output, b"LINE4   ");
+    }
+}
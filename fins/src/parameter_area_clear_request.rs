@@ -0,0 +1,57 @@
+use std::io::Write;
+
+use crate::*;
+
+/// Writes zeros to the entire parameter area.
+pub struct ParameterAreaClearRequest {
+    pub area_code: ParameterAreaCode,
+}
+
+impl Command for ParameterAreaClearRequest {
+    const MRC: u8 = 0x02;
+    const SRC: u8 = 0x03;
+
+    type Response = ();
+
+    fn encoded_len(&self) -> usize {
+        ::std::mem::size_of::<RawParameterAreaClearRequestBody>()
+    }
+
+    fn encode_body<W: Write>(&self, writer: &mut W) -> crate::Result<()> {
+        writer.write_raw(&RawParameterAreaClearRequestBody {
+            area_code: self.area_code.serialize(),
+            offset: u16be::from_u16(0x0000),
+            count: u16be::from_u16(self.area_code.word_count()),
+            data: u16be::from_u16(0x0000),
+        })?;
+
+        Ok(())
+    }
+
+    fn decode_response(&self, bytes: &[u8]) -> Result<Self::Response, ProtocolViolation> {
+        decode_empty_response(bytes)
+    }
+
+    fn decode_body(bytes: &[u8]) -> Result<Self, ProtocolViolation> {
+        let (body, bytes) = split_raw::<RawParameterAreaClearRequestBody>(bytes)?;
+        assert_body_length(bytes.len(), 0)?;
+
+        Ok(Self {
+            area_code: body.area_code.deserialize()?,
+        })
+    }
+
+    fn encode_response<W: Write>(_response: &(), _writer: &mut W) -> crate::Result<()> {
+        Ok(())
+    }
+}
+
+#[repr(C, packed)]
+struct RawParameterAreaClearRequestBody {
+    area_code: RawParameterAreaCode,
+    offset: u16be,
+    count: u16be,
+    data: u16be,
+}
+
+unsafe_impl_raw!(RawParameterAreaClearRequestBody);
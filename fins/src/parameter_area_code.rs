@@ -0,0 +1,122 @@
+use crate::*;
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct RawParameterAreaCode(pub u16be);
+
+unsafe_impl_raw!(RawParameterAreaCode);
+
+impl RawParameterAreaCode {
+    pub const PLC_SETUP: Self = Self(u16be::from_u16(0x8010));
+    pub const PERIPHERAL_DEVICE_SETTINGS: Self = Self(u16be::from_u16(0x8011));
+    pub const IO_TABLE: Self = Self(u16be::from_u16(0x8012));
+    pub const ROUTING_TABLES: Self = Self(u16be::from_u16(0x8013));
+    pub const CPU_BUS_UNIT_SETTINGS: Self = Self(u16be::from_u16(0x8002));
+
+    pub const fn deserialize(self) -> Result<ParameterAreaCode, ProtocolViolation> {
+        match self {
+            Self::PLC_SETUP => Ok(ParameterAreaCode::PlcSetup),
+            Self::PERIPHERAL_DEVICE_SETTINGS => Ok(ParameterAreaCode::PeripheralDeviceSettings),
+            Self::IO_TABLE => Ok(ParameterAreaCode::IoTable),
+            Self::ROUTING_TABLES => Ok(ParameterAreaCode::RoutingTables),
+            Self::CPU_BUS_UNIT_SETTINGS => Ok(ParameterAreaCode::CpuBusUnitSettings),
+            unknown => Err(ProtocolViolation::InvalidParameterAreaCode(unknown)),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum ParameterAreaCode {
+    PlcSetup,
+    PeripheralDeviceSettings,
+    IoTable,
+    RoutingTables,
+    CpuBusUnitSettings,
+}
+
+impl ParameterAreaCode {
+    pub const fn serialize(&self) -> RawParameterAreaCode {
+        match self {
+            Self::PlcSetup => RawParameterAreaCode::PLC_SETUP,
+            Self::PeripheralDeviceSettings => RawParameterAreaCode::PERIPHERAL_DEVICE_SETTINGS,
+            Self::IoTable => RawParameterAreaCode::IO_TABLE,
+            Self::RoutingTables => RawParameterAreaCode::ROUTING_TABLES,
+            Self::CpuBusUnitSettings => RawParameterAreaCode::CPU_BUS_UNIT_SETTINGS,
+        }
+    }
+
+    /// Number of words in the area. This is the word count to send with a parameter area clear.
+    pub const fn word_count(&self) -> u16 {
+        match self {
+            Self::PlcSetup => 0x0100,
+            Self::PeripheralDeviceSettings => 0x00C0,
+            Self::IoTable => 0x0400,
+            Self::RoutingTables => 0x0200,
+            Self::CpuBusUnitSettings => 0x0840,
+        }
+    }
+
+    /// Number of words that can be read and written. Only the first 48 words of the routing tables
+    /// are accessible.
+    pub const fn accessible_word_count(&self) -> u16 {
+        match self {
+            Self::RoutingTables => 0x0030,
+            _ => self.word_count(),
+        }
+    }
+
+    /// Splits the accessible part of the area into `(offset, count)` ranges that each fit in a
+    /// single parameter area read or write.
+    pub fn chunks(&self) -> impl Iterator<Item = (u16, u16)> {
+        let end = self.accessible_word_count();
+        (0..end)
+            .step_by(PARAMETER_AREA_MAX_WORD_COUNT as usize)
+            .map(move |offset| (offset, (end - offset).min(PARAMETER_AREA_MAX_WORD_COUNT)))
+    }
+
+    /// Checks that `count` words starting at `offset` fit in a single parameter area command and
+    /// in the accessible part of the area. Returns the offset following the range.
+    pub(crate) fn check_range(&self, offset: u16, count: usize) -> crate::Result<u16> {
+        if count > PARAMETER_AREA_MAX_WORD_COUNT as usize {
+            return Err(Error::InvalidValue(format!(
+                "{} words exceed the {} words of a parameter area command",
+                count, PARAMETER_AREA_MAX_WORD_COUNT
+            )));
+        }
+        match offset.checked_add(count as u16) {
+            Some(end) if end <= self.accessible_word_count() => Ok(end),
+            _ => Err(Error::InvalidValue(format!(
+                "{} words starting at word {} exceed the {} accessible words of {:?}",
+                count,
+                offset,
+                self.accessible_word_count(),
+                self
+            ))),
+        }
+    }
+}
+
+/// Maximum number of words that can be read or written with a single parameter area command.
+pub const PARAMETER_AREA_MAX_WORD_COUNT: u16 = 266;
+
+/// Bit 15 of the word count marks that the data includes the last word of the area.
+pub(crate) const PARAMETER_AREA_LAST_WORD_BIT: u16 = 0x8000;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunks_cover_accessible_words() {
+        assert_eq!(
+            ParameterAreaCode::IoTable.chunks().collect::<Vec<_>>(),
+            [(0, 266), (266, 266), (532, 266), (798, 226)]
+        );
+        assert_eq!(
+            ParameterAreaCode::RoutingTables
+                .chunks()
+                .collect::<Vec<_>>(),
+            [(0, 48)]
+        );
+    }
+}
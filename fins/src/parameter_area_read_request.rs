@@ -0,0 +1,102 @@
+use std::io::Write;
+
+use crate::*;
+
+pub struct ParameterAreaReadRequest {
+    pub area_code: ParameterAreaCode,
+    pub offset: u16,
+    pub count: u16,
+}
+
+impl Command for ParameterAreaReadRequest {
+    const MRC: u8 = 0x02;
+    const SRC: u8 = 0x01;
+
+    type Response = ParameterAreaReadResponse;
+
+    fn encoded_len(&self) -> usize {
+        ::std::mem::size_of::<RawParameterAreaReadRequestBody>()
+    }
+
+    fn encode_body<W: Write>(&self, writer: &mut W) -> crate::Result<()> {
+        self.area_code
+            .check_range(self.offset, self.count as usize)?;
+
+        writer.write_raw(&RawParameterAreaReadRequestBody {
+            area_code: self.area_code.serialize(),
+            offset: u16be::from_u16(self.offset),
+            count: u16be::from_u16(self.count),
+        })?;
+
+        Ok(())
+    }
+
+    fn decode_response(&self, bytes: &[u8]) -> Result<Self::Response, ProtocolViolation> {
+        ParameterAreaReadResponse::from_bytes(bytes)
+    }
+
+    fn decode_body(bytes: &[u8]) -> Result<Self, ProtocolViolation> {
+        let (body, bytes) = split_raw::<RawParameterAreaReadRequestBody>(bytes)?;
+        assert_body_length(bytes.len(), 0)?;
+
+        Ok(Self {
+            area_code: body.area_code.deserialize()?,
+            offset: body.offset.to_u16(),
+            count: body.count.to_u16(),
+        })
+    }
+
+    fn encode_response<W: Write>(
+        response: &ParameterAreaReadResponse,
+        writer: &mut W,
+    ) -> crate::Result<()> {
+        response.write_to(writer)
+    }
+}
+
+#[repr(C, packed)]
+struct RawParameterAreaReadRequestBody {
+    area_code: RawParameterAreaCode,
+    offset: u16be,
+    count: u16be,
+}
+
+unsafe_impl_raw!(RawParameterAreaReadRequestBody);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_body_works() {
+        let request = ParameterAreaReadRequest {
+            area_code: ParameterAreaCode::IoTable,
+            offset: 266,
+            count: 266,
+        };
+        let mut output = vec![];
+        request.encode_body(&mut output).unwrap();
+
+        assert_eq!(output.len(), request.encoded_len());
+        assert_eq!(
+            output,
+            [
+                0x80, 0x12, // parameter area code: I/O table
+                0x01, 0x0A, // beginning word: 266
+                0x01, 0x0A, // word count: 266
+            ]
+        );
+
+        for (offset, count) in [(0, 267), (1000, 25), (u16::MAX, 1)] {
+            let request = ParameterAreaReadRequest {
+                area_code: ParameterAreaCode::IoTable,
+                offset,
+                count,
+            };
+            assert!(matches!(
+                request.encode_body(&mut vec![]),
+                Err(Error::InvalidValue(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use std::io::Write;
+
+use crate::*;
+
+/// The body of a parameter area read response, following the response code.
+#[derive(Debug, Eq, PartialEq)]
+pub struct ParameterAreaReadResponse {
+    pub area_code: ParameterAreaCode,
+    pub offset: u16,
+    /// Whether `bytes` include the last word of the area.
+    pub includes_last_word: bool,
+    pub bytes: Vec<u8>,
+}
+
+impl ParameterAreaReadResponse {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ProtocolViolation> {
+        let (body, bytes) = split_raw::<RawParameterAreaReadResponseBody>(bytes)?;
+        let RawParameterAreaReadResponseBody {
+            area_code,
+            offset,
+            count,
+        } = body;
+        let count = count.to_u16();
+        let word_count = count & !PARAMETER_AREA_LAST_WORD_BIT;
+        assert_body_length(bytes.len(), word_count as usize * 2)?;
+
+        Ok(Self {
+            area_code: area_code.deserialize()?,
+            offset: offset.to_u16(),
+            includes_last_word: count & PARAMETER_AREA_LAST_WORD_BIT != 0,
+            bytes: bytes.to_vec(),
+        })
+    }
+
+    pub fn write_to<W: Write>(&self, writer: &mut W) -> crate::Result<()> {
+        let count = (self.bytes.len() / 2) as u16;
+        writer.write_raw(&RawParameterAreaReadResponseBody {
+            area_code: self.area_code.serialize(),
+            offset: u16be::from_u16(self.offset),
+            count: u16be::from_u16(if self.includes_last_word {
+                count | PARAMETER_AREA_LAST_WORD_BIT
+            } else {
+                count
+            }),
+        })?;
+        writer.write_all(&self.bytes)?;
+
+        Ok(())
+    }
+
+    /// Concatenates the responses to reads of the ranges produced by
+    /// [`ParameterAreaCode::chunks`] into the contents of the entire area.
+    pub fn concat(
+        area_code: ParameterAreaCode,
+        responses: impl IntoIterator<Item = Self>,
+    ) -> Result<Vec<u8>, ProtocolViolation> {
+        let mut bytes = Vec::with_capacity(area_code.accessible_word_count() as usize * 2);
+        let mut includes_last_word = false;
+        for response in responses {
+            if response.area_code != area_code {
+                return Err(ProtocolViolation::UnexpectedParameterAreaCode {
+                    actual: response.area_code,
+                    expected: area_code,
+                });
+            }
+            let expected_offset = (bytes.len() / 2) as u16;
+            if response.offset != expected_offset {
+                return Err(ProtocolViolation::UnexpectedParameterAreaOffset {
+                    actual: response.offset,
+                    expected: expected_offset,
+                });
+            }
+            bytes.extend_from_slice(&response.bytes);
+            includes_last_word = response.includes_last_word;
+        }
+        if !includes_last_word && bytes.len() < area_code.accessible_word_count() as usize * 2 {
+            return Err(ProtocolViolation::IncompleteParameterArea(area_code));
+        }
+        Ok(bytes)
+    }
+}
+
+#[repr(C, packed)]
+struct RawParameterAreaReadResponseBody {
+    area_code: RawParameterAreaCode,
+    offset: u16be,
+    count: u16be,
+}
+
+unsafe_impl_raw!(RawParameterAreaReadResponseBody);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_bytes_works() {
+        assert_eq!(
+            ParameterAreaReadResponse::from_bytes(&[
+                0x80, 0x13, // parameter area code: routing tables
+                0x00, 0x2E, // beginning word: 46
+                0x80, 0x02, // word count: 2, includes last word
+                0x01, 0x02, 0x03, 0x04,
+            ])
+            .unwrap(),
+            ParameterAreaReadResponse {
+                area_code: ParameterAreaCode::RoutingTables,
+                offset: 46,
+                includes_last_word: true,
+                bytes: vec![0x01, 0x02, 0x03, 0x04],
+            }
+        );
+    }
+
+    #[test]
+    fn concat_checks_offsets() {
+        let response = |offset, count: u16| ParameterAreaReadResponse {
+            area_code: ParameterAreaCode::PlcSetup,
+            offset,
+            includes_last_word: offset + count == 256,
+            bytes: vec![0; count as usize * 2],
+        };
+
+        assert_eq!(
+            ParameterAreaReadResponse::concat(
+                ParameterAreaCode::PlcSetup,
+                ParameterAreaCode::PlcSetup
+                    .chunks()
+                    .map(|(offset, count)| response(offset, count))
+            )
+            .unwrap()
+            .len(),
+            512
+        );
+
+        assert!(matches!(
+            ParameterAreaReadResponse::concat(
+                ParameterAreaCode::PlcSetup,
+                vec![response(0, 100), response(101, 155)]
+            ),
+            Err(ProtocolViolation::UnexpectedParameterAreaOffset {
+                actual: 101,
+                expected: 100
+            })
+        ));
+    }
+
+    #[test]
+    fn concat_stops_at_accessible_words() {
+        // Only the first 48 words of the routing tables can be read, so reading them completely
+        // does not depend on the last word flag.
+        let responses = ParameterAreaCode::RoutingTables
+            .chunks()
+            .map(|(offset, count)| ParameterAreaReadResponse {
+                area_code: ParameterAreaCode::RoutingTables,
+                offset,
+                includes_last_word: false,
+                bytes: vec![0; count as usize * 2],
+            });
+        assert_eq!(
+            ParameterAreaReadResponse::concat(ParameterAreaCode::RoutingTables, responses)
+                .unwrap()
+                .len(),
+            96
+        );
+    }
+}
@@ -0,0 +1,119 @@
+use std::io::Write;
+
+use crate::*;
+
+/// Writes `bytes` to the parameter area starting at word `offset`. The last word flag is set
+/// automatically when the data ends at the last word of the area.
+pub struct ParameterAreaWriteRequest {
+    pub area_code: ParameterAreaCode,
+    pub offset: u16,
+    pub bytes: Vec<u8>,
+}
+
+impl Command for ParameterAreaWriteRequest {
+    const MRC: u8 = 0x02;
+    const SRC: u8 = 0x02;
+
+    type Response = ();
+
+    fn encoded_len(&self) -> usize {
+        ::std::mem::size_of::<RawParameterAreaWriteRequestBody>() + self.bytes.len()
+    }
+
+    fn encode_body<W: Write>(&self, writer: &mut W) -> crate::Result<()> {
+        if !self.bytes.len().is_multiple_of(2) {
+            return Err(Error::InvalidValue(format!(
+                "parameter area data of {} bytes is not a number of words",
+                self.bytes.len()
+            )));
+        }
+        let count = self.bytes.len() / 2;
+        let end = self.area_code.check_range(self.offset, count)?;
+        let count = count as u16;
+        let includes_last_word = end == self.area_code.accessible_word_count();
+
+        writer.write_raw(&RawParameterAreaWriteRequestBody {
+            area_code: self.area_code.serialize(),
+            offset: u16be::from_u16(self.offset),
+            count: u16be::from_u16(if includes_last_word {
+                count | PARAMETER_AREA_LAST_WORD_BIT
+            } else {
+                count
+            }),
+        })?;
+        writer.write_all(&self.bytes)?;
+
+        Ok(())
+    }
+
+    fn decode_response(&self, bytes: &[u8]) -> Result<Self::Response, ProtocolViolation> {
+        decode_empty_response(bytes)
+    }
+
+    fn decode_body(bytes: &[u8]) -> Result<Self, ProtocolViolation> {
+        let (body, bytes) = split_raw::<RawParameterAreaWriteRequestBody>(bytes)?;
+        let word_count = body.count.to_u16() & !PARAMETER_AREA_LAST_WORD_BIT;
+        assert_body_length(bytes.len(), word_count as usize * 2)?;
+
+        Ok(Self {
+            area_code: body.area_code.deserialize()?,
+            offset: body.offset.to_u16(),
+            bytes: bytes.to_vec(),
+        })
+    }
+
+    fn encode_response<W: Write>(_response: &(), _writer: &mut W) -> crate::Result<()> {
+        Ok(())
+    }
+}
+
+#[repr(C, packed)]
+struct RawParameterAreaWriteRequestBody {
+    area_code: RawParameterAreaCode,
+    offset: u16be,
+    count: u16be,
+}
+
+unsafe_impl_raw!(RawParameterAreaWriteRequestBody);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_body_sets_last_word_bit() {
+        let request = ParameterAreaWriteRequest {
+            area_code: ParameterAreaCode::RoutingTables,
+            offset: 47,
+            bytes: vec![0x12, 0x34],
+        };
+        let mut output = vec![];
+        request.encode_body(&mut output).unwrap();
+
+        assert_eq!(output.len(), request.encoded_len());
+        assert_eq!(
+            output,
+            [
+                0x80, 0x13, // parameter area code: routing tables
+                0x00, 0x2F, // beginning word: 47
+                0x80, 0x01, // word count: 1, includes last word
+                0x12, 0x34, // data
+            ]
+        );
+    }
+
+    #[test]
+    fn encode_body_checks_range() {
+        for (offset, bytes) in [(0, vec![0; 3]), (47, vec![0; 4]), (u16::MAX, vec![0; 2])] {
+            let request = ParameterAreaWriteRequest {
+                area_code: ParameterAreaCode::RoutingTables,
+                offset,
+                bytes,
+            };
+            assert!(matches!(
+                request.encode_body(&mut vec![]),
+                Err(Error::InvalidValue(_))
+            ));
+        }
+    }
+}
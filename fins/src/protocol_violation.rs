@@ -4,6 +4,46 @@ use crate::*;
 pub enum ProtocolViolation {
     InvalidMemoryAreaCode(RawMemoryAreaCode),
     InvalidInformationControlField(RawInformationControlField),
+    InvalidParameterAreaCode(RawParameterAreaCode),
+    UnexpectedMemoryAreaCode {
+        actual: MemoryAreaCode,
+        expected: MemoryAreaCode,
+    },
+    BodyTooShort {
+        actual: usize,
+        expected: usize,
+    },
+    UnexpectedBodyLength {
+        actual: usize,
+        expected: usize,
+    },
+    UnexpectedParameterAreaCode {
+        actual: ParameterAreaCode,
+        expected: ParameterAreaCode,
+    },
+    UnexpectedParameterAreaOffset {
+        actual: u16,
+        expected: u16,
+    },
+    IncompleteParameterArea(ParameterAreaCode),
+    InvalidMessageParameter(u16),
+    InvalidAsciiCharacter(u8),
+    LoopbackMismatch,
+    InvalidBcd(u32),
+    InvalidCycleTimeParameter(u8),
+    InvalidControllerDataParameter(u8),
+    InvalidOperatingStatus(u8),
+    InvalidOperatingMode(u8),
+    UnexpectedRequest,
+    UnexpectedResponse,
+    UnexpectedCommandCode {
+        actual: [u8; 2],
+        expected: [u8; 2],
+    },
+    UnexpectedServiceId {
+        actual: u8,
+        expected: u8,
+    },
 }
 
 impl std::fmt::Display for ProtocolViolation {
@@ -15,8 +55,95 @@ impl std::fmt::Display for ProtocolViolation {
             Self::InvalidInformationControlField(val) => {
                 write!(f, "Invalid FINS information control field: {:?}", val)
             }
+            Self::InvalidParameterAreaCode(val) => {
+                write!(f, "Invalid FINS parameter area code: {:?}", val)
+            }
+            Self::UnexpectedMemoryAreaCode { actual, expected } => write!(
+                f,
+                "Received FINS memory area {:?} but expected {:?}",
+                actual, expected
+            ),
+            Self::BodyTooShort { actual, expected } => write!(
+                f,
+                "FINS body of {} bytes is too short, expected at least {} bytes",
+                actual, expected
+            ),
+            Self::UnexpectedBodyLength { actual, expected } => write!(
+                f,
+                "FINS body of {} bytes has unexpected length, expected {} bytes",
+                actual, expected
+            ),
+            Self::UnexpectedParameterAreaCode { actual, expected } => write!(
+                f,
+                "Received FINS parameter area {:?} but expected {:?}",
+                actual, expected
+            ),
+            Self::UnexpectedParameterAreaOffset { actual, expected } => write!(
+                f,
+                "Received FINS parameter area data at word {} but expected word {}",
+                actual, expected
+            ),
+            Self::IncompleteParameterArea(val) => {
+                write!(f, "FINS parameter area {:?} was not read completely", val)
+            }
+            Self::InvalidMessageParameter(val) => {
+                write!(f, "Invalid FINS message parameter: 0x{:04X}", val)
+            }
+            Self::InvalidAsciiCharacter(val) => {
+                write!(f, "Invalid FINS ASCII character: 0x{:02X}", val)
+            }
+            Self::LoopbackMismatch => {
+                write!(
+                    f,
+                    "FINS loopback test response does not match the request data"
+                )
+            }
+            Self::InvalidBcd(val) => write!(f, "Invalid FINS BCD value: 0x{:X}", val),
+            Self::InvalidCycleTimeParameter(val) => {
+                write!(f, "Invalid FINS cycle time parameter: 0x{:02X}", val)
+            }
+            Self::InvalidControllerDataParameter(val) => {
+                write!(f, "Invalid FINS controller data parameter: 0x{:02X}", val)
+            }
+            Self::InvalidOperatingStatus(val) => {
+                write!(f, "Invalid FINS operating status: 0x{:02X}", val)
+            }
+            Self::InvalidOperatingMode(val) => {
+                write!(f, "Invalid FINS operating mode: 0x{:02X}", val)
+            }
+            Self::UnexpectedRequest => write!(f, "Received FINS request but expected a response"),
+            Self::UnexpectedResponse => write!(f, "Received FINS response but expected a request"),
+            Self::UnexpectedCommandCode { actual, expected } => write!(
+                f,
+                "Received FINS response to command {:02X}{:02X} but expected {:02X}{:02X}",
+                actual[0], actual[1], expected[0], expected[1]
+            ),
+            Self::UnexpectedServiceId { actual, expected } => write!(
+                f,
+                "Received FINS response with service id {} but expected {}",
+                actual, expected
+            ),
         }
     }
 }
 
 impl std::error::Error for ProtocolViolation {}
+
+pub(crate) fn assert_body_length(actual: usize, expected: usize) -> Result<(), ProtocolViolation> {
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(ProtocolViolation::UnexpectedBodyLength { actual, expected })
+    }
+}
+
+pub(crate) fn assert_command_code(
+    actual: [u8; 2],
+    expected: [u8; 2],
+) -> Result<(), ProtocolViolation> {
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(ProtocolViolation::UnexpectedCommandCode { actual, expected })
+    }
+}
@@ -0,0 +1,92 @@
+use crate::*;
+
+/// The addresses and gateway settings of the frames exchanged between a client and a server.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Route {
+    /// Address of the unit that executes the commands.
+    pub destination: MachineAddress,
+
+    /// Address of the unit that sends the commands and receives the responses.
+    pub source: MachineAddress,
+
+    /// Permissible number of gateways (GCT) a frame may pass through.
+    pub gateway_count: u8,
+
+    /// Whether frames may pass through gateways to reach other networks.
+    pub use_gateway: bool,
+}
+
+impl Route {
+    pub const DEFAULT_GATEWAY_COUNT: u8 = 0x02;
+
+    /// A route between the CPU units of two nodes on the local network.
+    pub const fn local(server_node: u8, client_node: u8) -> Self {
+        Self::new(
+            MachineAddress::cpu(MachineAddress::LOCAL_NETWORK, server_node),
+            MachineAddress::cpu(MachineAddress::LOCAL_NETWORK, client_node),
+        )
+    }
+
+    pub const fn new(destination: MachineAddress, source: MachineAddress) -> Self {
+        Self {
+            destination,
+            source,
+            gateway_count: Self::DEFAULT_GATEWAY_COUNT,
+            use_gateway: true,
+        }
+    }
+
+    /// The route that responses take back to the client.
+    pub const fn reverse(&self) -> Self {
+        Self {
+            destination: self.source,
+            source: self.destination,
+            gateway_count: self.gateway_count,
+            use_gateway: self.use_gateway,
+        }
+    }
+
+    /// The header of a frame sent along this route.
+    pub const fn header(&self, icf: InformationControlField, sid: u8) -> Header {
+        Header {
+            icf,
+            use_gateway: self.use_gateway,
+            gct: self.gateway_count,
+            destination: self.destination,
+            source: self.source,
+            sid,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn header_works() {
+        let route = Route {
+            destination: MachineAddress::cpu_bus_unit(0x01, 0x20, 0x03).unwrap(),
+            source: MachineAddress::cpu(0x02, 0xFB),
+            gateway_count: 0x07,
+            use_gateway: true,
+        };
+
+        assert_eq!(
+            route
+                .header(InformationControlField::RequestWithResponse, 0x09)
+                .serialize()
+                .bytes(),
+            &[0x80, 0x00, 0x07, 0x01, 0x20, 0x13, 0x02, 0xFB, 0x00, 0x09]
+        );
+        assert!(MachineAddress::cpu_bus_unit(0x01, 0x20, 0x10).is_none());
+        assert_eq!(
+            route
+                .reverse()
+                .header(InformationControlField::ResponseWithResponse, 0x09)
+                .serialize()
+                .bytes(),
+            &[0xC0, 0x00, 0x07, 0x02, 0xFB, 0x00, 0x01, 0x20, 0x13, 0x09]
+        );
+    }
+}
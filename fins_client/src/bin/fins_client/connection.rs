@@ -0,0 +1,125 @@
+use std::net::IpAddr;
+
+use fins::MemoryAddress;
+use fins_client::{Client, ReadPlanner, UdpClient};
+
+use crate::Options;
+
+/// A FINS/TCP or FINS/UDP client.
+pub enum Connection {
+    Tcp(Client),
+    Udp(UdpClient),
+}
+
+impl Connection {
+    pub async fn connect(options: &Options) -> Result<Self, Box<dyn std::error::Error>> {
+        let addr = (options.host.as_str(), options.port);
+        let connect = async {
+            if options.udp {
+                let node = match options.node {
+                    Some(node) => node,
+                    None => match tokio::net::lookup_host(addr).await?.next() {
+                        Some(addr) => match addr.ip() {
+                            IpAddr::V4(ip) => ip.octets()[3],
+                            IpAddr::V6(_) => return Err("--node is required for IPv6".into()),
+                        },
+                        None => return Err(format!("{} has no address", options.host).into()),
+                    },
+                };
+                let mut client = UdpClient::connect(addr, node).await?;
+                client.set_response_timeout(options.timeout);
+                Ok(Connection::Udp(client))
+            } else {
+                let mut client = Client::connect(addr).await?;
+                client.set_response_timeout(options.timeout);
+                if let Some(node) = options.node {
+                    let mut route = *client.route();
+                    route.destination.node = node;
+                    client.set_route(route);
+                }
+                Ok(Connection::Tcp(client))
+            }
+        };
+        tokio::time::timeout(options.timeout, connect)
+            .await
+            .map_err(|_| format!("no connection to {}:{}", options.host, options.port))?
+    }
+
+    pub fn node(&self) -> u8 {
+        match self {
+            Connection::Tcp(client) => client.route().destination.node,
+            Connection::Udp(client) => client.route().destination.node,
+        }
+    }
+
+    pub async fn execute<C: fins::Command>(&self, command: &C) -> fins_tcp::Result<C::Response> {
+        match self {
+            Connection::Tcp(client) => client.execute(command).await,
+            Connection::Udp(client) => client.execute(command).await,
+        }
+    }
+
+    pub async fn read_range(
+        &self,
+        address: MemoryAddress,
+        count: usize,
+    ) -> fins_tcp::Result<Vec<u8>> {
+        match self {
+            Connection::Tcp(client) => client.read_range(address, count).await,
+            Connection::Udp(client) => client.read_range(address, count).await,
+        }
+    }
+
+    pub async fn write_range(&self, address: MemoryAddress, bytes: &[u8]) -> fins_tcp::Result<()> {
+        match self {
+            Connection::Tcp(client) => client.write_range(address, bytes).await,
+            Connection::Udp(client) => client.write_range(address, bytes).await,
+        }
+    }
+
+    /// Reads `count` items starting at `address` for every range. Over TCP the reads are planned
+    /// and pipelined, see [`ReadPlanner`].
+    pub async fn read_ranges(
+        &self,
+        ranges: &[(MemoryAddress, usize)],
+    ) -> fins_tcp::Result<Vec<Vec<u8>>> {
+        match self {
+            Connection::Tcp(client) => {
+                let plan = ReadPlanner::new().plan(ranges)?;
+                client.read_plan(&plan).await
+            }
+            Connection::Udp(client) => {
+                let mut responses = Vec::with_capacity(ranges.len());
+                for &(address, count) in ranges {
+                    responses.push(client.read_range(address, count).await?);
+                }
+                Ok(responses)
+            }
+        }
+    }
+
+    pub async fn read_bits(
+        &self,
+        address: MemoryAddress,
+        count: usize,
+    ) -> fins_tcp::Result<Vec<bool>> {
+        match self {
+            Connection::Tcp(client) => client.read_bits(address, count).await,
+            Connection::Udp(client) => client
+                .read_range(address, count)
+                .await
+                .map(|bytes| fins::decode_bit_items(&bytes)),
+        }
+    }
+
+    pub async fn write_bits(&self, address: MemoryAddress, bits: &[bool]) -> fins_tcp::Result<()> {
+        match self {
+            Connection::Tcp(client) => client.write_bits(address, bits).await,
+            Connection::Udp(client) => {
+                client
+                    .write_range(address, &fins::encode_bit_items(bits))
+                    .await
+            }
+        }
+    }
+}
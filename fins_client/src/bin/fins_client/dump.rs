@@ -0,0 +1,345 @@
+use std::time::SystemTime;
+
+use fins::{ControllerDataReadRequest, EndCode, MemoryAddress};
+use fins_client::Client;
+
+use crate::{format_utc, Connection};
+
+/// First line of dump files, with the version of the format.
+const MAGIC: &str = "FINS DUMP 1";
+
+/// A snapshot of consecutive words.
+///
+/// Dump files start with a header of `key: value` lines, which ends with an empty line and is
+/// followed by the big-endian words:
+///
+/// ```text
+/// FINS DUMP 1
+/// model: CJ2M-CPU33
+/// area: D
+/// start: 100
+/// count: 2000
+/// time: 2026-10-19T07:11:00.123Z
+///
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dump {
+    /// The model of the unit the words were read from.
+    pub model: String,
+    /// The first word.
+    pub address: MemoryAddress,
+    /// When the words were read, in UTC.
+    pub time: String,
+    pub bytes: Vec<u8>,
+}
+
+impl Dump {
+    pub fn count(&self) -> usize {
+        self.bytes.len() / 2
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let header = format!(
+            "{}\nmodel: {}\narea: {}\nstart: {}\ncount: {}\ntime: {}\n\n",
+            MAGIC,
+            self.model,
+            self.address.area_code,
+            self.address.offset,
+            self.count(),
+            self.time
+        );
+        let mut bytes = header.into_bytes();
+        bytes.extend_from_slice(&self.bytes);
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let end = bytes
+            .windows(2)
+            .position(|window| window == b"\n\n")
+            .ok_or("missing the end of the header")?;
+        let header = std::str::from_utf8(&bytes[..end]).map_err(|_| "invalid header")?;
+        let mut lines = header.lines();
+        if lines.next() != Some(MAGIC) {
+            return Err("not a dump file".to_string());
+        }
+
+        let mut fields = std::collections::HashMap::new();
+        for line in lines {
+            let (key, value) = line
+                .split_once(": ")
+                .ok_or(format!("invalid header line {:?}", line))?;
+            fields.insert(key, value);
+        }
+        let field = |key: &str| {
+            fields
+                .get(key)
+                .copied()
+                .ok_or(format!("missing {} in the header", key))
+        };
+        let address = format!("{}{}", field("area")?, field("start")?)
+            .parse::<MemoryAddress>()
+            .map_err(|error| error.to_string())?;
+        let count = field("count")?
+            .parse::<usize>()
+            .map_err(|_| "invalid count")?;
+        check_range(address, count)?;
+
+        let words = &bytes[end + 2..];
+        if words.len() != count * 2 {
+            return Err(format!(
+                "expected {} words but found {} bytes",
+                count,
+                words.len()
+            ));
+        }
+        Ok(Self {
+            model: field("model")?.to_string(),
+            address,
+            time: field("time")?.to_string(),
+            bytes: words.to_vec(),
+        })
+    }
+}
+
+/// Checks that `address` is a word and that `count` words starting at it fit in its area.
+pub fn check_range(address: MemoryAddress, count: usize) -> Result<(), String> {
+    let word_count = address.area_code.word_count();
+    if address.area_code.is_bit_area() {
+        Err(format!("{:?} is not a word address", address))
+    } else if address.offset as usize + count > word_count {
+        Err(format!(
+            "{} words starting at {:?} exceed the {} words of the {} area",
+            count, address, word_count, address.area_code
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// The address `count` words after `address`. Fails like a unit would beyond the end of the area.
+fn offset(address: MemoryAddress, count: usize) -> fins_tcp::Result<MemoryAddress> {
+    address
+        .offset_by(count)
+        .ok_or(fins_tcp::Error::EndCode(EndCode::ADDRESS_RANGE_EXCEEDED))
+}
+
+/// Reads `count` words starting at `address` into a dump.
+pub async fn dump(
+    connection: &Connection,
+    address: MemoryAddress,
+    count: usize,
+) -> Result<Dump, Box<dyn std::error::Error>> {
+    let model = connection.execute(&ControllerDataReadRequest).await?.model;
+    let time = format_utc(SystemTime::now());
+    let bytes = read_chunked(connection, address, count).await?;
+    Ok(Dump {
+        model,
+        address,
+        time,
+        bytes,
+    })
+}
+
+/// Writes the words of `dump` back. With `diff`, the words are read first and only the runs of
+/// words that differ are written. Returns the number of words written and the number of writes.
+pub async fn restore(
+    connection: &Connection,
+    dump: &Dump,
+    diff: bool,
+) -> Result<(usize, usize), Box<dyn std::error::Error>> {
+    let runs = if diff {
+        let current = read_chunked(connection, dump.address, dump.count()).await?;
+        changed_runs(&current, &dump.bytes)
+    } else {
+        vec![(0, dump.count())]
+    };
+
+    let mut words = 0;
+    let mut writes = 0;
+    for (start, count) in runs {
+        let address = offset(dump.address, start)?;
+        writes += write_chunked(connection, address, &dump.bytes[start * 2..][..count * 2]).await?;
+        words += count;
+    }
+    Ok((words, writes))
+}
+
+/// The start and count of the runs of words that differ between `old` and `new`.
+fn changed_runs(old: &[u8], new: &[u8]) -> Vec<(usize, usize)> {
+    assert_eq!(old.len(), new.len());
+    let mut runs = Vec::<(usize, usize)>::new();
+    for (index, (old, new)) in old.chunks(2).zip(new.chunks(2)).enumerate() {
+        if old == new {
+            continue;
+        }
+        match runs.last_mut() {
+            Some((start, count)) if *start + *count == index => *count += 1,
+            _ => runs.push((index, 1)),
+        }
+    }
+    runs
+}
+
+/// Reads `count` words in chunks as large as the unit accepts. Chunks start at the default
+/// maximum and are halved while the unit responds that a frame is too long.
+async fn read_chunked(
+    connection: &Connection,
+    address: MemoryAddress,
+    count: usize,
+) -> fins_tcp::Result<Vec<u8>> {
+    let mut chunk = Client::DEFAULT_MAX_READ_COUNT as usize;
+    let mut bytes = Vec::with_capacity(count * 2);
+    while bytes.len() < count * 2 {
+        let done = bytes.len() / 2;
+        let address = offset(address, done)?;
+        match connection
+            .read_range(address, chunk.min(count - done))
+            .await
+        {
+            Err(fins_tcp::Error::EndCode(EndCode::COMMAND_TOO_LONG)) if chunk > 1 => {
+                chunk /= 2;
+                tracing::info!("reading chunks of {} words", chunk);
+            }
+            result => bytes.extend(result?),
+        }
+    }
+    Ok(bytes)
+}
+
+/// Writes `bytes` in chunks like [`read_chunked`] and returns the number of writes.
+async fn write_chunked(
+    connection: &Connection,
+    address: MemoryAddress,
+    bytes: &[u8],
+) -> fins_tcp::Result<usize> {
+    let mut chunk = Client::DEFAULT_MAX_WRITE_COUNT as usize;
+    let mut done = 0;
+    let mut writes = 0;
+    while done < bytes.len() / 2 {
+        let count = chunk.min(bytes.len() / 2 - done);
+        let address = offset(address, done)?;
+        match connection
+            .write_range(address, &bytes[done * 2..][..count * 2])
+            .await
+        {
+            Err(fins_tcp::Error::EndCode(EndCode::COMMAND_TOO_LONG)) if chunk > 1 => {
+                chunk /= 2;
+                tracing::info!("writing chunks of {} words", chunk);
+            }
+            result => {
+                result?;
+                done += count;
+                writes += 1;
+            }
+        }
+    }
+    Ok(writes)
+}
+
+#[cfg(test)]
+mod tests {
+    use fins::MemoryAreaCode;
+    use fins_server::{MemoryHandler, Server};
+
+    use super::*;
+
+    /// Accepts at most 300 words per frame.
+    fn handler() -> MemoryHandler {
+        let mut handler = MemoryHandler::default();
+        handler.set_max_count(300);
+        handler
+    }
+
+    /// The bytes of the D area words in `range`.
+    fn words(handler: &MemoryHandler, range: std::ops::Range<usize>) -> Vec<u8> {
+        handler.memory().area(MemoryAreaCode::D)[range]
+            .iter()
+            .flat_map(|word| word.to_be_bytes())
+            .collect()
+    }
+
+    fn address(text: &str) -> MemoryAddress {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn dump_bytes_work() {
+        let dump = Dump {
+            model: "CJ2M-CPU33".to_string(),
+            address: address("E2_100"),
+            time: "2026-10-19T07:11:00.123Z".to_string(),
+            bytes: vec![0x12, 0x34, 0x0A, 0x0A],
+        };
+        let bytes = dump.to_bytes();
+        assert!(bytes.starts_with(b"FINS DUMP 1\nmodel: CJ2M-CPU33\narea: E2_\nstart: 100\n"));
+        assert!(bytes.ends_with(b"\n\n\x12\x34\x0A\x0A"));
+        assert_eq!(Dump::from_bytes(&bytes).as_ref(), Ok(&dump));
+
+        assert!(Dump::from_bytes(&bytes[..bytes.len() - 2]).is_err());
+        assert!(Dump::from_bytes(b"FINS DUMP 2\n\n").is_err());
+
+        // The words have to fit in the area.
+        let mut dump = dump;
+        dump.address = address("D32767");
+        assert!(Dump::from_bytes(&dump.to_bytes()).is_err());
+        dump.address = address("D32766");
+        assert!(Dump::from_bytes(&dump.to_bytes()).is_ok());
+    }
+
+    #[test]
+    fn changed_runs_work() {
+        let old = [0, 1, 0, 2, 0, 3, 0, 4, 0, 5];
+        let new = [0, 1, 9, 2, 0, 9, 0, 4, 9, 9];
+        assert_eq!(changed_runs(&old, &new), [(1, 2), (4, 1)]);
+        assert!(changed_runs(&old, &old).is_empty());
+    }
+
+    #[tokio::test]
+    async fn dump_and_restore_work() {
+        let handler = handler();
+        for (offset, word) in handler.memory().area(MemoryAreaCode::D)[..4000]
+            .iter_mut()
+            .enumerate()
+        {
+            *word = u16::from_be_bytes([(offset * 2) as u8, (offset * 2 + 1) as u8]);
+        }
+        let server = Server::bind("127.0.0.1:0", 0x01, handler.clone())
+            .await
+            .unwrap();
+        let addr = server.local_addr().unwrap();
+        tokio::spawn(server.run());
+        let connection = Connection::Tcp(Client::connect(addr).await.unwrap());
+
+        // Chunks of 999 and 499 words are too long, chunks of 249 words fit.
+        let start = address("D100");
+        let bytes = read_chunked(&connection, start, 2000).await.unwrap();
+        assert_eq!(bytes, words(&handler, 100..2100));
+
+        let dump = Dump {
+            model: String::new(),
+            address: start,
+            time: String::new(),
+            bytes,
+        };
+        {
+            let mut memory = handler.memory();
+            let words = memory.area(MemoryAreaCode::D);
+            words[150] |= 0xFF00;
+            words[151] |= 0x00FF;
+            words[1500] |= 0xFF00;
+        }
+        assert_eq!(restore(&connection, &dump, true).await.unwrap(), (3, 2));
+        let writes = handler
+            .accesses()
+            .iter()
+            .filter(|access| access.write)
+            .map(|access| (access.address.offset, access.count))
+            .collect::<Vec<_>>();
+        assert_eq!(writes, [(150, 2), (1500, 1)]);
+        assert_eq!(words(&handler, 100..2100), dump.bytes);
+
+        handler.accesses().clear();
+        assert_eq!(restore(&connection, &dump, false).await.unwrap(), (2000, 9));
+    }
+}
@@ -0,0 +1,349 @@
+use std::convert::TryFrom;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use fins::MemoryAddress;
+use serde_json::{json, Value};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Format {
+    Hex,
+    Dec,
+    Bcd,
+    Float,
+    Dump,
+}
+
+impl Format {
+    /// The number of words that hold a value.
+    pub fn word_count(self) -> usize {
+        match self {
+            Format::Float => 2,
+            _ => 1,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Output {
+    Table,
+    Json,
+}
+
+impl std::str::FromStr for Format {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text {
+            "hex" => Ok(Format::Hex),
+            "dec" => Ok(Format::Dec),
+            "bcd" => Ok(Format::Bcd),
+            "float" => Ok(Format::Float),
+            "dump" => Ok(Format::Dump),
+            _ => Err(format!("unknown format {:?}", text)),
+        }
+    }
+}
+
+impl std::str::FromStr for Output {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text {
+            "table" => Ok(Output::Table),
+            "json" => Ok(Output::Json),
+            _ => Err(format!("unknown output {:?}", text)),
+        }
+    }
+}
+
+/// Fails for formats that `output` can not show.
+pub fn check_output(format: Format, output: Output) -> Result<(), String> {
+    if format == Format::Dump && output == Output::Json {
+        return Err("the dump format needs table output".to_string());
+    }
+    Ok(())
+}
+
+pub fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02X}", byte)).collect()
+}
+
+/// Formats the values of `format` in the words in `bytes`, which start at `address`.
+pub fn format_words(address: MemoryAddress, bytes: &[u8], format: Format) -> Vec<(String, Value)> {
+    let word_count = format.word_count();
+    bytes
+        .chunks_exact(word_count * 2)
+        .enumerate()
+        .map(|(index, bytes)| {
+            let word = u16::from_be_bytes([bytes[0], bytes[1]]);
+            let value = match format {
+                Format::Hex | Format::Dump => json!(format!("0x{:04X}", word)),
+                Format::Dec => json!(word),
+                Format::Bcd => json!(fins_util::from_bcd_u32(word as u32)),
+                Format::Float => {
+                    let high = u16::from_be_bytes([bytes[2], bytes[3]]);
+                    let value = f32::from_bits((high as u32) << 16 | word as u32);
+                    // Through the shortest text that parses back to the float, so that 1.1 is
+                    // not shown as 1.100000023841858.
+                    json!(value.to_string().parse::<f64>().unwrap())
+                }
+            };
+            (item_name(address, index * word_count), value)
+        })
+        .collect()
+}
+
+pub fn format_bits(address: MemoryAddress, bits: &[bool]) -> Vec<(String, Value)> {
+    bits.iter()
+        .enumerate()
+        .map(|(index, &bit)| (item_name(address, index), json!(bit as u8)))
+        .collect()
+}
+
+/// The address of the item `index` items after `address`, or the offset from `address` for
+/// items beyond the end of the area.
+pub fn item_name(address: MemoryAddress, index: usize) -> String {
+    match address.offset_by(index) {
+        Some(address) => format!("{:?}", address),
+        None => format!("{:?}+{}", address, index),
+    }
+}
+
+/// Formats every word in `bytes`, which start at `address`, in hex, binary, decimal and ASCII.
+pub fn dump_bytes(address: MemoryAddress, bytes: &[u8]) -> Vec<String> {
+    let ascii = |byte: u8| {
+        if byte.is_ascii_graphic() {
+            byte as char
+        } else {
+            ' '
+        }
+    };
+    bytes
+        .chunks_exact(2)
+        .enumerate()
+        .map(|(index, word)| {
+            format!(
+                "{0:>8}: 0x{1:02X} 0x{2:02X} | 0b{1:08b} 0b{2:08b} | {1:3} {2:3} | {3} {4} | {5}",
+                item_name(address, index),
+                word[0],
+                word[1],
+                ascii(word[0]),
+                ascii(word[1]),
+                u16::from_be_bytes([word[0], word[1]])
+            )
+        })
+        .collect()
+}
+
+pub fn print_values(output: Output, values: &[(String, Value)]) {
+    match output {
+        Output::Table => {
+            for (name, value) in values {
+                println!("{:<10} {}", name, table_cell(value));
+            }
+        }
+        Output::Json => {
+            let values = values
+                .iter()
+                .map(|(name, value)| json!({ "address": name, "value": value }))
+                .collect::<Vec<_>>();
+            println!("{}", Value::Array(values));
+        }
+    }
+}
+
+pub fn print_record(output: Output, fields: Vec<(&str, Value)>) {
+    match output {
+        Output::Table => {
+            for (name, value) in fields {
+                println!("{:<24} {}", name.replace('_', " "), table_cell(&value));
+            }
+        }
+        Output::Json => {
+            let fields = fields
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value))
+                .collect();
+            println!("{}", Value::Object(fields));
+        }
+    }
+}
+
+pub fn table_cell(value: &Value) -> String {
+    match value {
+        Value::String(text) => text.clone(),
+        Value::Null => "-".to_string(),
+        value => value.to_string(),
+    }
+}
+
+/// Parses a decimal or hexadecimal integer, like `-12` or `0x00FF`.
+pub fn parse_integer(text: &str) -> Result<i64, String> {
+    let result = match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        Some(digits) => i64::from_str_radix(digits, 16),
+        None => text.parse(),
+    };
+    result.map_err(|_| format!("invalid number {:?}", text))
+}
+
+/// Encodes `values` as words in `format`.
+pub fn parse_words(values: &[String], format: Format) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::with_capacity(values.len() * format.word_count() * 2);
+    for text in values {
+        match format {
+            Format::Hex | Format::Dec | Format::Dump => {
+                let value = parse_integer(text)?;
+                if !(i16::MIN as i64..=u16::MAX as i64).contains(&value) {
+                    return Err(format!("{} does not fit in a word", value));
+                }
+                bytes.extend_from_slice(&(value as u16).to_be_bytes());
+            }
+            Format::Bcd => {
+                let value = parse_integer(text)?;
+                let bcd = u32::try_from(value)
+                    .ok()
+                    .filter(|&value| value <= 9999)
+                    .and_then(fins_util::to_bcd_u32)
+                    .ok_or(format!("{} is not a BCD word from 0 to 9999", value))?;
+                bytes.extend_from_slice(&(bcd as u16).to_be_bytes());
+            }
+            Format::Float => {
+                let value = text
+                    .parse::<f32>()
+                    .map_err(|_| format!("invalid float {:?}", text))?
+                    .to_bits();
+                bytes.extend_from_slice(&(value as u16).to_be_bytes());
+                bytes.extend_from_slice(&((value >> 16) as u16).to_be_bytes());
+            }
+        }
+    }
+    Ok(bytes)
+}
+
+pub fn parse_bit(text: &str) -> Result<bool, String> {
+    match text {
+        "0" | "false" => Ok(false),
+        "1" | "true" => Ok(true),
+        _ => Err(format!(
+            "invalid bit {:?}, expected 0, 1, false or true",
+            text
+        )),
+    }
+}
+/// Formats `time` in UTC like `2026-10-19T07:11:00.123Z`.
+pub fn format_utc(time: SystemTime) -> String {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let seconds = since_epoch.as_secs();
+    let (days, seconds) = (seconds / 86400, seconds % 86400);
+
+    // The civil date of the days since 1970-01-01, see
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days.
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let day_of_era = z - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + (month <= 2) as u64;
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60,
+        since_epoch.subsec_millis()
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn args(text: &str) -> Vec<String> {
+        text.split_whitespace().map(str::to_string).collect()
+    }
+
+    fn address(text: &str) -> MemoryAddress {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn format_utc_works() {
+        assert_eq!(format_utc(UNIX_EPOCH), "1970-01-01T00:00:00.000Z");
+        assert_eq!(
+            format_utc(UNIX_EPOCH + Duration::from_secs(951_782_400)),
+            "2000-02-29T00:00:00.000Z"
+        );
+        assert_eq!(
+            format_utc(UNIX_EPOCH + Duration::from_millis(1_700_000_000_042)),
+            "2023-11-14T22:13:20.042Z"
+        );
+    }
+
+    #[test]
+    fn format_words_works() {
+        let bytes = [0x12, 0x34, 0x00, 0xFF];
+        let values = |format| {
+            format_words(address("D100"), &bytes, format)
+                .into_iter()
+                .map(|(name, value)| format!("{} {}", name, table_cell(&value)))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(values(Format::Hex), ["D100 0x1234", "D101 0x00FF"]);
+        assert_eq!(values(Format::Dec), ["D100 4660", "D101 255"]);
+        assert_eq!(values(Format::Bcd), ["D100 1234", "D101 -"]);
+        assert_eq!(item_name(address("W511"), 0), "W511");
+        assert_eq!(item_name(address("W511"), 2), "W511+2");
+
+        let bytes = parse_words(&args("1.1 -2"), Format::Float).unwrap();
+        assert_eq!(&bytes[..4], [0xCC, 0xCD, 0x3F, 0x8C]);
+        let values = format_words(address("D100"), &bytes, Format::Float);
+        assert_eq!(
+            values,
+            [
+                ("D100".to_string(), json!(1.1)),
+                ("D102".to_string(), json!(-2.0))
+            ]
+        );
+
+        assert_eq!(
+            format_bits(address("W3.15"), &[true, false]),
+            [
+                ("W3.15".to_string(), json!(1)),
+                ("W4.0".to_string(), json!(0))
+            ]
+        );
+        assert_eq!(
+            dump_bytes(address("D1"), b"AB"),
+            ["      D1: 0x41 0x42 | 0b01000001 0b01000010 |  65  66 | A B | 16706"]
+        );
+    }
+
+    #[test]
+    fn parse_words_works() {
+        assert_eq!(
+            parse_words(&args("0x00FF -1 65535"), Format::Hex).unwrap(),
+            [0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]
+        );
+        assert_eq!(
+            parse_words(&args("650"), Format::Bcd).unwrap(),
+            [0x06, 0x50]
+        );
+        assert!(parse_words(&args("10000"), Format::Bcd).is_err());
+        assert!(parse_words(&args("65536"), Format::Dec).is_err());
+        assert_eq!(parse_bit("true"), Ok(true));
+        assert!(parse_bit("2").is_err());
+    }
+}
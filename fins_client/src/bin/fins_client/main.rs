@@ -0,0 +1,782 @@
+mod connection;
+mod dump;
+mod format;
+mod shell;
+mod tags;
+mod watch;
+
+use std::time::Duration;
+
+use fins::{
+    ClockReadRequest, ControllerDataReadRequest, ControllerStatusReadRequest, LoopbackTestRequest,
+    MemoryAddress,
+};
+use fins_client::Client;
+use serde_json::{json, Value};
+
+use connection::*;
+use dump::*;
+use format::*;
+use tags::*;
+use watch::*;
+
+const USAGE: &str = "\
+Usage: fins_client [OPTIONS] <COMMAND>
+
+Commands:
+    read <ADDR> [COUNT]     Read COUNT values starting at ADDR, like D100 or W3.02 [default: 1]
+    write <ADDR> <VALUE>... Write values starting at ADDR
+    status                  Read the operating status and errors of the CPU unit
+    info                    Read the model and version of the CPU unit
+    clock                   Read the clock of the CPU unit
+    ping [COUNT] [SIZE]     Send COUNT loopback tests of SIZE bytes [default: 4 32]
+    bench [COUNT]           Read COUNT words starting at D0 and report the round-trip times
+                            [default: 2000]
+    watch <ADDR> [COUNT]... Read COUNT values starting at every ADDR and show them in a table
+                            with the time and number of their changes
+    dump <AREA> <START> <COUNT> -o <FILE>
+                            Save COUNT words starting at word START of AREA, like D or E0_, to
+                            a dump file
+    restore <FILE> [--diff] [--force]
+                            Write the words of a dump file back. With --diff only the words
+                            that changed are written. Dumps of other models are refused
+                            without --force
+    shell                   Run commands typed at a prompt over one session
+
+Options:
+    --host <HOST>           Host name or address of the unit, or FINS_HOST
+    --port <PORT>           Port of the unit [default: 9600]
+    --udp                   Use FINS/UDP instead of FINS/TCP
+    --node <NODE>           Node of the unit [default: assigned by the unit over TCP, the last
+                            byte of its IP address over UDP]
+    --timeout <SECONDS>     Response timeout [default: 5]
+    --format <FORMAT>       Format of the values of words, one of hex, dec, bcd, float and dump
+                            [default: hex]
+    --output <OUTPUT>       Output as a table or json [default: table]
+    --interval <SECONDS>    Time between the reads of watch [default: 1]
+    --log <FILE>            Append the time and values of every read of watch to a CSV file
+    --tags <FILE>           Use the names of the name and address columns of a tag CSV file
+                            as addresses
+
+Values are decimal or hexadecimal with 0x, and 0, 1, false or true for bits. Floats take two
+words, the low word first. The dump format shows every word in hex, binary, decimal and ASCII,
+and is the default format of the shell.
+";
+
+#[derive(Debug, Clone, PartialEq)]
+enum Command {
+    Read {
+        address: MemoryAddress,
+        count: usize,
+    },
+    Write {
+        address: MemoryAddress,
+        values: Vec<String>,
+    },
+    Status,
+    Info,
+    Clock,
+    Ping {
+        count: usize,
+        size: usize,
+    },
+    Bench {
+        count: usize,
+    },
+    Watch {
+        items: Vec<WatchItem>,
+        interval: Duration,
+        log: Option<String>,
+    },
+    Dump {
+        address: MemoryAddress,
+        count: usize,
+        path: String,
+    },
+    Restore {
+        path: String,
+        diff: bool,
+        force: bool,
+    },
+    Shell,
+}
+
+#[derive(Debug)]
+struct Options {
+    host: String,
+    port: u16,
+    udp: bool,
+    node: Option<u8>,
+    timeout: Duration,
+    /// The format given with `--format`, if any.
+    format: Option<Format>,
+    output: Output,
+    tags: Tags,
+    command: Command,
+}
+
+fn parse_options<I: IntoIterator<Item = String>>(args: I) -> Result<Options, String> {
+    let mut host = std::env::var("FINS_HOST").ok();
+    let mut port = 9600;
+    let mut udp = false;
+    let mut node = None;
+    let mut timeout = Client::DEFAULT_RESPONSE_TIMEOUT;
+    let mut format = None;
+    let mut output = Output::Table;
+    let mut tags = Tags::default();
+    let mut positional = Vec::new();
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("missing value for {}", arg));
+        match arg.as_str() {
+            "--host" => host = Some(value()?),
+            "--port" => port = value()?.parse().map_err(|e| format!("--port: {}", e))?,
+            "--udp" => udp = true,
+            "--node" => node = Some(value()?.parse().map_err(|e| format!("--node: {}", e))?),
+            "--timeout" => {
+                let seconds = value()?
+                    .parse::<f64>()
+                    .map_err(|e| format!("--timeout: {}", e))?;
+                if !(seconds > 0.0 && seconds.is_finite()) {
+                    return Err("--timeout: expected a positive number of seconds".to_string());
+                }
+                timeout = Duration::from_secs_f64(seconds);
+            }
+            "--format" => format = Some(value()?.parse().map_err(|e| format!("--format: {}", e))?),
+            "--output" => output = value()?.parse().map_err(|e| format!("--output: {}", e))?,
+            "--tags" => {
+                let path = value()?;
+                let text =
+                    std::fs::read_to_string(&path).map_err(|e| format!("{}: {}", path, e))?;
+                tags = Tags::from_csv(&text).map_err(|e| format!("{}: {}", path, e))?;
+            }
+            // Options of commands, which are parsed along with their arguments.
+            "--interval" | "--log" | "-o" => {
+                let value = value()?;
+                positional.extend([arg, value]);
+            }
+            "--diff" | "--force" => positional.push(arg),
+            "--help" | "-h" => return Err(String::new()),
+            _ if arg.starts_with("--") => return Err(format!("unknown argument {:?}", arg)),
+            _ => positional.push(arg),
+        }
+    }
+
+    let command = parse_command(&positional, &tags)?;
+    if let Some(format) = format {
+        check_output(format, output)?;
+    }
+
+    Ok(Options {
+        host: host.ok_or("missing --host")?,
+        port,
+        udp,
+        node,
+        timeout,
+        format,
+        output,
+        tags,
+        command,
+    })
+}
+
+/// Parses a command and its arguments, with addresses that may be the names of `tags`.
+fn parse_command(args: &[String], tags: &Tags) -> Result<Command, String> {
+    let (name, args) = args.split_first().ok_or("missing command")?;
+    let address = || -> Result<MemoryAddress, String> {
+        let text = args.first().ok_or(format!("{}: missing address", name))?;
+        tags.resolve(text)
+            .ok_or(format!("{}: invalid address or tag {:?}", name, text))
+    };
+    let number = |index: usize, default| -> Result<usize, String> {
+        args.get(index).map_or(Ok(default), |text| {
+            text.parse()
+                .map_err(|_| format!("{}: invalid number {:?}", name, text))
+        })
+    };
+    let max_args = |max: usize| {
+        if args.len() > max {
+            Err(format!("{}: unexpected argument {:?}", name, args[max]))
+        } else {
+            Ok(())
+        }
+    };
+
+    match name.as_str() {
+        "read" => {
+            max_args(2)?;
+            let (address, count) = (address()?, number(1, 1)?);
+            if address.offset_by(count).is_none() {
+                return Err(format!(
+                    "read: {} values starting at {:?} exceed the {} area",
+                    count, address, address.area_code
+                ));
+            }
+            Ok(Command::Read { address, count })
+        }
+        "write" => {
+            let address = address()?;
+            if args.len() < 2 {
+                return Err("write: missing values".to_string());
+            }
+            Ok(Command::Write {
+                address,
+                values: args[1..].to_vec(),
+            })
+        }
+        "status" => max_args(0).map(|_| Command::Status),
+        "info" => max_args(0).map(|_| Command::Info),
+        "clock" => max_args(0).map(|_| Command::Clock),
+        "ping" => {
+            max_args(2)?;
+            let (count, size) = (number(0, 4)?, number(1, 32)?);
+            if size > LoopbackTestRequest::MAX_BYTE_COUNT {
+                return Err(format!(
+                    "ping: size {} exceeds {} bytes",
+                    size,
+                    LoopbackTestRequest::MAX_BYTE_COUNT
+                ));
+            }
+            Ok(Command::Ping { count, size })
+        }
+        "bench" => {
+            max_args(1)?;
+            Ok(Command::Bench {
+                count: number(0, 2000)?,
+            })
+        }
+        "watch" => parse_watch(args, tags),
+        "dump" => {
+            let (arguments, path) = match args {
+                [area, start, count, o, path] if o == "-o" => ([area, start, count], path),
+                _ => return Err("dump: expected <AREA> <START> <COUNT> -o <FILE>".to_string()),
+            };
+            let [area, start, count] = arguments;
+            let address = format!("{}{}", area, start)
+                .parse::<MemoryAddress>()
+                .ok()
+                .filter(|address| !address.area_code.is_bit_area())
+                .ok_or(format!(
+                    "dump: invalid area {:?} or start {:?}",
+                    area, start
+                ))?;
+            let count = count
+                .parse()
+                .map_err(|_| format!("dump: invalid count {:?}", count))?;
+            dump::check_range(address, count).map_err(|error| format!("dump: {}", error))?;
+            Ok(Command::Dump {
+                address,
+                count,
+                path: path.clone(),
+            })
+        }
+        "restore" => {
+            let flag = |flag: &str| args.iter().any(|arg| arg == flag);
+            let mut paths = args.iter().filter(|arg| !arg.starts_with("--"));
+            match (paths.next(), paths.next()) {
+                (Some(path), None) => Ok(Command::Restore {
+                    path: path.clone(),
+                    diff: flag("--diff"),
+                    force: flag("--force"),
+                }),
+                _ => Err("restore: expected <FILE> [--diff] [--force]".to_string()),
+            }
+        }
+        "shell" => max_args(0).map(|_| Command::Shell),
+        _ => Err(format!("unknown command {:?}", name)),
+    }
+}
+/// Parses the arguments of watch, addresses or tags that are each followed by an optional count,
+/// and the `--interval` and `--log` options.
+fn parse_watch(args: &[String], tags: &Tags) -> Result<Command, String> {
+    let mut items = Vec::<WatchItem>::new();
+    let mut interval = Duration::from_secs(1);
+    let mut log = None;
+    // Whether the last item has a count.
+    let mut counted = true;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || {
+            args.next()
+                .ok_or(format!("watch: missing value for {}", arg))
+        };
+        match arg.as_str() {
+            "--interval" => {
+                let text = value()?;
+                let seconds = text
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|seconds| *seconds > 0.0 && seconds.is_finite())
+                    .ok_or(format!("watch: invalid interval {:?}", text))?;
+                interval = Duration::from_secs_f64(seconds);
+            }
+            "--log" => log = Some(value()?.clone()),
+            _ => match arg.parse::<usize>() {
+                Ok(count) if !counted && count > 0 => {
+                    items.last_mut().unwrap().count = count;
+                    counted = true;
+                }
+                _ => {
+                    let address = tags
+                        .resolve(arg)
+                        .ok_or(format!("watch: invalid address or tag {:?}", arg))?;
+                    let name = if arg.parse::<MemoryAddress>().is_ok() {
+                        String::new()
+                    } else {
+                        arg.clone()
+                    };
+                    items.push(WatchItem {
+                        name,
+                        address,
+                        count: 1,
+                    });
+                    counted = false;
+                }
+            },
+        }
+    }
+
+    if items.is_empty() {
+        return Err("watch: missing address".to_string());
+    }
+    Ok(Command::Watch {
+        items,
+        interval,
+        log,
+    })
+}
+
+#[tokio::main]
+async fn main() {
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            std::env::var("RUST_LOG").unwrap_or_else(|_| "fins_client=warn".to_string()),
+        )
+        .with_writer(std::io::stderr)
+        .init();
+
+    let options = match parse_options(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(error) => {
+            if !error.is_empty() {
+                eprintln!("error: {}\n", error);
+            }
+            eprint!("{}", USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    let result = match Connection::connect(&options).await {
+        Ok(connection) if options.command == Command::Shell => {
+            shell::run_shell(&connection, &options).await
+        }
+        Ok(connection) => {
+            let format = options.format.unwrap_or(Format::Hex);
+            tokio::select! {
+                result = run(&connection, &options.command, format, options.output) => result,
+                _ = tokio::signal::ctrl_c() => Ok(()),
+            }
+        }
+        Err(error) => Err(error),
+    };
+    if let Err(error) = result {
+        eprintln!("error: {}", error);
+        std::process::exit(1);
+    }
+}
+
+/// Runs `command`, which is not [`Command::Shell`], and prints the results.
+async fn run(
+    connection: &Connection,
+    command: &Command,
+    format: Format,
+    output: Output,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match command {
+        &Command::Read { address, count } => {
+            print_reading(output, &read(connection, address, count, format).await?);
+        }
+        Command::Write { address, values } => {
+            if address.area_code.is_bit_area() {
+                let bits = values
+                    .iter()
+                    .map(|value| parse_bit(value))
+                    .collect::<Result<Vec<_>, _>>()?;
+                connection.write_bits(*address, &bits).await?;
+            } else {
+                let bytes = parse_words(values, format)?;
+                connection.write_range(*address, &bytes).await?;
+            }
+        }
+        Command::Status => {
+            let status = connection.execute(&ControllerStatusReadRequest).await?;
+            print_record(
+                output,
+                vec![
+                    ("status", json!(format!("{:?}", status.status))),
+                    ("mode", json!(format!("{:?}", status.mode))),
+                    (
+                        "fatal_error",
+                        json!(format!("0x{:04X}", status.fatal_error)),
+                    ),
+                    (
+                        "non_fatal_error",
+                        json!(format!("0x{:04X}", status.non_fatal_error)),
+                    ),
+                    ("messages", json!(format!("0b{:08b}", status.messages))),
+                    ("fal_number", json!(format!("0x{:04X}", status.fal_number))),
+                    ("error_message", json!(status.error_message)),
+                ],
+            );
+        }
+        Command::Info => {
+            let data = connection.execute(&ControllerDataReadRequest).await?;
+            print_record(
+                output,
+                vec![
+                    ("model", json!(data.model)),
+                    ("version", json!(data.version)),
+                    ("area_data", json!(hex(&data.area_data))),
+                ],
+            );
+        }
+        Command::Clock => {
+            let clock = connection.execute(&ClockReadRequest).await?;
+            // The units count years from 1998 to 2069 with two digits.
+            let century = if clock.year < 70 { 2000 } else { 1900 };
+            print_record(
+                output,
+                vec![
+                    (
+                        "time",
+                        json!(format!(
+                            "{}-{:02}-{:02} {:02}:{:02}:{:02}",
+                            century + clock.year as u16,
+                            clock.month,
+                            clock.day,
+                            clock.hour,
+                            clock.minute,
+                            clock.second
+                        )),
+                    ),
+                    ("day_of_week", json!(clock.day_of_week)),
+                ],
+            );
+        }
+        &Command::Ping { count, size } => ping(connection, output, count, size).await?,
+        &Command::Bench { count } => bench(connection, output, count).await?,
+        Command::Watch {
+            items,
+            interval,
+            log,
+        } => watch(connection, items, *interval, format, output, log.as_deref()).await?,
+        &Command::Dump {
+            address,
+            count,
+            ref path,
+        } => {
+            let dump = dump(connection, address, count).await?;
+            std::fs::write(path, dump.to_bytes()).map_err(|e| format!("{}: {}", path, e))?;
+            print_record(
+                output,
+                vec![
+                    ("file", json!(path)),
+                    ("model", json!(dump.model)),
+                    ("start", json!(format!("{:?}", address))),
+                    ("words", json!(count)),
+                ],
+            );
+        }
+        &Command::Restore {
+            ref path,
+            diff,
+            force,
+        } => {
+            let bytes = std::fs::read(path).map_err(|e| format!("{}: {}", path, e))?;
+            let dump = Dump::from_bytes(&bytes).map_err(|e| format!("{}: {}", path, e))?;
+            let model = connection.execute(&ControllerDataReadRequest).await?.model;
+            if model != dump.model && !force {
+                return Err(format!(
+                    "{} was dumped from a {} but the unit is a {}, restore with --force to write it anyway",
+                    path, dump.model, model
+                )
+                .into());
+            }
+            let (words, writes) = restore(connection, &dump, diff).await?;
+            print_record(
+                output,
+                vec![
+                    ("start", json!(format!("{:?}", dump.address))),
+                    ("words", json!(dump.count())),
+                    ("written", json!(words)),
+                    ("writes", json!(writes)),
+                ],
+            );
+        }
+        Command::Shell => unreachable!("the shell does not run in a shell"),
+    }
+
+    Ok(())
+}
+
+/// The values read by [`read`], formatted as values or dumped.
+#[derive(Debug)]
+enum Reading {
+    Values(Vec<(String, Value)>),
+    Dump(Vec<String>),
+}
+
+/// Reads `count` values of `format` starting at `address`, or bits at bit addresses.
+async fn read(
+    connection: &Connection,
+    address: MemoryAddress,
+    count: usize,
+    format: Format,
+) -> fins_tcp::Result<Reading> {
+    if address.area_code.is_bit_area() {
+        let bits = connection.read_bits(address, count).await?;
+        Ok(Reading::Values(format_bits(address, &bits)))
+    } else {
+        let bytes = connection
+            .read_range(address, count * format.word_count())
+            .await?;
+        Ok(match format {
+            Format::Dump => Reading::Dump(dump_bytes(address, &bytes)),
+            format => Reading::Values(format_words(address, &bytes, format)),
+        })
+    }
+}
+
+fn print_reading(output: Output, reading: &Reading) {
+    match reading {
+        Reading::Values(values) => print_values(output, values),
+        Reading::Dump(lines) => {
+            for line in lines {
+                println!("{}", line);
+            }
+        }
+    }
+}
+
+/// Sends `count` loopback tests with `size` bytes of data one after the other and reports the
+/// round-trip times.
+async fn ping(
+    connection: &Connection,
+    output: Output,
+    count: usize,
+    size: usize,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut round_trip_times = Vec::with_capacity(count);
+
+    for _ in 0..count {
+        let request = LoopbackTestRequest {
+            bytes: (0..size).map(|i| i as u8).collect(),
+        };
+
+        let start = std::time::Instant::now();
+        connection.execute(&request).await?;
+        let round_trip_time = start.elapsed();
+
+        if output == Output::Table {
+            println!(
+                "{} bytes from node {}: time={:.1}ms",
+                size,
+                connection.node(),
+                as_millis_f64(round_trip_time)
+            );
+        }
+
+        round_trip_times.push(round_trip_time);
+    }
+
+    match output {
+        Output::Table => {
+            if let (Some(min), Some(max)) =
+                (round_trip_times.iter().min(), round_trip_times.iter().max())
+            {
+                let avg = round_trip_times.iter().sum::<Duration>() / round_trip_times.len() as u32;
+                println!(
+                    "round-trip min/avg/max = {:.1}/{:.1}/{:.1}ms",
+                    as_millis_f64(*min),
+                    as_millis_f64(avg),
+                    as_millis_f64(*max)
+                );
+            }
+        }
+        Output::Json => println!(
+            "{}",
+            json!({
+                "node": connection.node(),
+                "bytes": size,
+                "round_trip_times_ms": round_trip_times
+                    .iter()
+                    .map(|time| as_millis_f64(*time))
+                    .collect::<Vec<_>>(),
+            })
+        ),
+    }
+
+    Ok(())
+}
+
+/// Reads `count` words starting at D0 and reports how long that took.
+async fn bench(
+    connection: &Connection,
+    output: Output,
+    count: usize,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let address = "D0".parse().unwrap();
+    let start = std::time::Instant::now();
+    connection.read_range(address, count).await?;
+    let elapsed = start.elapsed();
+
+    let mut fields = vec![
+        ("words", json!(count)),
+        ("time_ms", json!(as_millis_f64(elapsed))),
+    ];
+    if let Connection::Tcp(client) = connection {
+        let metrics = client.metrics();
+        fields.push(("window", json!(client.window())));
+        if let (Some(queue_time), Some(round_trip_time)) =
+            (metrics.mean_queue_time(), metrics.mean_round_trip_time())
+        {
+            fields.push(("mean_queue_time_ms", json!(as_millis_f64(queue_time))));
+            fields.push((
+                "mean_round_trip_time_ms",
+                json!(as_millis_f64(round_trip_time)),
+            ));
+        }
+    }
+    print_record(output, fields);
+
+    Ok(())
+}
+
+fn as_millis_f64(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(text: &str) -> Vec<String> {
+        text.split_whitespace().map(str::to_string).collect()
+    }
+
+    fn address(text: &str) -> MemoryAddress {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn parse_options_works() {
+        let options = parse_options(args(
+            "--host plc read D100 4 --udp --node 11 --format float --output json --timeout 0.5",
+        ))
+        .unwrap();
+        assert_eq!(options.host, "plc");
+        assert_eq!(
+            (options.port, options.udp, options.node),
+            (9600, true, Some(11))
+        );
+        assert_eq!(options.timeout, Duration::from_millis(500));
+        assert_eq!(
+            (options.format, options.output),
+            (Some(Format::Float), Output::Json)
+        );
+        assert_eq!(
+            options.command,
+            Command::Read {
+                address: address("D100"),
+                count: 4
+            }
+        );
+
+        // Service ids are assigned by the client, so the count is not limited to 255.
+        let options = parse_options(args("--host plc ping 1000")).unwrap();
+        assert_eq!(
+            options.command,
+            Command::Ping {
+                count: 1000,
+                size: 32
+            }
+        );
+        assert!(parse_options(args("--host plc ping 4 1998")).is_ok());
+        assert!(parse_options(args("--host plc ping 4 5000")).is_err());
+
+        let options = parse_options(args("--host plc write W3.02 1 0")).unwrap();
+        assert_eq!(
+            options.command,
+            Command::Write {
+                address: address("W3.02"),
+                values: args("1 0"),
+            }
+        );
+
+        let tags = Tags::from_csv("name,address\nTank.Level,D100\n").unwrap();
+        assert_eq!(
+            parse_command(
+                &args("watch D1500 5 Tank.Level W3.02 --interval 0.5 --log trend.csv"),
+                &tags
+            ),
+            Ok(Command::Watch {
+                items: vec![
+                    WatchItem {
+                        name: String::new(),
+                        address: address("D1500"),
+                        count: 5
+                    },
+                    WatchItem {
+                        name: "Tank.Level".to_string(),
+                        address: address("D100"),
+                        count: 1
+                    },
+                    WatchItem {
+                        name: String::new(),
+                        address: address("W3.02"),
+                        count: 1
+                    },
+                ],
+                interval: Duration::from_millis(500),
+                log: Some("trend.csv".to_string()),
+            })
+        );
+        let options = parse_options(args("--host plc watch D0 --interval 2")).unwrap();
+        assert!(matches!(
+            options.command,
+            Command::Watch { interval, .. } if interval == Duration::from_secs(2)
+        ));
+
+        let options = parse_options(args("--host plc dump E2_ 100 2000 -o before.dump")).unwrap();
+        assert_eq!(
+            options.command,
+            Command::Dump {
+                address: address("E2_100"),
+                count: 2000,
+                path: "before.dump".to_string()
+            }
+        );
+        let options = parse_options(args("--host plc restore --diff before.dump")).unwrap();
+        assert_eq!(
+            options.command,
+            Command::Restore {
+                path: "before.dump".to_string(),
+                diff: true,
+                force: false
+            }
+        );
+        assert!(parse_options(args("--host plc dump D 100 2000")).is_err());
+        assert!(parse_options(args("--host plc dump W 500 13 -o w.dump")).is_err());
+        assert!(parse_options(args("--host plc dump W 500 12 -o w.dump")).is_ok());
+        assert!(parse_options(args("--host plc read W511 2")).is_err());
+        assert!(parse_options(args("--host plc read W511.15 1")).is_ok());
+
+        assert!(parse_options(args("--host plc read")).is_err());
+        assert!(parse_options(args("--host plc read D0 --interval 2")).is_err());
+        assert!(parse_options(args("--host plc status now")).is_err());
+        assert!(parse_options(args("--host plc read D0 --format dump --output json")).is_err());
+        assert_eq!(parse_options(args("--help")).unwrap_err(), "");
+    }
+}
@@ -0,0 +1,224 @@
+use rustyline::completion::Completer;
+use rustyline::error::ReadlineError;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+
+use crate::{check_output, parse_command, run, Command, Connection, Format, Options};
+
+const HELP: &str = "\
+Commands:
+    read <ADDR> [COUNT]     Read COUNT values starting at ADDR or the address of a tag
+    write <ADDR> <VALUE>... Write values starting at ADDR
+    watch <ADDR> [COUNT]... [--interval SECONDS] [--log FILE]
+                            Show a table of the values until Ctrl-C
+    status, info, clock     Read the status, model or clock of the CPU unit
+    ping [COUNT] [SIZE]     Send COUNT loopback tests of SIZE bytes
+    bench [COUNT]           Read COUNT words starting at D0
+    dump <AREA> <START> <COUNT> -o <FILE>
+                            Save COUNT words starting at word START of AREA to a file
+    restore <FILE> [--diff] [--force]
+                            Write the words of a dump file back
+    format [FORMAT]         Show or set the format, one of hex, dec, bcd, float and dump
+    output [OUTPUT]         Show or set the output, table or json
+    help                    Show this help
+    exit                    Leave the shell
+";
+
+const COMMANDS: [&str; 15] = [
+    "bench", "clock", "dump", "exit", "format", "help", "info", "output", "ping", "quit", "read",
+    "restore", "status", "watch", "write",
+];
+
+const AREA_PREFIXES: [&str; 6] = ["CIO", "W", "H", "A", "D", "E0_"];
+
+const FORMATS: [&str; 5] = ["hex", "dec", "bcd", "float", "dump"];
+
+const OUTPUTS: [&str; 2] = ["table", "json"];
+
+/// File in the home directory that keeps the command history between sessions.
+const HISTORY_FILE: &str = ".fins_client_history";
+
+/// Runs the commands typed at a prompt until the input ends, over the session of `connection`.
+pub async fn run_shell(
+    connection: &Connection,
+    options: &Options,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut format = options.format.unwrap_or(Format::Dump);
+    let mut output = options.output;
+    if check_output(format, output).is_err() {
+        format = Format::Hex;
+    }
+
+    let mut editor = Editor::<ShellHelper>::new();
+    editor.set_helper(Some(ShellHelper {
+        tag_names: options.tags.names().map(str::to_string).collect(),
+    }));
+    let history =
+        std::env::var_os("HOME").map(|home| std::path::PathBuf::from(home).join(HISTORY_FILE));
+    if let Some(history) = &history {
+        // The history does not exist before the first session.
+        let _ = editor.load_history(history);
+    }
+
+    println!(
+        "connected to node {} of {}:{}, type help for the commands",
+        connection.node(),
+        options.host,
+        options.port
+    );
+
+    loop {
+        let line = match tokio::task::block_in_place(|| editor.readline("fins> ")) {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(error) => return Err(error.into()),
+        };
+        let words = line
+            .split_whitespace()
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        if words.is_empty() {
+            continue;
+        }
+        editor.add_history_entry(line.trim());
+
+        let result = match words[0].as_str() {
+            "exit" | "quit" => break,
+            "help" => {
+                print!("{}", HELP);
+                Ok(())
+            }
+            "format" => match words.get(1) {
+                None => {
+                    println!("{:?}", format);
+                    Ok(())
+                }
+                Some(text) => text
+                    .parse()
+                    .and_then(|new_format| {
+                        check_output(new_format, output)?;
+                        format = new_format;
+                        Ok(())
+                    })
+                    .map_err(Into::into),
+            },
+            "output" => match words.get(1) {
+                None => {
+                    println!("{:?}", output);
+                    Ok(())
+                }
+                Some(text) => text
+                    .parse()
+                    .and_then(|new_output| {
+                        check_output(format, new_output)?;
+                        output = new_output;
+                        Ok(())
+                    })
+                    .map_err(Into::into),
+            },
+            _ => match parse_command(&words, &options.tags) {
+                Ok(Command::Shell) => Err("already in the shell".into()),
+                Ok(command) => run(connection, &command, format, output).await,
+                Err(error) => Err(error.into()),
+            },
+        };
+        if let Err(error) = result {
+            eprintln!("error: {}", error);
+        }
+    }
+
+    if let Some(history) = &history {
+        if let Err(error) = editor.save_history(history) {
+            tracing::warn!("failed to save the history: {}", error);
+        }
+    }
+    Ok(())
+}
+
+/// Completes commands, area prefixes, tag names, formats and outputs.
+struct ShellHelper {
+    tag_names: Vec<String>,
+}
+
+impl ShellHelper {
+    fn candidates(&self, previous: &[&str], word: &str) -> Vec<String> {
+        let matching = |options: &[&str]| {
+            options
+                .iter()
+                .filter(|option| option.starts_with(word))
+                .map(|option| option.to_string())
+                .collect::<Vec<_>>()
+        };
+        match previous {
+            [] => matching(&COMMANDS),
+            ["read" | "write" | "watch"] => {
+                let upper = word.to_ascii_uppercase();
+                let mut candidates = AREA_PREFIXES
+                    .iter()
+                    .filter(|prefix| prefix.starts_with(&upper))
+                    .map(|prefix| prefix.to_string())
+                    .collect::<Vec<_>>();
+                candidates.extend(
+                    self.tag_names
+                        .iter()
+                        .filter(|name| name.starts_with(word))
+                        .cloned(),
+                );
+                candidates
+            }
+            ["format"] => matching(&FORMATS),
+            ["output"] => matching(&OUTPUTS),
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl Completer for ShellHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        let line = &line[..pos];
+        let start = line.rfind(char::is_whitespace).map_or(0, |index| index + 1);
+        let previous = line[..start].split_whitespace().collect::<Vec<_>>();
+        Ok((start, self.candidates(&previous, &line[start..])))
+    }
+}
+
+impl Hinter for ShellHelper {
+    type Hint = String;
+}
+
+impl Highlighter for ShellHelper {}
+
+impl Validator for ShellHelper {}
+
+impl Helper for ShellHelper {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn candidates_work() {
+        let helper = ShellHelper {
+            tag_names: vec!["Tank.Level".to_string(), "Tank.Volume".to_string()],
+        };
+        assert_eq!(helper.candidates(&[], "wr"), ["write"]);
+        assert_eq!(helper.candidates(&["read"], "c"), ["CIO"]);
+        assert_eq!(helper.candidates(&["watch"], "Tank.L"), ["Tank.Level"]);
+        assert_eq!(
+            helper.candidates(&["read"], "").len(),
+            AREA_PREFIXES.len() + 2
+        );
+        assert_eq!(helper.candidates(&["format"], "d"), ["dec", "dump"]);
+        assert!(helper.candidates(&["read", "D0"], "").is_empty());
+    }
+}
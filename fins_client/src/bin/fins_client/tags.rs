@@ -0,0 +1,87 @@
+use std::collections::BTreeMap;
+
+use fins::MemoryAddress;
+
+/// Names of addresses, which can be used wherever an address is expected.
+#[derive(Debug, Default)]
+pub struct Tags(BTreeMap<String, MemoryAddress>);
+
+impl Tags {
+    /// Reads the `name` and `address` columns of a tag file in the CSV format of `fins_tags`,
+    /// like:
+    ///
+    /// ```text
+    /// name,address,type
+    /// Tank.Level,D100,INT
+    /// ```
+    pub fn from_csv(text: &str) -> Result<Self, String> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line))
+            .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'));
+        let split = |line: &str| {
+            line.split(',')
+                .map(|field| field.trim().trim_matches('"').to_string())
+                .collect::<Vec<_>>()
+        };
+
+        let columns = split(lines.next().ok_or("missing header")?.1);
+        let column = |name: &str| {
+            columns
+                .iter()
+                .position(|column| column == name)
+                .ok_or(format!("missing column {:?}", name))
+        };
+        let (name, address) = (column("name")?, column("address")?);
+
+        let mut tags = BTreeMap::new();
+        for (line, text) in lines {
+            let fields = split(text);
+            let (name, address) = match (fields.get(name), fields.get(address)) {
+                (Some(name), Some(address)) => (name, address),
+                _ => return Err(format!("line {}: missing name or address", line)),
+            };
+            let address = address
+                .parse()
+                .map_err(|_| format!("line {}: invalid address {:?}", line, address))?;
+            tags.insert(name.clone(), address);
+        }
+        Ok(Self(tags))
+    }
+
+    /// The address named `text`, or the address `text` itself.
+    pub fn resolve(&self, text: &str) -> Option<MemoryAddress> {
+        self.0.get(text).copied().or_else(|| text.parse().ok())
+    }
+
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(String::as_str)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tags_work() {
+        let tags = Tags::from_csv(
+            "# Exported from the tag database\n\
+             name,address,type\n\
+             Tank.Level,D100,INT\n\
+             \"Pump.Running\",W3.02,BOOL\n",
+        )
+        .unwrap();
+        assert_eq!(
+            tags.names().collect::<Vec<_>>(),
+            ["Pump.Running", "Tank.Level"]
+        );
+        assert_eq!(tags.resolve("Tank.Level"), "D100".parse().ok());
+        assert_eq!(tags.resolve("d200"), "D200".parse().ok());
+        assert_eq!(tags.resolve("Tank"), None);
+
+        assert!(Tags::from_csv("name,type\n").is_err());
+        assert!(Tags::from_csv("name,address\nTank.Level,X1\n").is_err());
+    }
+}
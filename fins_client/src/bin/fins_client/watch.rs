@@ -0,0 +1,239 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::time::{Duration, SystemTime};
+
+use fins::MemoryAddress;
+use serde_json::{json, Map, Value};
+
+use crate::{format_bits, format_utc, format_words, table_cell, Connection, Format, Output};
+
+/// Values to watch: `count` values starting at `address`, shown as `name`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WatchItem {
+    pub name: String,
+    pub address: MemoryAddress,
+    pub count: usize,
+}
+
+/// A value in the table.
+#[derive(Debug, Default)]
+struct Row {
+    name: String,
+    value: Option<Value>,
+    changed_at: Option<SystemTime>,
+    changes: u64,
+    /// Whether the value changed in the last poll.
+    changed: bool,
+}
+
+/// Reads `items` every `interval` and redraws a table of their values until interrupted. With
+/// `log`, a row with the time and every value is appended to that CSV file after every poll.
+pub async fn watch(
+    connection: &Connection,
+    items: &[WatchItem],
+    interval: Duration,
+    format: Format,
+    output: Output,
+    log: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    // Dumps do not fit in a table.
+    let format = match format {
+        Format::Dump => Format::Hex,
+        format => format,
+    };
+    let ranges = items
+        .iter()
+        .map(|item| {
+            if item.address.area_code.is_bit_area() {
+                fins_client::words_holding(item.address, item.count)
+            } else {
+                (item.address, item.count * format.word_count())
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut rows = Vec::new();
+    for item in items {
+        let names = (0..item.count).map(|index| {
+            let offset = if item.address.area_code.is_bit_area() {
+                index
+            } else {
+                index * format.word_count()
+            };
+            match item.address.offset_by(offset) {
+                Some(address) if index > 0 || item.name.is_empty() => format!("{:?}", address),
+                _ => item.name.clone(),
+            }
+        });
+        rows.extend(names.map(|name| Row {
+            name,
+            ..Row::default()
+        }));
+    }
+
+    let mut log = match log {
+        Some(path) => {
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .map_err(|error| format!("{}: {}", path, error))?;
+            if file.metadata()?.len() == 0 {
+                let names = rows.iter().map(|row| row.name.as_str());
+                writeln!(file, "time,{}", names.collect::<Vec<_>>().join(","))?;
+            }
+            Some(file)
+        }
+        None => None,
+    };
+
+    let mut next_poll = tokio::time::Instant::now();
+    loop {
+        let now = SystemTime::now();
+        let status = match connection.read_ranges(&ranges).await {
+            Ok(responses) => {
+                let values = items.iter().zip(responses).flat_map(|(item, bytes)| {
+                    let values = if item.address.area_code.is_bit_area() {
+                        let bits = fins::word_bits(&bytes, item.address.bits as usize, item.count);
+                        format_bits(item.address, &bits)
+                    } else {
+                        format_words(item.address, &bytes, format)
+                    };
+                    values.into_iter().map(|(_, value)| value)
+                });
+                for (row, value) in rows.iter_mut().zip(values) {
+                    row.changed = row.value.as_ref() != Some(&value);
+                    if row.changed {
+                        row.changed_at = Some(now);
+                        row.changes += 1;
+                        row.value = Some(value);
+                    }
+                }
+                if let Some(file) = &mut log {
+                    let values = rows
+                        .iter()
+                        .map(|row| table_cell(row.value.as_ref().unwrap()));
+                    writeln!(
+                        file,
+                        "{},{}",
+                        format_utc(now),
+                        values.collect::<Vec<_>>().join(",")
+                    )?;
+                }
+                None
+            }
+            Err(error) => {
+                for row in &mut rows {
+                    row.changed = false;
+                }
+                Some(format!("error: {}", error))
+            }
+        };
+
+        match output {
+            Output::Table => {
+                print!("\x1b[H\x1b[2J{}", render(&rows));
+                println!(
+                    "\n{} every {:.1}s, Ctrl-C to stop",
+                    status.as_deref().unwrap_or("polling"),
+                    interval.as_secs_f64()
+                );
+            }
+            Output::Json => {
+                let changes = rows
+                    .iter()
+                    .filter(|row| row.changed)
+                    .map(|row| (row.name.clone(), row.value.clone().unwrap()))
+                    .collect::<Map<_, _>>();
+                if let Some(status) = &status {
+                    eprintln!("{}", status);
+                } else if !changes.is_empty() {
+                    println!("{}", json!({ "time": format_utc(now), "values": changes }));
+                }
+            }
+        }
+
+        next_poll += interval;
+        next_poll = next_poll.max(tokio::time::Instant::now());
+        tokio::select! {
+            _ = tokio::time::sleep_until(next_poll) => {}
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+        }
+    }
+}
+
+/// Formats `rows` as a table in which the values that changed in the last poll are highlighted.
+fn render(rows: &[Row]) -> String {
+    let header = ["Name", "Value", "Changed (UTC)", "Changes"];
+    let cells = rows
+        .iter()
+        .map(|row| {
+            [
+                row.name.clone(),
+                row.value.as_ref().map_or("-".to_string(), table_cell),
+                row.changed_at
+                    .map_or("-".to_string(), |time| format_utc(time)[11..23].to_string()),
+                row.changes.to_string(),
+            ]
+        })
+        .collect::<Vec<_>>();
+    let mut widths = header.map(str::len);
+    for cells in &cells {
+        for (width, cell) in widths.iter_mut().zip(cells) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let mut table = format!(
+        "{:<4$}  {:<5$}  {:<6$}  {:>7$}\n",
+        header[0], header[1], header[2], header[3], widths[0], widths[1], widths[2], widths[3]
+    );
+    for (row, cells) in rows.iter().zip(&cells) {
+        let value = format!("{:<1$}", cells[1], widths[1]);
+        let value = if row.changed {
+            // Reverse video.
+            format!("\x1b[7m{}\x1b[0m", value)
+        } else {
+            value
+        };
+        table += &format!(
+            "{:<4$}  {}  {:<5$}  {:>6$}\n",
+            cells[0], value, cells[2], cells[3], widths[0], widths[2], widths[3]
+        );
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::UNIX_EPOCH;
+
+    use super::*;
+
+    fn utc(seconds: u64, millis: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(seconds) + Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn render_works() {
+        let rows = [
+            Row {
+                name: "Tank.Level".to_string(),
+                value: Some(json!(16706)),
+                changed_at: Some(utc(1_700_000_000, 42)),
+                changes: 3,
+                changed: true,
+            },
+            Row {
+                name: "D101".to_string(),
+                ..Row::default()
+            },
+        ];
+        assert_eq!(
+            render(&rows),
+            "Name        Value  Changed (UTC)  Changes\n\
+             Tank.Level  \x1b[7m16706\x1b[0m  22:13:20.042         3\n\
+             D101        -      -                    0\n"
+        );
+    }
+}
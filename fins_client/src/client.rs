@@ -0,0 +1,909 @@
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use fins::{
+    Command, EndCode, FinsStruct, MemoryAddress, MemoryAreaCode, MemoryAreaReadRequest,
+    MemoryAreaWriteRequest, MultipleMemoryAreaReadRequest, Route,
+};
+use tokio::net::ToSocketAddrs;
+use tokio::sync::{broadcast, mpsc, oneshot};
+
+use crate::connection::{self, Connection, Job, Settings, Shared};
+use crate::{ConnectionEvent, Metrics, PlannedRead, ReadPlan};
+
+/// Number of requests that can wait for a place in the window before [`Client::send`] blocks.
+const QUEUE_CAPACITY: usize = 64;
+
+/// Number of connection events kept for subscribers that fall behind.
+const EVENT_CAPACITY: usize = 16;
+
+/// A FINS/TCP connection to a single server.
+///
+/// Requests are sent by a background task which keeps a limited number of them in flight. Methods
+/// take `&self` so that the client can be shared, for example through an `Arc`, and requests
+/// that are waiting for a place in the window are sent in the order in which they were queued.
+///
+/// The task detects dead connections by timing out responses and by sending loopback tests when
+/// the connection is idle. It then connects again, waiting longer after every failed attempt.
+pub struct Client {
+    jobs: mpsc::Sender<Job>,
+    shared: Arc<Shared>,
+    route: Route,
+    max_read_count: u16,
+    max_write_count: u16,
+    /// Bit areas the unit does not support, of which the bits are accessed through their words.
+    unsupported_bit_areas: Mutex<HashSet<MemoryAreaCode>>,
+}
+
+/// A request that has been queued by [`Client::send`].
+pub struct PendingResponse(oneshot::Receiver<fins_tcp::Result<Vec<u8>>>);
+
+impl Client {
+    /// Maximum number of words a CS/CJ series CPU unit reads in a single frame.
+    pub const DEFAULT_MAX_READ_COUNT: u16 = 999;
+
+    /// Maximum number of words a CS/CJ series CPU unit writes in a single frame.
+    pub const DEFAULT_MAX_WRITE_COUNT: u16 = 996;
+
+    /// Number of requests in flight at once unless configured otherwise.
+    pub const DEFAULT_WINDOW: usize = 4;
+
+    pub const DEFAULT_RESPONSE_TIMEOUT: Duration = Duration::from_secs(5);
+
+    pub const DEFAULT_KEEPALIVE_INTERVAL: Duration = Duration::from_secs(10);
+
+    pub const DEFAULT_MIN_RECONNECT_DELAY: Duration = Duration::from_millis(100);
+
+    pub const DEFAULT_MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+    /// Connects to `addr` and lets the server assign the client node. Fails when the first
+    /// connection can not be established, later failures are handled by reconnecting.
+    pub async fn connect<A: ToSocketAddrs>(addr: A) -> fins_tcp::Result<Self> {
+        let connection = Connection::connect(addr).await?;
+        let route = connection.session.route();
+
+        let (events, _) = broadcast::channel(EVENT_CAPACITY);
+        let shared = Arc::new(Shared {
+            settings: Mutex::new(Settings {
+                window: Self::DEFAULT_WINDOW,
+                response_timeout: Self::DEFAULT_RESPONSE_TIMEOUT,
+                keepalive_interval: Some(Self::DEFAULT_KEEPALIVE_INTERVAL),
+                min_reconnect_delay: Self::DEFAULT_MIN_RECONNECT_DELAY,
+                max_reconnect_delay: Self::DEFAULT_MAX_RECONNECT_DELAY,
+            }),
+            metrics: Mutex::default(),
+            connected: AtomicBool::new(true),
+            in_flight: AtomicUsize::new(0),
+            events,
+        });
+        let (jobs, receiver) = mpsc::channel(QUEUE_CAPACITY);
+        tokio::spawn(connection::run(connection, receiver, Arc::clone(&shared)));
+
+        Ok(Self {
+            jobs,
+            shared,
+            route,
+            max_read_count: Self::DEFAULT_MAX_READ_COUNT,
+            max_write_count: Self::DEFAULT_MAX_WRITE_COUNT,
+            unsupported_bit_areas: Mutex::default(),
+        })
+    }
+
+    /// Whether the connection is established. Requests fail with
+    /// [`Error::Disconnected`](fins_tcp::Error::Disconnected) while it is not.
+    pub fn is_connected(&self) -> bool {
+        self.shared.connected.load(Ordering::Relaxed)
+    }
+
+    /// Receives the connection events that happen from now on.
+    pub fn subscribe(&self) -> broadcast::Receiver<ConnectionEvent> {
+        self.shared.events.subscribe()
+    }
+
+    /// The route along which commands are sent. Initially this targets the CPU unit of the
+    /// server using the nodes assigned during the connection handshake. The source node is
+    /// replaced by the client node of the current session when a command is sent.
+    pub fn route(&self) -> &Route {
+        &self.route
+    }
+
+    /// Changes the route along which commands are sent, for example to reach a CPU bus unit or a
+    /// unit on another network through the server.
+    pub fn set_route(&mut self, route: Route) {
+        self.route = route;
+    }
+
+    /// The maximum number of requests awaiting a response.
+    pub fn window(&self) -> usize {
+        self.shared.settings.lock().unwrap().window
+    }
+
+    /// The number of requests awaiting a response. The window is saturated when this reaches
+    /// [`Client::window`].
+    pub fn in_flight(&self) -> usize {
+        self.shared.in_flight.load(Ordering::Relaxed)
+    }
+
+    /// Sets the maximum number of requests awaiting a response. Requests beyond the window wait
+    /// until a response arrives. A window of 1 sends requests one at a time.
+    pub fn set_window(&self, window: usize) {
+        assert!(
+            (1..=255).contains(&window),
+            "window must be between 1 and 255 to keep service ids unique"
+        );
+        self.shared.settings.lock().unwrap().window = window;
+    }
+
+    /// Sets how long to wait for a response before considering the connection dead.
+    pub fn set_response_timeout(&self, timeout: Duration) {
+        self.shared.settings.lock().unwrap().response_timeout = timeout;
+    }
+
+    /// Sets how long the connection may be idle before a loopback test checks it, `None` to not
+    /// check idle connections.
+    pub fn set_keepalive_interval(&self, interval: Option<Duration>) {
+        self.shared.settings.lock().unwrap().keepalive_interval = interval;
+    }
+
+    /// Sets the delay before the first attempt to reconnect, which doubles after every failed
+    /// attempt up to `max_delay`.
+    pub fn set_reconnect_delay(&self, min_delay: Duration, max_delay: Duration) {
+        let mut settings = self.shared.settings.lock().unwrap();
+        settings.min_reconnect_delay = min_delay;
+        settings.max_reconnect_delay = max_delay;
+    }
+
+    /// The latencies of the requests executed since the client connected or since the metrics
+    /// were last taken.
+    pub fn metrics(&self) -> Metrics {
+        *self.shared.metrics.lock().unwrap()
+    }
+
+    /// Returns the metrics and resets them, to measure the effect of a change in window.
+    pub fn take_metrics(&self) -> Metrics {
+        std::mem::take(&mut *self.shared.metrics.lock().unwrap())
+    }
+
+    /// Sets the maximum number of items read and written per frame by [`Client::read_range`] and
+    /// [`Client::write_range`]. Some units, or units reached through a gateway, accept less than
+    /// the defaults.
+    pub fn set_max_counts(&mut self, max_read_count: u16, max_write_count: u16) {
+        assert!(max_read_count > 0 && max_write_count > 0);
+        self.max_read_count = max_read_count;
+        self.max_write_count = max_write_count;
+    }
+
+    /// Reads `count` items starting at `address`. The range is split into as many memory area
+    /// reads as needed, which are pipelined.
+    pub async fn read_range(
+        &self,
+        address: MemoryAddress,
+        count: usize,
+    ) -> fins_tcp::Result<Vec<u8>> {
+        let requests = split_range(address, count, self.max_read_count)?
+            .map(|(address, count)| MemoryAreaReadRequest { address, count })
+            .collect::<Vec<_>>();
+
+        let mut bytes = Vec::with_capacity(count * address.area_code.item_size());
+        for result in self.execute_pipelined(&requests).await? {
+            bytes.extend(result?);
+        }
+        Ok(bytes)
+    }
+
+    /// Writes `bytes` to consecutive items starting at `address`. The range is split into as many
+    /// memory area writes as needed, which are pipelined. Nothing is written when the range
+    /// exceeds the area, but a unit that fails a write halfway may leave the range partially
+    /// written.
+    pub async fn write_range(&self, address: MemoryAddress, bytes: &[u8]) -> fins_tcp::Result<()> {
+        let item_size = address.area_code.item_size();
+        assert!(bytes.len().is_multiple_of(item_size));
+
+        let requests = split_range(address, bytes.len() / item_size, self.max_write_count)?
+            .zip(bytes.chunks(self.max_write_count as usize * item_size))
+            .map(|((address, _), bytes)| MemoryAreaWriteRequest {
+                address,
+                bytes: bytes.to_vec(),
+            })
+            .collect::<Vec<_>>();
+
+        for result in self.execute_pipelined(&requests).await? {
+            result?;
+        }
+        Ok(())
+    }
+
+    /// Reads the struct stored in the words starting at `address`.
+    pub async fn read_struct<T: FinsStruct>(&self, address: MemoryAddress) -> fins_tcp::Result<T> {
+        assert!(!address.area_code.is_bit_area());
+        let bytes = self.read_range(address, T::WORD_COUNT).await?;
+        Ok(T::decode(&bytes)?)
+    }
+
+    /// Writes `value` to the words starting at `address`. Bits of these words that are not fields
+    /// of the struct are cleared.
+    pub async fn write_struct<T: FinsStruct>(
+        &self,
+        address: MemoryAddress,
+        value: &T,
+    ) -> fins_tcp::Result<()> {
+        assert!(!address.area_code.is_bit_area());
+        self.write_range(address, &value.encode()).await
+    }
+
+    /// Reads `count` bits starting at the bit address `address`, like `D2420.01`. When the unit
+    /// does not support the bit area, the words holding the bits are read instead. Fails with
+    /// [`EndCode::NO_AREA_TYPE`] for a word address.
+    pub async fn read_bits(
+        &self,
+        address: MemoryAddress,
+        count: usize,
+    ) -> fins_tcp::Result<Vec<bool>> {
+        if !address.area_code.is_bit_area() {
+            return Err(fins_tcp::Error::EndCode(EndCode::NO_AREA_TYPE));
+        }
+        if self.supports_bit_area(address.area_code) {
+            match self.read_range(address, count).await {
+                Err(fins_tcp::Error::EndCode(EndCode::NO_AREA_TYPE)) => {
+                    self.set_bit_area_unsupported(address.area_code)
+                }
+                result => return result.map(|bytes| fins::decode_bit_items(&bytes)),
+            }
+        }
+
+        let (words, word_count) = words_holding(address, count);
+        let bytes = self.read_range(words, word_count).await?;
+        Ok(fins::word_bits(&bytes, address.bits as usize, count))
+    }
+
+    /// Writes `bits` starting at the bit address `address`, like `D2420.01`.
+    ///
+    /// When the unit does not support the bit area, like the D area on some CS/CJ series units,
+    /// the words holding the bits are read, changed and written back. This is not atomic: changes
+    /// that the unit makes to the other bits of these words in the meantime are lost.
+    ///
+    /// Fails with [`EndCode::NO_AREA_TYPE`] for a word address.
+    pub async fn write_bits(&self, address: MemoryAddress, bits: &[bool]) -> fins_tcp::Result<()> {
+        if !address.area_code.is_bit_area() {
+            return Err(fins_tcp::Error::EndCode(EndCode::NO_AREA_TYPE));
+        }
+        if self.supports_bit_area(address.area_code) {
+            match self
+                .write_range(address, &fins::encode_bit_items(bits))
+                .await
+            {
+                Err(fins_tcp::Error::EndCode(EndCode::NO_AREA_TYPE)) => {
+                    self.set_bit_area_unsupported(address.area_code)
+                }
+                result => return result,
+            }
+        }
+
+        let (words, word_count) = words_holding(address, bits.len());
+        let mut bytes = self.read_range(words, word_count).await?;
+        fins::set_word_bits(&mut bytes, address.bits as usize, bits);
+        self.write_range(words, &bytes).await
+    }
+
+    fn supports_bit_area(&self, area_code: MemoryAreaCode) -> bool {
+        !self
+            .unsupported_bit_areas
+            .lock()
+            .unwrap()
+            .contains(&area_code)
+    }
+
+    fn set_bit_area_unsupported(&self, area_code: MemoryAreaCode) {
+        tracing::info!("accessing the bits of {} through words", area_code);
+        self.unsupported_bit_areas.lock().unwrap().insert(area_code);
+    }
+
+    /// Executes the requests of `plan`, which are pipelined, and returns the bytes of every range
+    /// in the order in which the ranges were planned. Like [`Client::read_bits`], the bits of
+    /// areas the unit does not support are read through the words holding them.
+    pub async fn read_plan(&self, plan: &ReadPlan) -> fins_tcp::Result<Vec<Vec<u8>>> {
+        let reads_unsupported_bits = plan
+            .ranges()
+            .iter()
+            .any(|(address, _)| self.reads_through_words(address.area_code));
+        if !reads_unsupported_bits {
+            match self.execute_plan(plan).await {
+                Err(fins_tcp::Error::EndCode(EndCode::NO_AREA_TYPE))
+                    if self.find_unsupported_bit_areas(plan.ranges()).await? => {}
+                result => return result,
+            }
+        }
+
+        let ranges = plan
+            .ranges()
+            .iter()
+            .map(|&(address, count)| {
+                if self.reads_through_words(address.area_code) {
+                    words_holding(address, count)
+                } else {
+                    (address, count)
+                }
+            })
+            .collect::<Vec<_>>();
+        let responses = self.execute_plan(&plan.planner().plan(&ranges)?).await?;
+        Ok(plan
+            .ranges()
+            .iter()
+            .zip(responses)
+            .map(|(&(address, count), bytes)| {
+                if self.reads_through_words(address.area_code) {
+                    let bits = fins::word_bits(&bytes, address.bits as usize, count);
+                    fins::encode_bit_items(&bits)
+                } else {
+                    bytes
+                }
+            })
+            .collect())
+    }
+
+    /// Whether the items of `area_code` are bits that the unit does not support.
+    fn reads_through_words(&self, area_code: MemoryAreaCode) -> bool {
+        area_code.is_bit_area() && !self.supports_bit_area(area_code)
+    }
+
+    /// Reads a bit of every bit area in `ranges` to find the areas the unit does not support,
+    /// after a read of the ranges failed because of an unsupported area. Returns whether any
+    /// were found.
+    async fn find_unsupported_bit_areas(
+        &self,
+        ranges: &[(MemoryAddress, usize)],
+    ) -> fins_tcp::Result<bool> {
+        let mut found = false;
+        let mut probed = HashSet::new();
+        for &(address, _) in ranges {
+            if !address.area_code.is_bit_area() || !probed.insert(address.area_code) {
+                continue;
+            }
+            match self.read_range(address, 1).await {
+                Err(fins_tcp::Error::EndCode(EndCode::NO_AREA_TYPE)) => {
+                    self.set_bit_area_unsupported(address.area_code);
+                    found = true;
+                }
+                result => {
+                    result?;
+                }
+            }
+        }
+        Ok(found)
+    }
+
+    async fn execute_plan(&self, plan: &ReadPlan) -> fins_tcp::Result<Vec<Vec<u8>>> {
+        let mut pending = Vec::with_capacity(plan.requests().len());
+        for request in plan.requests() {
+            pending.push(match request {
+                PlannedRead::Range { address, count } => {
+                    self.send(&MemoryAreaReadRequest {
+                        address: *address,
+                        count: *count,
+                    })
+                    .await?
+                }
+                PlannedRead::Multiple(addresses) => {
+                    self.send(&MultipleMemoryAreaReadRequest {
+                        addresses: addresses.clone(),
+                    })
+                    .await?
+                }
+            });
+        }
+
+        let mut responses = Vec::with_capacity(pending.len());
+        for (request, pending) in plan.requests().iter().zip(pending) {
+            responses.push(match request {
+                PlannedRead::Range { address, count } => {
+                    let command = MemoryAreaReadRequest {
+                        address: *address,
+                        count: *count,
+                    };
+                    self.receive(&command, pending).await?
+                }
+                PlannedRead::Multiple(addresses) => {
+                    let command = MultipleMemoryAreaReadRequest {
+                        addresses: addresses.clone(),
+                    };
+                    let items = self.receive(&command, pending).await?;
+                    items.into_iter().flat_map(|item| item.bytes).collect()
+                }
+            });
+        }
+        Ok(plan.split(responses))
+    }
+
+    /// Queues all `commands` before waiting for their responses. The outer result fails when the
+    /// connection is no longer usable, the inner results hold the response to every command.
+    async fn execute_pipelined<C: Command>(
+        &self,
+        commands: &[C],
+    ) -> fins_tcp::Result<Vec<fins_tcp::Result<C::Response>>> {
+        let mut pending = Vec::with_capacity(commands.len());
+        for command in commands {
+            pending.push(self.send(command).await?);
+        }
+
+        let mut results = Vec::with_capacity(commands.len());
+        for (command, pending) in commands.iter().zip(pending) {
+            match self.receive(command, pending).await {
+                Err(error @ fins_tcp::Error::Disconnected) => return Err(error),
+                result => results.push(result),
+            }
+        }
+        Ok(results)
+    }
+
+    /// Sends `command` and waits for its response.
+    pub async fn execute<C: Command>(&self, command: &C) -> fins_tcp::Result<C::Response> {
+        let pending = self.send(command).await?;
+        self.receive(command, pending).await
+    }
+
+    /// Queues `command` without waiting for its response, which is obtained by passing the
+    /// returned [`PendingResponse`] to [`Client::receive`]. Waits while the queue is full.
+    pub async fn send<C: Command>(&self, command: &C) -> fins_tcp::Result<PendingResponse> {
+        let mut frame = Vec::with_capacity(fins::request_byte_size(command));
+        fins::write_request(&mut frame, &self.route, 0, command)?;
+
+        let (response, receiver) = oneshot::channel();
+        self.jobs
+            .send(Job {
+                frame,
+                queued_at: Instant::now(),
+                response,
+            })
+            .await
+            .map_err(|_| fins_tcp::Error::Disconnected)?;
+
+        Ok(PendingResponse(receiver))
+    }
+
+    /// Waits for the response to `command` which was queued as `pending`.
+    pub async fn receive<C: Command>(
+        &self,
+        command: &C,
+        pending: PendingResponse,
+    ) -> fins_tcp::Result<C::Response> {
+        let frame = pending
+            .0
+            .await
+            .map_err(|_| fins_tcp::Error::Disconnected)??;
+        let response = fins::read_response(command, &frame)?;
+        Ok(response.body)
+    }
+}
+
+/// The first of the words holding `count` bits starting at the bit address `address`, and the
+/// number of words.
+pub fn words_holding(address: MemoryAddress, count: usize) -> (MemoryAddress, usize) {
+    let words = MemoryAddress {
+        area_code: address.area_code.word_area(),
+        offset: address.offset,
+        bits: 0,
+    };
+    (words, (address.bits as usize + count).div_ceil(16))
+}
+
+/// Splits `count` items starting at `address` into ranges of at most `max_count` items. Fails
+/// like a unit would when the items do not fit in the area.
+pub(crate) fn split_range(
+    address: MemoryAddress,
+    count: usize,
+    max_count: u16,
+) -> fins_tcp::Result<impl Iterator<Item = (MemoryAddress, u16)>> {
+    if address.offset_by(0).is_none() {
+        return Err(fins_tcp::Error::EndCode(EndCode::ADDRESS_OUT_OF_RANGE));
+    }
+    if address.offset_by(count).is_none() {
+        return Err(fins_tcp::Error::EndCode(EndCode::ADDRESS_RANGE_EXCEEDED));
+    }
+    let max_count = max_count as usize;
+    Ok((0..count).step_by(max_count).map(move |start| {
+        (
+            address.offset_by(start).unwrap(),
+            max_count.min(count - start) as u16,
+        )
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use fins::Header;
+    use fins_server::{MemoryHandler, Server};
+    use fins_tcp::ServerAddressFrame;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    use super::*;
+
+    fn address(text: &str) -> MemoryAddress {
+        text.parse().unwrap()
+    }
+
+    /// Accepts a connection and assigns client node 0x02 as server node 0x01.
+    async fn accept_handshake(listener: &TcpListener) -> TcpStream {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut buffer = vec![0; 20];
+        stream.read_exact(&mut buffer).await.unwrap();
+        let mut output = Vec::new();
+        ServerAddressFrame {
+            client_node: 0x02,
+            server_node: 0x01,
+        }
+        .write_to(&mut output)
+        .unwrap();
+        stream.write_all(&output).await.unwrap();
+        stream
+    }
+
+    #[test]
+    fn split_range_works() {
+        assert_eq!(
+            split_range(address("D100"), 5, 2)
+                .unwrap()
+                .collect::<Vec<_>>(),
+            [
+                (address("D100"), 2),
+                (address("D102"), 2),
+                (address("D104"), 1)
+            ]
+        );
+        assert_eq!(
+            split_range(address("W0.10"), 20, 8)
+                .unwrap()
+                .collect::<Vec<_>>(),
+            [
+                (address("W0.10"), 8),
+                (address("W1.02"), 8),
+                (address("W1.10"), 4)
+            ]
+        );
+        assert!(matches!(
+            split_range(address("W500"), 13, 999),
+            Err(fins_tcp::Error::EndCode(EndCode::ADDRESS_RANGE_EXCEEDED))
+        ));
+    }
+
+    async fn serve(handler: MemoryHandler, response_delay: Duration) -> SocketAddr {
+        let mut server = Server::bind("127.0.0.1:0", 0x01, handler).await.unwrap();
+        server.set_response_delay(response_delay);
+        let addr = server.local_addr().unwrap();
+        tokio::spawn(server.run());
+        addr
+    }
+
+    /// Rejects bit areas like units that only access words.
+    fn handler() -> MemoryHandler {
+        let mut handler = MemoryHandler::default();
+        handler.set_words_only(true);
+        handler
+    }
+
+    /// The offsets and counts of the reads and writes executed by `handler`.
+    fn requests(handler: &MemoryHandler) -> Vec<(u16, u16)> {
+        handler
+            .accesses()
+            .iter()
+            .map(|access| (access.address.offset, access.count))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn read_write_range_works() {
+        let handler = handler();
+        let addr = serve(handler.clone(), Duration::ZERO).await;
+
+        let mut client = Client::connect(addr).await.unwrap();
+        client.set_max_counts(4, 3);
+
+        let bytes = (0..20).collect::<Vec<u8>>();
+        client.write_range(address("D10"), &bytes).await.unwrap();
+        assert_eq!(
+            client.read_range(address("D9"), 11).await.unwrap()[2..],
+            bytes
+        );
+        assert_eq!(
+            requests(&handler),
+            [(10, 3), (13, 3), (16, 3), (19, 1), (9, 4), (13, 4), (17, 3)]
+        );
+    }
+
+    #[tokio::test]
+    async fn read_plan_works() {
+        let handler = handler();
+        for (offset, word) in handler
+            .memory()
+            .area(MemoryAreaCode::D)
+            .iter_mut()
+            .enumerate()
+        {
+            *word = offset as u16;
+        }
+        let addr = serve(handler.clone(), Duration::ZERO).await;
+        let client = Client::connect(addr).await.unwrap();
+
+        let plan = crate::ReadPlanner::new()
+            .plan(&[
+                (address("D12"), 1),
+                (address("D5000"), 1),
+                (address("D10"), 2),
+                (address("D9000"), 2),
+            ])
+            .unwrap();
+        assert_eq!(
+            client.read_plan(&plan).await.unwrap(),
+            [
+                vec![0, 12],
+                vec![0x13, 0x88],
+                vec![0, 10, 0, 11],
+                vec![0x23, 0x28, 0x23, 0x29]
+            ]
+        );
+        assert_eq!(
+            requests(&handler),
+            [(10, 3), (5000, 1), (9000, 1), (9001, 1)]
+        );
+    }
+
+    #[derive(Debug, PartialEq, FinsStruct)]
+    struct Assign {
+        step: i16,
+        target: i32,
+        #[fins(bit = 1)]
+        busy: bool,
+    }
+
+    #[tokio::test]
+    async fn read_write_struct_works() {
+        let handler = handler();
+        let addr = serve(handler.clone(), Duration::ZERO).await;
+        let client = Client::connect(addr).await.unwrap();
+
+        let assign = Assign {
+            step: 3,
+            target: -1,
+            busy: true,
+        };
+        client
+            .write_struct(address("D1500"), &assign)
+            .await
+            .unwrap();
+        assert_eq!(
+            handler.memory().area(MemoryAreaCode::D)[1500..1504],
+            [3, 0xFFFF, 0xFFFF, 0x0002]
+        );
+        assert_eq!(
+            client
+                .read_struct::<Assign>(address("D1500"))
+                .await
+                .unwrap(),
+            assign
+        );
+    }
+
+    #[tokio::test]
+    async fn bits_are_accessed_through_words() {
+        let handler = handler();
+        handler.memory().area(MemoryAreaCode::D)[10] = 0x0001;
+        let addr = serve(handler.clone(), Duration::ZERO).await;
+        let client = Client::connect(addr).await.unwrap();
+
+        client
+            .write_bits(address("D10.14"), &[true, true, true])
+            .await
+            .unwrap();
+        assert_eq!(
+            handler.memory().area(MemoryAreaCode::D)[10..12],
+            [0xC001, 0x0001]
+        );
+        assert_eq!(
+            client.read_bits(address("D10.13"), 4).await.unwrap(),
+            [false, true, true, true]
+        );
+        // The bit area is tried once.
+        assert_eq!(requests(&handler), [(10, 3), (10, 2), (10, 2), (10, 2)]);
+    }
+
+    #[tokio::test]
+    async fn read_plan_reads_bits_through_words() {
+        let handler = handler();
+        handler.memory().area(MemoryAreaCode::D)[10] = 0xC001;
+        handler.memory().area(MemoryAreaCode::D)[20] = 0x0004;
+        let addr = serve(handler.clone(), Duration::ZERO).await;
+        let client = Client::connect(addr).await.unwrap();
+
+        let plan = crate::ReadPlanner::new()
+            .plan(&[
+                (address("D10.14"), 2),
+                (address("D20"), 1),
+                (address("D20.02"), 1),
+            ])
+            .unwrap();
+        let expected = [vec![1, 1], vec![0, 4], vec![1]];
+        assert_eq!(client.read_plan(&plan).await.unwrap(), expected);
+
+        // The bit area is tried once.
+        let request_count = handler.accesses().len();
+        assert_eq!(client.read_plan(&plan).await.unwrap(), expected);
+        assert_eq!(requests(&handler)[request_count..], [(10, 11)]);
+    }
+
+    #[tokio::test]
+    async fn bits_need_bit_addresses() {
+        let addr = serve(handler(), Duration::ZERO).await;
+        let client = Client::connect(addr).await.unwrap();
+        assert!(matches!(
+            client.read_bits(address("D10"), 1).await,
+            Err(fins_tcp::Error::EndCode(EndCode::NO_AREA_TYPE))
+        ));
+        assert!(matches!(
+            client.write_bits(address("D10"), &[true]).await,
+            Err(fins_tcp::Error::EndCode(EndCode::NO_AREA_TYPE))
+        ));
+    }
+
+    #[tokio::test]
+    async fn window_limits_requests_in_flight() {
+        let addr = serve(handler(), Duration::from_millis(20)).await;
+        let client = Client::connect(addr).await.unwrap();
+        let command = MemoryAreaReadRequest {
+            address: address("D0"),
+            count: 1,
+        };
+
+        for window in [3, 1] {
+            client.set_window(window);
+            client.take_metrics();
+            let pending = [
+                client.send(&command).await.unwrap(),
+                client.send(&command).await.unwrap(),
+                client.send(&command).await.unwrap(),
+            ];
+            for pending in pending {
+                client.receive(&command, pending).await.unwrap();
+            }
+
+            let metrics = client.metrics();
+            assert_eq!((metrics.requests, metrics.responses), (3, 3));
+            if window == 3 {
+                assert!(metrics.max_queue_time < Duration::from_millis(20));
+            } else {
+                assert!(metrics.max_queue_time >= Duration::from_millis(40));
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn reconnects_when_responses_time_out() {
+        // Complete the handshake but never respond.
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let unresponsive = tokio::spawn(async move {
+            let mut stream = accept_handshake(&listener).await;
+            let mut buffer = vec![0; 2048];
+            while stream.read(&mut buffer).await.unwrap() > 0 {}
+        });
+
+        let client = Client::connect(addr).await.unwrap();
+        client.set_response_timeout(Duration::from_millis(50));
+        client.set_reconnect_delay(Duration::from_millis(10), Duration::from_millis(20));
+        let mut events = client.subscribe();
+
+        let command = MemoryAreaReadRequest {
+            address: address("D0"),
+            count: 1,
+        };
+        let error = client.execute(&command).await.unwrap_err();
+        assert!(error.is_retriable(), "{}", error);
+        assert!(!client.is_connected());
+        assert!(matches!(
+            events.recv().await.unwrap(),
+            ConnectionEvent::Disconnected { .. }
+        ));
+
+        unresponsive.abort();
+        let _ = unresponsive.await;
+        let server = Server::bind(addr, 0x01, handler()).await.unwrap();
+        tokio::spawn(server.run());
+
+        let mut attempts = 0;
+        loop {
+            match events.recv().await.unwrap() {
+                ConnectionEvent::Reconnecting { attempt, delay } => {
+                    attempts = attempt;
+                    assert!(delay <= Duration::from_millis(20));
+                }
+                ConnectionEvent::Connected { session } => {
+                    assert_eq!(session.server_node, 0x01);
+                    break;
+                }
+                event => panic!("unexpected event {:?}", event),
+            }
+        }
+        assert!(attempts >= 1);
+        assert!(client.is_connected());
+        assert_eq!(client.execute(&command).await.unwrap(), [0, 0]);
+    }
+
+    #[tokio::test]
+    async fn rejects_responses_from_other_nodes() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut stream = accept_handshake(&listener).await;
+            let mut buffer = vec![0; 16];
+            stream.read_exact(&mut buffer).await.unwrap();
+            let header = fins_tcp::Header::read_from(&mut &buffer[..]).unwrap();
+            buffer.resize(8 + header.length as usize, 0);
+            stream.read_exact(&mut buffer[16..]).await.unwrap();
+            let request = fins_tcp::read_fins_frame(&mut &buffer[..]).unwrap();
+
+            // Respond to the read as another node.
+            let mut response = Vec::new();
+            let mut header = Header::from_bytes(&request).unwrap().response();
+            header.source.node = 0x05;
+            header.write_to(&mut response).unwrap();
+            response.extend([0x01, 0x01, 0x00, 0x00, 0x12, 0x34]);
+            let mut output = Vec::new();
+            fins_tcp::write_fins_frame(&mut output, &response).unwrap();
+            stream.write_all(&output).await.unwrap();
+            while stream.read(&mut buffer).await.unwrap() > 0 {}
+        });
+
+        let mut client = Client::connect(addr).await.unwrap();
+        // The session overrides the source node.
+        client.set_route(Route::local(0x01, 0x7F));
+        let command = MemoryAreaReadRequest {
+            address: address("D0"),
+            count: 1,
+        };
+        assert!(matches!(
+            client.execute(&command).await,
+            Err(fins_tcp::Error::ProtocolViolation(
+                fins_tcp::ProtocolViolation::UnexpectedSource { actual, .. }
+            )) if actual.node == 0x05
+        ));
+    }
+
+    #[tokio::test]
+    async fn rejects_oversized_frames() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut stream = accept_handshake(&listener).await;
+            // Answer the first request.
+            let mut buffer = vec![0; 2048];
+            assert!(stream.read(&mut buffer).await.unwrap() > 0);
+            let mut output = Vec::new();
+            fins_tcp::Header {
+                length: u32::MAX,
+                command: fins_tcp::CommandCode::Fins,
+                error_code: 0,
+            }
+            .write_to(&mut output)
+            .unwrap();
+            stream.write_all(&output).await.unwrap();
+            while stream.read(&mut buffer).await.unwrap_or(0) > 0 {}
+        });
+
+        let client = Client::connect(addr).await.unwrap();
+        let mut events = client.subscribe();
+        let command = MemoryAreaReadRequest {
+            address: address("D0"),
+            count: 1,
+        };
+        assert!(client.execute(&command).await.unwrap_err().is_retriable());
+        match events.recv().await.unwrap() {
+            ConnectionEvent::Disconnected { error } => {
+                assert!(error.contains("unexpected length"), "{}", error)
+            }
+            event => panic!("unexpected event {:?}", event),
+        }
+    }
+}
@@ -0,0 +1,370 @@
+use std::collections::HashMap;
+use std::io::{Cursor, ErrorKind};
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use fins::LoopbackTestRequest;
+use fins_tcp::{ClientAddressFrame, ProtocolViolation, RawHeader, ServerAddressFrame, Session};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::{TcpStream, ToSocketAddrs};
+use tokio::sync::{broadcast, mpsc, oneshot};
+use tracing::{debug, info, warn};
+
+use crate::{ConnectionEvent, Metrics};
+
+/// A request waiting to be sent by the connection task.
+pub(crate) struct Job {
+    /// The FINS frame of the request. The service id and the nodes of the session are stamped when
+    /// the request is sent.
+    pub frame: Vec<u8>,
+    pub queued_at: Instant,
+    pub response: oneshot::Sender<fins_tcp::Result<Vec<u8>>>,
+}
+
+/// How the connection task sends requests and keeps the connection alive.
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct Settings {
+    pub window: usize,
+    pub response_timeout: Duration,
+    pub keepalive_interval: Option<Duration>,
+    pub min_reconnect_delay: Duration,
+    pub max_reconnect_delay: Duration,
+}
+
+/// State shared between a client and its connection task.
+pub(crate) struct Shared {
+    pub settings: Mutex<Settings>,
+    pub metrics: Mutex<Metrics>,
+    pub connected: AtomicBool,
+    /// Number of requests awaiting a response, including keepalives.
+    pub in_flight: AtomicUsize,
+    pub events: broadcast::Sender<ConnectionEvent>,
+}
+
+impl Shared {
+    fn settings(&self) -> Settings {
+        *self.settings.lock().unwrap()
+    }
+
+    fn publish(&self, event: ConnectionEvent) {
+        self.connected.store(
+            matches!(event, ConnectionEvent::Connected { .. }),
+            Ordering::Relaxed,
+        );
+        // There may be no subscribers.
+        let _ = self.events.send(event);
+    }
+}
+
+/// A request that has been sent and awaits its response.
+struct InFlight {
+    request: fins::Header,
+    sent_at: Instant,
+    /// Where to deliver the response, `None` for keepalives.
+    response: Option<oneshot::Sender<fins_tcp::Result<Vec<u8>>>>,
+}
+
+/// Reads frames from a connection into a buffer.
+pub(crate) struct FrameReader {
+    reader: OwnedReadHalf,
+    buffer: Vec<u8>,
+    position: usize,
+}
+
+impl FrameReader {
+    pub fn new(reader: OwnedReadHalf) -> Self {
+        Self {
+            reader,
+            buffer: vec![0; std::mem::size_of::<RawHeader>() + fins_tcp::MAX_FINS_FRAME_SIZE],
+            position: 0,
+        }
+    }
+
+    /// Reads from the connection until `read_from` can parse a complete frame. Bytes following
+    /// the frame are kept for the next read. Frames longer than the largest FINS/TCP frame are
+    /// rejected as soon as their header arrives. Cancelling this future does not lose any bytes.
+    pub async fn read_frame<T, F>(&mut self, read_from: F) -> fins_tcp::Result<T>
+    where
+        F: Fn(&mut Cursor<&[u8]>) -> fins_tcp::Result<T>,
+    {
+        loop {
+            let mut cursor = Cursor::new(&self.buffer[0..self.position]);
+            match read_from(&mut cursor) {
+                Ok(frame) => {
+                    let consumed_position = cursor.position() as usize;
+                    self.buffer.copy_within(consumed_position..self.position, 0);
+                    self.position -= consumed_position;
+                    return Ok(frame);
+                }
+                Err(fins_tcp::Error::Io(err)) if err.kind() == ErrorKind::UnexpectedEof => {
+                    // Continue reading.
+                }
+                Err(err) => return Err(err),
+            }
+            let header_size = std::mem::size_of::<RawHeader>();
+            if self.position >= header_size {
+                let header = fins_tcp::Header::read_from(&mut &self.buffer[..header_size])?;
+                if header_size + header.body_size()? > self.buffer.len() {
+                    return Err(ProtocolViolation::UnexpectedFrameLength(header.length).into());
+                }
+            }
+            match self.reader.read(&mut self.buffer[self.position..]).await? {
+                0 => return Err(std::io::Error::from(ErrorKind::UnexpectedEof).into()),
+                n => self.position += n,
+            }
+        }
+    }
+}
+
+/// An established FINS/TCP connection.
+pub(crate) struct Connection {
+    pub peer_addr: SocketAddr,
+    pub session: Session,
+    reader: FrameReader,
+    writer: OwnedWriteHalf,
+}
+
+impl Connection {
+    /// Connects to `addr` and lets the server assign the client node.
+    pub async fn connect<A: ToSocketAddrs>(addr: A) -> fins_tcp::Result<Self> {
+        let stream = TcpStream::connect(addr).await?;
+        let peer_addr = stream.peer_addr()?;
+        info!("connection established with {}", peer_addr);
+        stream.set_nodelay(true)?;
+
+        let (reader, mut writer) = stream.into_split();
+        let mut reader = FrameReader::new(reader);
+
+        // Exchange nodes
+        let mut buffer = Vec::new();
+        ClientAddressFrame { client_node: 0 }.write_to(&mut buffer)?;
+        writer.write_all(&buffer).await?;
+        writer.flush().await?;
+
+        let session = Session::from(
+            reader
+                .read_frame(|cursor| ServerAddressFrame::read_from(cursor))
+                .await?,
+        );
+
+        info!(
+            "client node {}, server node {}",
+            session.client_node, session.server_node
+        );
+
+        Ok(Self {
+            peer_addr,
+            session,
+            reader,
+            writer,
+        })
+    }
+
+    fn connected_event(&self) -> ConnectionEvent {
+        ConnectionEvent::Connected {
+            session: self.session,
+        }
+    }
+}
+
+/// Serves the queued jobs over `connection`, and over new connections to the same server when the
+/// connection fails. Returns when the client is dropped. The client is assumed to know that the
+/// first connection is established, later connections are published as events.
+pub(crate) async fn run(
+    mut connection: Connection,
+    mut jobs: mpsc::Receiver<Job>,
+    shared: Arc<Shared>,
+) {
+    let peer_addr = connection.peer_addr;
+    loop {
+        let mut in_flight = HashMap::new();
+        let result = serve(&mut connection, &mut jobs, &shared, &mut in_flight).await;
+
+        if let Err(error) = &result {
+            warn!("connection with {} failed: {}", peer_addr, error);
+            shared.publish(ConnectionEvent::Disconnected {
+                error: error.to_string(),
+            });
+        }
+
+        // Requests in flight may or may not have been executed, let the callers decide whether
+        // to send them again.
+        for (_, InFlight { response, .. }) in in_flight {
+            if let Some(response) = response {
+                let _ = response.send(Err(fins_tcp::Error::Disconnected));
+            }
+        }
+        shared.in_flight.store(0, Ordering::Relaxed);
+
+        if result.is_ok() {
+            return;
+        }
+
+        connection = match reconnect(peer_addr, &mut jobs, &shared).await {
+            Some(connection) => connection,
+            None => return,
+        };
+        shared.publish(connection.connected_event());
+    }
+}
+
+/// Connects to `peer_addr` again, waiting longer after every failed attempt. Jobs queued in the
+/// meantime fail as disconnected. Returns `None` when the client is dropped.
+async fn reconnect(
+    peer_addr: SocketAddr,
+    jobs: &mut mpsc::Receiver<Job>,
+    shared: &Shared,
+) -> Option<Connection> {
+    let mut delay = shared.settings().min_reconnect_delay;
+    for attempt in 1.. {
+        shared.publish(ConnectionEvent::Reconnecting { attempt, delay });
+        let sleep = tokio::time::sleep(delay);
+        tokio::pin!(sleep);
+        loop {
+            tokio::select! {
+                _ = &mut sleep => break,
+                job = jobs.recv() => {
+                    let _ = job?.response.send(Err(fins_tcp::Error::Disconnected));
+                }
+            }
+        }
+
+        match Connection::connect(peer_addr).await {
+            Ok(connection) => return Some(connection),
+            Err(error) => {
+                warn!("reconnecting to {} failed: {}", peer_addr, error);
+                delay = (delay * 2).min(shared.settings().max_reconnect_delay);
+            }
+        }
+    }
+    unreachable!()
+}
+
+/// Sends the queued jobs while keeping at most `window` requests in flight, and hands the
+/// responses to the jobs that are waiting for them. Returns `Ok` when the client is dropped and
+/// an error when the connection fails or a response does not arrive in time.
+async fn serve(
+    connection: &mut Connection,
+    jobs: &mut mpsc::Receiver<Job>,
+    shared: &Shared,
+    in_flight: &mut HashMap<u8, InFlight>,
+) -> fins_tcp::Result<()> {
+    let Connection {
+        reader,
+        writer,
+        session,
+        ..
+    } = connection;
+    let mut next_service_id = 0u8;
+    let mut write_buffer = Vec::with_capacity(2048);
+    let mut last_activity = Instant::now();
+
+    loop {
+        let settings = shared.settings();
+
+        // Wait for the oldest request in flight to time out, or for the connection to become
+        // idle long enough to check it.
+        let deadline = match in_flight.values().map(|request| request.sent_at).min() {
+            Some(sent_at) => Some(sent_at + settings.response_timeout),
+            None => settings
+                .keepalive_interval
+                .map(|interval| last_activity + interval),
+        };
+        let timeout = tokio::time::sleep_until(tokio::time::Instant::from_std(
+            deadline.unwrap_or_else(Instant::now),
+        ));
+
+        let (mut frame, response, queued_at) = tokio::select! {
+            job = jobs.recv(), if in_flight.len() < settings.window => {
+                match job {
+                    Some(Job { frame, queued_at, response }) => (frame, Some(response), Some(queued_at)),
+                    None => return Ok(()),
+                }
+            }
+            frame = reader.read_frame(|cursor| fins_tcp::read_fins_frame(cursor)) => {
+                let frame = frame?;
+                last_activity = Instant::now();
+                let header = fins::Header::from_bytes(&frame)?;
+                let service_id = header.sid;
+                let InFlight { request, sent_at, response } = match in_flight.remove(&service_id) {
+                    Some(in_flight) => in_flight,
+                    None => {
+                        warn!("dropping response with unexpected service id {}", service_id);
+                        continue;
+                    }
+                };
+                shared.in_flight.store(in_flight.len(), Ordering::Relaxed);
+                let round_trip_time = sent_at.elapsed();
+                debug!("response {} received in {:?}", service_id, round_trip_time);
+                let result = session
+                    .check_response(&request, &header)
+                    .map(|()| frame)
+                    .map_err(fins_tcp::Error::from);
+                match response {
+                    Some(response) => {
+                        shared.metrics.lock().unwrap().record_round_trip_time(round_trip_time);
+                        // The caller may have stopped waiting.
+                        let _ = response.send(result);
+                    }
+                    None => match result {
+                        Ok(_) => debug!("keepalive {} answered", service_id),
+                        Err(error) => warn!("keepalive {} failed: {}", service_id, error),
+                    },
+                }
+                continue;
+            }
+            _ = timeout, if deadline.is_some() => {
+                if !in_flight.is_empty() {
+                    return Err(std::io::Error::new(
+                        ErrorKind::TimedOut,
+                        "no response within the response timeout",
+                    )
+                    .into());
+                }
+                let mut frame = Vec::new();
+                let keepalive = LoopbackTestRequest { bytes: Vec::new() };
+                fins::write_request(&mut frame, &session.route(), 0, &keepalive)?;
+                (frame, None, None)
+            }
+        };
+
+        while in_flight.contains_key(&next_service_id) {
+            next_service_id = next_service_id.wrapping_add(1);
+        }
+        let service_id = next_service_id;
+        next_service_id = next_service_id.wrapping_add(1);
+
+        // The server may assign another client node after reconnecting.
+        let mut request = fins::Header::from_bytes(&frame)?;
+        request.sid = service_id;
+        session.stamp(&mut request);
+        request.write_to(&mut &mut frame[..])?;
+
+        write_buffer.clear();
+        fins_tcp::write_fins_frame(&mut write_buffer, &frame)?;
+        writer.write_all(&write_buffer).await?;
+        writer.flush().await?;
+
+        let sent_at = Instant::now();
+        last_activity = sent_at;
+        if let Some(queued_at) = queued_at {
+            shared
+                .metrics
+                .lock()
+                .unwrap()
+                .record_queue_time(sent_at - queued_at);
+        }
+        in_flight.insert(
+            service_id,
+            InFlight {
+                request,
+                sent_at,
+                response,
+            },
+        );
+        shared.in_flight.store(in_flight.len(), Ordering::Relaxed);
+    }
+}
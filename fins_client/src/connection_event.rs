@@ -0,0 +1,18 @@
+use std::time::Duration;
+
+use fins_tcp::Session;
+
+/// A change in the state of the connection of a [`Client`](crate::Client).
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ConnectionEvent {
+    /// The handshake completed and requests are sent with the nodes of `session`.
+    Connected { session: Session },
+
+    /// The connection failed. Requests in flight fail with
+    /// [`Error::Disconnected`](fins_tcp::Error::Disconnected).
+    Disconnected { error: String },
+
+    /// The client waits `delay` before connecting again. Requests fail with
+    /// [`Error::Disconnected`](fins_tcp::Error::Disconnected) until the connection is restored.
+    Reconnecting { attempt: u32, delay: Duration },
+}
@@ -1 +1,14 @@
-
+mod client;
+mod connection;
+mod connection_event;
+mod metrics;
+mod pool;
+mod read_plan;
+mod udp_client;
+
+pub use client::*;
+pub use connection_event::*;
+pub use metrics::*;
+pub use pool::*;
+pub use read_plan::*;
+pub use udp_client::*;
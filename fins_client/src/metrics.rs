@@ -0,0 +1,53 @@
+use std::time::Duration;
+
+/// Latencies of the requests executed by a [`Client`](crate::Client), used to tune its window.
+/// A window that is too large shows up as a round trip time that grows with the window, a window
+/// that is too small as a queue time that grows with the load.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct Metrics {
+    /// Number of requests sent.
+    pub requests: u64,
+
+    /// Total time requests waited for a place in the window.
+    pub total_queue_time: Duration,
+
+    pub max_queue_time: Duration,
+
+    /// Number of responses received.
+    pub responses: u64,
+
+    /// Total time between sending requests and receiving their responses.
+    pub total_round_trip_time: Duration,
+
+    pub max_round_trip_time: Duration,
+}
+
+impl Metrics {
+    pub fn mean_queue_time(&self) -> Option<Duration> {
+        mean(self.total_queue_time, self.requests)
+    }
+
+    pub fn mean_round_trip_time(&self) -> Option<Duration> {
+        mean(self.total_round_trip_time, self.responses)
+    }
+
+    pub(crate) fn record_queue_time(&mut self, queue_time: Duration) {
+        self.requests += 1;
+        self.total_queue_time += queue_time;
+        self.max_queue_time = self.max_queue_time.max(queue_time);
+    }
+
+    pub(crate) fn record_round_trip_time(&mut self, round_trip_time: Duration) {
+        self.responses += 1;
+        self.total_round_trip_time += round_trip_time;
+        self.max_round_trip_time = self.max_round_trip_time.max(round_trip_time);
+    }
+}
+
+fn mean(total: Duration, count: u64) -> Option<Duration> {
+    if count == 0 {
+        None
+    } else {
+        Some(Duration::from_secs_f64(total.as_secs_f64() / count as f64))
+    }
+}
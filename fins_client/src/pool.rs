@@ -0,0 +1,273 @@
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::Client;
+
+/// Connections to many servers by name, established when first needed.
+///
+/// Every [`Client`] handed out is shared through an `Arc` and can execute commands for many tasks
+/// at once. A server gets a single connection unless configured otherwise. More connections are
+/// only established while the windows of all existing connections are saturated, and never beyond
+/// the maximum number of connections of the server. Clients of lost connections reconnect in the
+/// background and keep counting toward that maximum. Clients are spawned on the runtime that
+/// calls [`Pool::client`].
+pub struct Pool {
+    servers: Mutex<Servers>,
+    connect_timeout: Mutex<Duration>,
+}
+
+#[derive(Default)]
+struct Servers {
+    addrs: HashMap<String, SocketAddr>,
+    servers: HashMap<SocketAddr, Arc<Server>>,
+}
+
+struct Server {
+    max_connections: Mutex<usize>,
+    clients: tokio::sync::Mutex<Vec<Arc<Client>>>,
+}
+
+impl Default for Pool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Pool {
+    /// Maximum number of FINS/TCP connections to a server unless configured otherwise. Units
+    /// accept a limited number of connections which other clients may need.
+    pub const DEFAULT_MAX_CONNECTIONS: usize = 1;
+
+    pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+    pub fn new() -> Self {
+        Self {
+            servers: Mutex::default(),
+            connect_timeout: Mutex::new(Self::DEFAULT_CONNECT_TIMEOUT),
+        }
+    }
+
+    /// Names the server at `addr`. Names for the same address share its connections.
+    pub fn insert(&self, name: impl Into<String>, addr: SocketAddr) {
+        let mut servers = self.servers.lock().unwrap();
+        servers.addrs.insert(name.into(), addr);
+        servers.servers.entry(addr).or_insert_with(|| {
+            Arc::new(Server {
+                max_connections: Mutex::new(Self::DEFAULT_MAX_CONNECTIONS),
+                clients: Default::default(),
+            })
+        });
+    }
+
+    /// The names of the servers.
+    pub fn names(&self) -> Vec<String> {
+        self.servers.lock().unwrap().addrs.keys().cloned().collect()
+    }
+
+    /// Limits the number of connections to the server named `name`. Existing connections are
+    /// kept.
+    pub fn set_max_connections(&self, name: &str, max_connections: usize) -> fins_tcp::Result<()> {
+        assert!(max_connections > 0);
+        let (_, server) = self.server(name)?;
+        *server.max_connections.lock().unwrap() = max_connections;
+        Ok(())
+    }
+
+    /// Sets how long [`Pool::client`] waits for a new connection to be established.
+    pub fn set_connect_timeout(&self, timeout: Duration) {
+        *self.connect_timeout.lock().unwrap() = timeout;
+    }
+
+    /// A client for the server named `name`, the connected one with the fewest requests in flight.
+    /// A new connection is established when there is none, or when no client is connected with
+    /// room in its window and the maximum number of connections has not been reached.
+    pub async fn client(&self, name: &str) -> fins_tcp::Result<Arc<Client>> {
+        let (addr, server) = self.server(name)?;
+        let max_connections = *server.max_connections.lock().unwrap();
+        let connect_timeout = *self.connect_timeout.lock().unwrap();
+
+        // Connecting holds the lock so that concurrent callers do not exceed the maximum.
+        let mut clients = server.clients.lock().await;
+
+        // A lost connection is reestablished by the task of its client, so the client is kept
+        // and handed out when no connected client is available.
+        let least_loaded = clients
+            .iter()
+            .min_by_key(|client| (!client.is_connected(), client.in_flight()));
+        if let Some(client) = least_loaded {
+            let available = client.is_connected() && client.in_flight() < client.window();
+            if available || clients.len() >= max_connections {
+                return Ok(Arc::clone(client));
+            }
+        }
+
+        let client = tokio::time::timeout(connect_timeout, Client::connect(addr))
+            .await
+            .map_err(|_| {
+                std::io::Error::new(
+                    ErrorKind::TimedOut,
+                    format!("connecting to {} timed out", addr),
+                )
+            })??;
+        let client = Arc::new(client);
+        clients.push(Arc::clone(&client));
+        Ok(client)
+    }
+
+    /// Number of connections established to the server named `name`.
+    pub async fn connection_count(&self, name: &str) -> fins_tcp::Result<usize> {
+        let (_, server) = self.server(name)?;
+        let count = server.clients.lock().await.len();
+        Ok(count)
+    }
+
+    fn server(&self, name: &str) -> fins_tcp::Result<(SocketAddr, Arc<Server>)> {
+        let servers = self.servers.lock().unwrap();
+        let addr = servers.addrs.get(name).copied().ok_or_else(|| {
+            std::io::Error::new(ErrorKind::NotFound, format!("unknown server {:?}", name))
+        })?;
+        Ok((addr, Arc::clone(&servers.servers[&addr])))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fins::NameReadRequest;
+    use fins_server::{MemoryHandler, Server};
+    use fins_tcp::ServerAddressFrame;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    use super::*;
+    use crate::ConnectionEvent;
+
+    #[tokio::test]
+    async fn pool_works() {
+        let mut handler = MemoryHandler::default();
+        handler.set_name("PLC");
+        let mut server = Server::bind("127.0.0.1:0", 0x01, handler).await.unwrap();
+        server.set_response_delay(Duration::from_millis(100));
+        let addr = server.local_addr().unwrap();
+        tokio::spawn(server.run());
+
+        let pool = Pool::new();
+        pool.insert("press", addr);
+        pool.insert("press-alias", addr);
+        assert_eq!(pool.connection_count("press").await.unwrap(), 0);
+
+        // Names for the same server share its connection.
+        let first = pool.client("press").await.unwrap();
+        let second = pool.client("press-alias").await.unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+
+        // A saturated window only leads to a new connection below the maximum.
+        first.set_window(1);
+        let pending = tokio::spawn({
+            let first = Arc::clone(&first);
+            async move { first.execute(&NameReadRequest).await }
+        });
+        while first.in_flight() == 0 {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+        let second = pool.client("press").await.unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+
+        pool.set_max_connections("press", 2).unwrap();
+        let second = pool.client("press").await.unwrap();
+        assert!(!Arc::ptr_eq(&first, &second));
+        assert_eq!(pool.connection_count("press").await.unwrap(), 2);
+
+        // The client with the fewest requests in flight is handed out.
+        let third = pool.client("press").await.unwrap();
+        assert!(Arc::ptr_eq(&third, &second));
+
+        assert_eq!(pending.await.unwrap().unwrap().name, "PLC");
+
+        assert!(pool.client("mill").await.is_err());
+    }
+
+    /// Accepts a connection and assigns client node 0x02 as server node 0x01.
+    async fn accept_handshake(listener: &TcpListener) -> TcpStream {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut buffer = vec![0; 20];
+        stream.read_exact(&mut buffer).await.unwrap();
+        let mut output = Vec::new();
+        ServerAddressFrame {
+            client_node: 0x02,
+            server_node: 0x01,
+        }
+        .write_to(&mut output)
+        .unwrap();
+        stream.write_all(&output).await.unwrap();
+        stream
+    }
+
+    #[tokio::test]
+    async fn keeps_reconnecting_clients() {
+        // Keeps the connections it accepts open until told to drop them.
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let streams = Arc::new(Mutex::new(Vec::new()));
+        let (lose, mut lost) = tokio::sync::mpsc::channel::<()>(1);
+        tokio::spawn({
+            let streams = Arc::clone(&streams);
+            async move {
+                loop {
+                    tokio::select! {
+                        stream = accept_handshake(&listener) => streams.lock().unwrap().push(stream),
+                        Some(()) = lost.recv() => streams.lock().unwrap().clear(),
+                    }
+                }
+            }
+        });
+
+        let pool = Pool::new();
+        pool.insert("press", addr);
+        let client = pool.client("press").await.unwrap();
+        client.set_reconnect_delay(Duration::from_millis(10), Duration::from_millis(10));
+        let mut events = client.subscribe();
+        for _ in 0..2 {
+            lose.send(()).await.unwrap();
+            while !matches!(
+                events.recv().await,
+                Ok(ConnectionEvent::Disconnected { .. })
+            ) {}
+
+            // The client that reconnects is handed out instead of a new connection.
+            let same = pool.client("press").await.unwrap();
+            assert!(Arc::ptr_eq(&client, &same));
+
+            while !matches!(events.recv().await, Ok(ConnectionEvent::Connected { .. })) {}
+            while streams.lock().unwrap().is_empty() {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+            let same = pool.client("press").await.unwrap();
+            assert!(Arc::ptr_eq(&client, &same));
+            assert_eq!(streams.lock().unwrap().len(), Pool::DEFAULT_MAX_CONNECTIONS);
+            assert_eq!(
+                pool.connection_count("press").await.unwrap(),
+                Pool::DEFAULT_MAX_CONNECTIONS
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn connecting_times_out() {
+        // Accepts connections but never completes the handshake.
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let pool = Pool::new();
+        pool.insert("press", addr);
+        pool.set_connect_timeout(Duration::from_millis(10));
+        assert!(matches!(
+            pool.client("press").await,
+            Err(fins_tcp::Error::Io(error)) if error.kind() == ErrorKind::TimedOut
+        ));
+        assert_eq!(pool.connection_count("press").await.unwrap(), 0);
+        drop(listener);
+    }
+}
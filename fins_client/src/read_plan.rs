@@ -0,0 +1,354 @@
+use fins::{EndCode, MemoryAddress, MULTIPLE_MEMORY_AREA_READ_MAX_COUNT};
+
+use crate::client::split_range;
+use crate::Client;
+
+/// A request planned by a [`ReadPlanner`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum PlannedRead {
+    /// A memory area read of `count` consecutive items.
+    Range { address: MemoryAddress, count: u16 },
+    /// A multiple memory area read of a single item at every address.
+    Multiple(Vec<MemoryAddress>),
+}
+
+/// Plans the reads of many small ranges, like the values of tags, in few requests.
+///
+/// A request costs a round trip while reading a word more costs little, so ranges in the same
+/// area that are at most `max_gap` items apart are read as one. The ranges that remain small are
+/// collected in multiple memory area reads.
+#[derive(Debug, Clone)]
+pub struct ReadPlanner {
+    max_gap: usize,
+    max_read_count: u16,
+    multiple_read_threshold: usize,
+}
+
+impl Default for ReadPlanner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReadPlanner {
+    /// Number of items between two ranges that are read to join them unless configured
+    /// otherwise.
+    pub const DEFAULT_MAX_GAP: usize = 64;
+
+    /// Number of items of the ranges read with multiple memory area reads unless configured
+    /// otherwise, enough for the 32-bit values of scattered tags.
+    pub const DEFAULT_MULTIPLE_READ_THRESHOLD: usize = 2;
+
+    pub fn new() -> Self {
+        Self {
+            max_gap: Self::DEFAULT_MAX_GAP,
+            max_read_count: Client::DEFAULT_MAX_READ_COUNT,
+            multiple_read_threshold: Self::DEFAULT_MULTIPLE_READ_THRESHOLD,
+        }
+    }
+
+    pub fn set_max_gap(&mut self, max_gap: usize) {
+        self.max_gap = max_gap;
+    }
+
+    /// Sets the maximum number of items per memory area read, which should match
+    /// [`Client::set_max_counts`].
+    pub fn set_max_read_count(&mut self, max_read_count: u16) {
+        assert!(max_read_count > 0);
+        self.max_read_count = max_read_count;
+    }
+
+    /// Ranges of at most `threshold` items that are not joined with others are read with
+    /// multiple memory area reads. A threshold of 0 disables multiple memory area reads, for
+    /// units that do not support them.
+    pub fn set_multiple_read_threshold(&mut self, threshold: usize) {
+        assert!(threshold <= MULTIPLE_MEMORY_AREA_READ_MAX_COUNT);
+        self.multiple_read_threshold = threshold;
+    }
+
+    /// Plans the reads of `count` items starting at `address` for every range. Fails like a unit
+    /// would when a range does not fit in its area.
+    pub fn plan(&self, ranges: &[(MemoryAddress, usize)]) -> fins_tcp::Result<ReadPlan> {
+        for &(address, count) in ranges {
+            if address.offset_by(0).is_none() {
+                return Err(fins_tcp::Error::EndCode(EndCode::ADDRESS_OUT_OF_RANGE));
+            }
+            if address.offset_by(count).is_none() {
+                return Err(fins_tcp::Error::EndCode(EndCode::ADDRESS_RANGE_EXCEEDED));
+            }
+        }
+
+        let mut order = (0..ranges.len())
+            .filter(|&index| ranges[index].1 > 0)
+            .collect::<Vec<_>>();
+        order.sort_by_key(|&index| {
+            let address = ranges[index].0;
+            (address.area_code.serialize().0, position(address))
+        });
+
+        let mut segments = Vec::<Segment>::new();
+        for index in order {
+            let (address, count) = ranges[index];
+            let start = position(address);
+            match segments.last_mut() {
+                Some(segment)
+                    if segment.address.area_code == address.area_code
+                        && start <= segment.end + self.max_gap =>
+                {
+                    segment.end = segment.end.max(start + count);
+                    segment.members.push(index);
+                }
+                _ => segments.push(Segment {
+                    address,
+                    start,
+                    end: start + count,
+                    members: vec![index],
+                }),
+            }
+        }
+
+        let (mut small, mut large): (Vec<_>, Vec<_>) = segments
+            .into_iter()
+            .partition(|segment| segment.count() <= self.multiple_read_threshold);
+        if small.len() < 2 {
+            // A multiple memory area read of a single range saves nothing.
+            large.append(&mut small);
+        }
+
+        let mut plan = ReadPlan {
+            requests: Vec::new(),
+            buffers: Vec::new(),
+            slices: vec![None; ranges.len()],
+            ranges: ranges.to_vec(),
+            planner: self.clone(),
+        };
+        let mut buffer_count = 0;
+
+        for segment in &large {
+            let buffer = buffer_count;
+            buffer_count += 1;
+            for (address, count) in
+                split_range(segment.address, segment.count(), self.max_read_count)?
+            {
+                plan.requests.push(PlannedRead::Range { address, count });
+                plan.buffers.push(buffer);
+            }
+            plan.place(ranges, segment, buffer, 0);
+        }
+
+        let mut addresses = Vec::new();
+        let mut bytes = 0;
+        for segment in &small {
+            if addresses.len() + segment.count() > MULTIPLE_MEMORY_AREA_READ_MAX_COUNT {
+                plan.requests
+                    .push(PlannedRead::Multiple(std::mem::take(&mut addresses)));
+                plan.buffers.push(buffer_count);
+                buffer_count += 1;
+                bytes = 0;
+            }
+            plan.place(ranges, segment, buffer_count, bytes);
+            addresses.extend((0..segment.count()).map(|i| segment.address.offset_by(i).unwrap()));
+            bytes += segment.count() * segment.address.area_code.item_size();
+        }
+        if !addresses.is_empty() {
+            plan.requests.push(PlannedRead::Multiple(addresses));
+            plan.buffers.push(buffer_count);
+        }
+
+        Ok(plan)
+    }
+}
+
+/// The requests that read a set of ranges, see [`ReadPlanner::plan`].
+#[derive(Debug, Clone)]
+pub struct ReadPlan {
+    requests: Vec<PlannedRead>,
+    /// The buffer every request reads into. Requests into the same buffer are consecutive.
+    buffers: Vec<usize>,
+    /// Where the bytes of every range are, `None` for empty ranges.
+    slices: Vec<Option<Slice>>,
+    /// The planned ranges, to plan them again when bits have to be read through words.
+    ranges: Vec<(MemoryAddress, usize)>,
+    planner: ReadPlanner,
+}
+
+impl ReadPlan {
+    pub fn requests(&self) -> &[PlannedRead] {
+        &self.requests
+    }
+
+    pub(crate) fn ranges(&self) -> &[(MemoryAddress, usize)] {
+        &self.ranges
+    }
+
+    pub(crate) fn planner(&self) -> &ReadPlanner {
+        &self.planner
+    }
+
+    /// Splits the bytes read by every request into the bytes of every range, in the order in
+    /// which the ranges were planned.
+    pub fn split(&self, responses: Vec<Vec<u8>>) -> Vec<Vec<u8>> {
+        assert_eq!(responses.len(), self.requests.len());
+        let mut buffers = Vec::<Vec<u8>>::new();
+        for (&buffer, bytes) in self.buffers.iter().zip(responses) {
+            if buffer == buffers.len() {
+                buffers.push(bytes);
+            } else {
+                buffers[buffer].extend(bytes);
+            }
+        }
+        self.slices
+            .iter()
+            .map(|slice| match slice {
+                Some(slice) => buffers[slice.buffer][slice.start..slice.start + slice.len].to_vec(),
+                None => Vec::new(),
+            })
+            .collect()
+    }
+
+    /// Records where the bytes of the ranges in `segment` are, given that the segment starts
+    /// `offset` bytes into `buffer`.
+    fn place(
+        &mut self,
+        ranges: &[(MemoryAddress, usize)],
+        segment: &Segment,
+        buffer: usize,
+        offset: usize,
+    ) {
+        let item_size = segment.address.area_code.item_size();
+        for &index in &segment.members {
+            let (address, count) = ranges[index];
+            self.slices[index] = Some(Slice {
+                buffer,
+                start: offset + (position(address) - segment.start) * item_size,
+                len: count * item_size,
+            });
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+struct Slice {
+    buffer: usize,
+    start: usize,
+    len: usize,
+}
+
+/// Ranges in the same area that are read together.
+struct Segment {
+    address: MemoryAddress,
+    start: usize,
+    end: usize,
+    /// Indices of the ranges in the segment.
+    members: Vec<usize>,
+}
+
+impl Segment {
+    fn count(&self) -> usize {
+        self.end - self.start
+    }
+}
+
+/// The index of the item at `address` within its area.
+fn position(address: MemoryAddress) -> usize {
+    if address.area_code.is_bit_area() {
+        address.offset as usize * 16 + address.bits as usize
+    } else {
+        address.offset as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn address(text: &str) -> MemoryAddress {
+        text.parse().unwrap()
+    }
+
+    fn count_ranges(plan: &ReadPlan) -> (usize, usize) {
+        let ranges = plan
+            .requests()
+            .iter()
+            .filter(|request| matches!(request, PlannedRead::Range { .. }))
+            .count();
+        (ranges, plan.requests().len() - ranges)
+    }
+
+    #[test]
+    fn plan_works() {
+        let ranges = [
+            (address("D110"), 1),
+            (address("D100"), 2),
+            (address("D101"), 1),
+            (address("D300"), 2),
+            (address("W3.02"), 1),
+            (address("D0"), 0),
+        ];
+        let plan = ReadPlanner::new().plan(&ranges).unwrap();
+        assert_eq!(
+            plan.requests(),
+            [
+                PlannedRead::Range {
+                    address: address("D100"),
+                    count: 11
+                },
+                PlannedRead::Multiple(vec![address("W3.02"), address("D300"), address("D301")]),
+            ]
+        );
+
+        let mut range = (0..22).collect::<Vec<u8>>();
+        range.reverse();
+        let bytes = plan.split(vec![range, vec![1, 30, 0, 30, 1]]);
+        assert_eq!(
+            bytes,
+            [
+                vec![1, 0],
+                vec![21, 20, 19, 18],
+                vec![19, 18],
+                vec![30, 0, 30, 1],
+                vec![1],
+                vec![]
+            ]
+        );
+
+        let mut planner = ReadPlanner::new();
+        planner.set_max_gap(0);
+        planner.set_multiple_read_threshold(0);
+        assert_eq!(count_ranges(&planner.plan(&ranges).unwrap()), (4, 0));
+
+        assert!(matches!(
+            planner.plan(&[(address("W511"), 2)]),
+            Err(fins_tcp::Error::EndCode(EndCode::ADDRESS_RANGE_EXCEEDED))
+        ));
+    }
+
+    #[test]
+    fn plans_few_requests_for_realistic_tag_sets() {
+        let mut ranges = Vec::new();
+        // 100 INT and 50 DINT process values in a block of D memory.
+        ranges.extend((0..100).map(|i| (address("D1000").offset_by(i).unwrap(), 1)));
+        ranges.extend((0..50).map(|i| (address("D1100").offset_by(2 * i).unwrap(), 2)));
+        // 64 alarm bits in consecutive words.
+        ranges.extend((0..64).map(|i| (address("D2420.00").offset_by(i).unwrap(), 1)));
+        // 40 status bits scattered across the CIO and work areas.
+        ranges.extend((0..20).map(|i| (address("CIO0.00").offset_by(i * 100 * 16).unwrap(), 1)));
+        ranges.extend((0..20).map(|i| (address("W0.05").offset_by(i * 20 * 16).unwrap(), 1)));
+        // 46 setpoints in a recipe far from the process values.
+        ranges.extend((0..46).map(|i| (address("D20000").offset_by(10 * i).unwrap(), 2)));
+        assert_eq!(ranges.len(), 300);
+
+        let mut planner = ReadPlanner::new();
+        let plan = planner.plan(&ranges).unwrap();
+        // D1000 to D1199, D20000 to D20451 and the alarm bits are read as ranges, the scattered
+        // bits with one multiple memory area read.
+        assert_eq!(count_ranges(&plan), (3, 1));
+
+        planner.set_max_read_count(100);
+        assert_eq!(count_ranges(&planner.plan(&ranges).unwrap()), (8, 1));
+
+        planner.set_max_gap(0);
+        planner.set_multiple_read_threshold(0);
+        assert_eq!(count_ranges(&planner.plan(&ranges).unwrap()), (89, 0));
+    }
+}
@@ -0,0 +1,175 @@
+use std::net::IpAddr;
+use std::time::Duration;
+
+use fins::{Command, Header, MemoryAddress, MemoryAreaReadRequest, MemoryAreaWriteRequest, Route};
+use fins_tcp::MAX_FINS_FRAME_SIZE;
+use tokio::net::{ToSocketAddrs, UdpSocket};
+use tokio::sync::Mutex;
+
+use crate::client::split_range;
+use crate::Client;
+
+/// A FINS/UDP client. Every frame is a datagram, so there is no connection to lose, and
+/// requests are executed one at a time.
+pub struct UdpClient {
+    socket: UdpSocket,
+    route: Route,
+    response_timeout: Duration,
+    /// The service id of the last request, held while a request is executed.
+    sid: Mutex<u8>,
+}
+
+impl UdpClient {
+    /// Sends requests to `addr`, which is the unit with node `server_node`. The client node is
+    /// the last byte of the local IPv4 address, as units with automatically generated node
+    /// addresses expect.
+    pub async fn connect<A: ToSocketAddrs>(addr: A, server_node: u8) -> fins_tcp::Result<Self> {
+        let socket = UdpSocket::bind("0.0.0.0:0").await?;
+        socket.connect(addr).await?;
+        let client_node = match socket.local_addr()?.ip() {
+            IpAddr::V4(ip) => ip.octets()[3],
+            IpAddr::V6(_) => 0,
+        };
+        Ok(Self {
+            socket,
+            route: Route::local(server_node, client_node),
+            response_timeout: Client::DEFAULT_RESPONSE_TIMEOUT,
+            sid: Mutex::new(0),
+        })
+    }
+
+    pub fn route(&self) -> &Route {
+        &self.route
+    }
+
+    pub fn set_route(&mut self, route: Route) {
+        self.route = route;
+    }
+
+    pub fn set_response_timeout(&mut self, timeout: Duration) {
+        self.response_timeout = timeout;
+    }
+
+    /// Reads `count` items starting at `address`, see [`Client::read_range`].
+    pub async fn read_range(
+        &self,
+        address: MemoryAddress,
+        count: usize,
+    ) -> fins_tcp::Result<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(count * address.area_code.item_size());
+        for (address, count) in split_range(address, count, Client::DEFAULT_MAX_READ_COUNT)? {
+            bytes.extend(
+                self.execute(&MemoryAreaReadRequest { address, count })
+                    .await?,
+            );
+        }
+        Ok(bytes)
+    }
+
+    /// Writes `bytes` to consecutive items starting at `address`, see [`Client::write_range`].
+    pub async fn write_range(&self, address: MemoryAddress, bytes: &[u8]) -> fins_tcp::Result<()> {
+        let item_size = address.area_code.item_size();
+        assert!(bytes.len().is_multiple_of(item_size));
+
+        let max_count = Client::DEFAULT_MAX_WRITE_COUNT;
+        for ((address, _), bytes) in split_range(address, bytes.len() / item_size, max_count)?
+            .zip(bytes.chunks(max_count as usize * item_size))
+        {
+            self.execute(&MemoryAreaWriteRequest {
+                address,
+                bytes: bytes.to_vec(),
+            })
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Sends `command` and waits for its response. Responses to earlier requests that arrive
+    /// late are dropped, as are oversized frames.
+    pub async fn execute<C: Command>(&self, command: &C) -> fins_tcp::Result<C::Response> {
+        let mut sid = self.sid.lock().await;
+        *sid = sid.wrapping_add(1);
+
+        let mut frame = Vec::with_capacity(fins::request_byte_size(command));
+        fins::write_request(&mut frame, &self.route, *sid, command)?;
+        self.socket.send(&frame).await?;
+
+        let deadline = tokio::time::Instant::now() + self.response_timeout;
+        // One byte more than the largest frame tells oversized datagrams from truncated ones.
+        let mut buffer = vec![0; MAX_FINS_FRAME_SIZE + 1];
+        loop {
+            let size = tokio::time::timeout_at(deadline, self.socket.recv(&mut buffer))
+                .await
+                .map_err(|_| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::TimedOut,
+                        "no response within the response timeout",
+                    )
+                })??;
+            if size > MAX_FINS_FRAME_SIZE {
+                tracing::warn!("dropped oversized frame");
+                continue;
+            }
+            let frame = &buffer[..size];
+            match Header::from_bytes(frame) {
+                Ok(header) if header.sid == *sid => {
+                    return Ok(fins::read_response(command, frame)?.body);
+                }
+                Ok(header) => tracing::debug!("dropped response with service id {}", header.sid),
+                Err(error) => tracing::warn!("dropped frame: {}", error),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fins::{LoopbackTestRequest, MemoryAreaCode};
+    use fins_server::{MemoryHandler, UdpServer};
+
+    use super::*;
+
+    #[tokio::test]
+    async fn udp_client_works() {
+        let handler = MemoryHandler::default();
+        for (offset, word) in handler.memory().area(MemoryAreaCode::D)[..1000]
+            .iter_mut()
+            .enumerate()
+        {
+            *word = offset as u16;
+        }
+        let server = UdpServer::bind("127.0.0.1:0", handler.clone())
+            .await
+            .unwrap();
+        let addr = server.local_addr().unwrap();
+        tokio::spawn(server.run());
+
+        let client = UdpClient::connect(addr, 0x01).await.unwrap();
+        assert_eq!(client.route().source.node, 1);
+        let request = LoopbackTestRequest {
+            bytes: vec![1, 2, 3],
+        };
+        assert_eq!(client.execute(&request).await.unwrap().bytes, [1, 2, 3]);
+
+        let bytes = client
+            .read_range("D0".parse().unwrap(), 1000)
+            .await
+            .unwrap();
+        assert_eq!(bytes.len(), 2000);
+        assert_eq!(bytes[1996..], [0x03, 0xE6, 0x03, 0xE7]);
+        assert_eq!(handler.accesses().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn udp_client_times_out() {
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let mut client = UdpClient::connect(socket.local_addr().unwrap(), 0x01)
+            .await
+            .unwrap();
+        client.set_response_timeout(Duration::from_millis(10));
+        assert!(matches!(
+            client.execute(&LoopbackTestRequest { bytes: vec![] }).await,
+            Err(fins_tcp::Error::Io(error)) if error.kind() == std::io::ErrorKind::TimedOut
+        ));
+    }
+}
@@ -0,0 +1,267 @@
+//! The derive macro of `fins::FinsStruct`, see the documentation of that trait.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Lit, Meta, NestedMeta, Type};
+
+#[proc_macro_derive(FinsStruct, attributes(fins))]
+pub fn derive_fins_struct(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// The options of a `#[fins(...)]` attribute.
+#[derive(Default)]
+struct Options {
+    offset: Option<usize>,
+    bit: Option<u8>,
+    word_order: Option<TokenStream>,
+}
+
+impl Options {
+    fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut options = Self::default();
+        for attr in attrs.iter().filter(|attr| attr.path.is_ident("fins")) {
+            let list = match attr.parse_meta()? {
+                Meta::List(list) => list,
+                meta => return Err(syn::Error::new(meta.span(), "expected #[fins(...)]")),
+            };
+            for nested in list.nested {
+                let pair = match nested {
+                    NestedMeta::Meta(Meta::NameValue(pair)) => pair,
+                    nested => {
+                        return Err(syn::Error::new(nested.span(), "expected `name = value`"))
+                    }
+                };
+                let name = pair.path.get_ident().map(|name| name.to_string());
+                match (name.as_deref(), &pair.lit) {
+                    (Some("offset"), Lit::Int(offset)) => {
+                        options.offset = Some(offset.base10_parse()?);
+                    }
+                    (Some("bit"), Lit::Int(bit)) => {
+                        let bit = bit.base10_parse::<u8>()?;
+                        if bit > 15 {
+                            return Err(syn::Error::new(
+                                pair.lit.span(),
+                                "bits are numbered 0 to 15",
+                            ));
+                        }
+                        options.bit = Some(bit);
+                    }
+                    (Some("word_order"), Lit::Str(word_order)) => {
+                        options.word_order = Some(match word_order.value().as_str() {
+                            "low_first" => quote!(::fins::WordOrder::LowFirst),
+                            "high_first" => quote!(::fins::WordOrder::HighFirst),
+                            _ => {
+                                return Err(syn::Error::new(
+                                    word_order.span(),
+                                    "expected \"low_first\" or \"high_first\"",
+                                ))
+                            }
+                        });
+                    }
+                    _ => {
+                        return Err(syn::Error::new(
+                            pair.span(),
+                            "expected `offset = <word>`, `bit = <bit>` or `word_order = \"...\"`",
+                        ))
+                    }
+                }
+            }
+        }
+        Ok(options)
+    }
+}
+
+/// Where a field is stored. `offset` is an expression for the word offset of the field.
+struct Layout<'a> {
+    ident: &'a syn::Ident,
+    ty: &'a Type,
+    offset: TokenStream,
+    /// The bit of `bool` fields.
+    bit: Option<u8>,
+    word_order: TokenStream,
+}
+
+fn is_bool(ty: &Type) -> bool {
+    matches!(ty, Type::Path(path) if path.qself.is_none() && path.path.is_ident("bool"))
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    if !input.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            input.generics.span(),
+            "FinsStruct can not be derived for generic structs",
+        ));
+    }
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new(
+                    input.span(),
+                    "FinsStruct can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new(
+                input.span(),
+                "FinsStruct can only be derived for structs",
+            ))
+        }
+    };
+    let word_order = Options::parse(&input.attrs)?
+        .word_order
+        .unwrap_or_else(|| quote!(::fins::WordOrder::LowFirst));
+
+    let mut layouts = Vec::<Layout>::new();
+    for field in fields {
+        let options = Options::parse(&field.attrs)?;
+        let ident = field.ident.as_ref().unwrap();
+        let ty = &field.ty;
+        let previous = layouts.last();
+        let (offset, bit) = if is_bool(ty) {
+            // Bits follow the bits before them in the same word.
+            let previous_bit =
+                previous.and_then(|previous| Some((&previous.offset, previous.bit?)));
+            match (options.offset, options.bit, previous_bit) {
+                (Some(offset), bit, _) => (quote!(#offset), Some(bit.unwrap_or(0))),
+                (None, bit, Some((offset, previous_bit))) => match bit {
+                    Some(bit) => (offset.clone(), Some(bit)),
+                    None if previous_bit < 15 => (offset.clone(), Some(previous_bit + 1)),
+                    None => (quote!(#offset + 1), Some(0)),
+                },
+                (None, bit, None) => (next_offset(previous), Some(bit.unwrap_or(0))),
+            }
+        } else {
+            if options.bit.is_some() {
+                return Err(syn::Error::new(ty.span(), "only bool fields have a bit"));
+            }
+            let offset = match options.offset {
+                Some(offset) => quote!(#offset),
+                None => next_offset(previous),
+            };
+            (offset, None)
+        };
+        layouts.push(Layout {
+            ident,
+            ty,
+            offset,
+            bit,
+            word_order: options.word_order.unwrap_or_else(|| word_order.clone()),
+        });
+    }
+
+    let ends = layouts
+        .iter()
+        .map(|layout| {
+            let (offset, ty) = (&layout.offset, layout.ty);
+            match layout.bit {
+                Some(_) => quote!(#offset + 1),
+                None => quote!(#offset + <#ty as ::fins::FinsValue>::WORD_COUNT),
+            }
+        })
+        .collect::<Vec<_>>();
+    let decode = layouts.iter().map(|layout| {
+        let (ident, ty, offset, word_order) =
+            (layout.ident, layout.ty, &layout.offset, &layout.word_order);
+        match layout.bit {
+            Some(bit) => quote! {
+                #ident: ::fins::word_bits(bytes, (#offset) * 16 + #bit as usize, 1)[0]
+            },
+            None => quote! {
+                #ident: <#ty as ::fins::FinsValue>::decode_words(&bytes[(#offset) * 2..], #word_order)
+            },
+        }
+    });
+    let encode = layouts.iter().map(|layout| {
+        let (ident, offset, word_order) = (layout.ident, &layout.offset, &layout.word_order);
+        match layout.bit {
+            Some(bit) => quote! {
+                ::fins::set_word_bits(bytes, (#offset) * 16 + #bit as usize, &[self.#ident]);
+            },
+            None => quote! {
+                ::fins::FinsValue::encode_words(&self.#ident, &mut bytes[(#offset) * 2..], #word_order);
+            },
+        }
+    });
+
+    // Fields other than bits may not overlap, which is checked when the offsets are known.
+    let words = layouts
+        .iter()
+        .filter(|layout| layout.bit.is_none())
+        .collect::<Vec<_>>();
+    let mut checks = Vec::new();
+    for (index, a) in words.iter().enumerate() {
+        for b in &words[index + 1..] {
+            let message = format!(
+                "fields `{}` and `{}` of `{}` overlap",
+                a.ident, b.ident, name
+            );
+            let (a_offset, a_ty, b_offset, b_ty) = (&a.offset, a.ty, &b.offset, b.ty);
+            checks.push(quote! {
+                assert!(
+                    #a_offset + <#a_ty as ::fins::FinsValue>::WORD_COUNT <= #b_offset
+                        || #b_offset + <#b_ty as ::fins::FinsValue>::WORD_COUNT <= #a_offset,
+                    #message
+                );
+            });
+        }
+    }
+
+    Ok(quote! {
+        impl ::fins::FinsStruct for #name {
+            const WORD_COUNT: usize = {
+                let mut count = 0;
+                #(
+                    if #ends > count {
+                        count = #ends;
+                    }
+                )*
+                count
+            };
+
+            fn decode(bytes: &[u8]) -> ::std::result::Result<Self, ::fins::ProtocolViolation> {
+                let expected = <Self as ::fins::FinsStruct>::WORD_COUNT * 2;
+                if bytes.len() != expected {
+                    return Err(::fins::ProtocolViolation::UnexpectedBodyLength {
+                        actual: bytes.len(),
+                        expected,
+                    });
+                }
+                Ok(Self {
+                    #(#decode,)*
+                })
+            }
+
+            fn encode_into(&self, bytes: &mut [u8]) {
+                assert_eq!(bytes.len(), <Self as ::fins::FinsStruct>::WORD_COUNT * 2);
+                #(#encode)*
+            }
+        }
+
+        const _: () = {
+            #(#checks)*
+        };
+    })
+}
+
+/// The offset of the word after `previous`.
+fn next_offset(previous: Option<&Layout>) -> TokenStream {
+    match previous {
+        None => quote!(0usize),
+        Some(Layout {
+            offset,
+            bit: Some(_),
+            ..
+        }) => quote!(#offset + 1),
+        Some(Layout { offset, ty, .. }) => {
+            quote!(#offset + <#ty as ::fins::FinsValue>::WORD_COUNT)
+        }
+    }
+}
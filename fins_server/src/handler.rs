@@ -0,0 +1,391 @@
+use fins::*;
+
+/// Executes the commands received by a server. Every method corresponds to one command and
+/// receives the header of the request frame so that gateways can inspect its route.
+///
+/// Commands that are not implemented respond with [`EndCode::UNDEFINED_COMMAND`], except for the
+/// loopback test which echoes the data and the multiple memory area read which is built on the
+/// memory area read.
+pub trait Handler {
+    /// Called for every request before its command is decoded. Returning an end code responds
+    /// with it instead of executing the command.
+    fn intercept(&mut self, _request: &Request) -> Result<(), EndCode> {
+        Ok(())
+    }
+
+    fn memory_area_read(
+        &mut self,
+        _header: &Header,
+        _command: MemoryAreaReadRequest,
+    ) -> Result<Vec<u8>, EndCode> {
+        Err(EndCode::UNDEFINED_COMMAND)
+    }
+
+    fn memory_area_write(
+        &mut self,
+        _header: &Header,
+        _command: MemoryAreaWriteRequest,
+    ) -> Result<(), EndCode> {
+        Err(EndCode::UNDEFINED_COMMAND)
+    }
+
+    /// Reads every address with [`Handler::memory_area_read`] unless overridden.
+    fn multiple_memory_area_read(
+        &mut self,
+        header: &Header,
+        command: MultipleMemoryAreaReadRequest,
+    ) -> Result<Vec<MemoryAreaItem>, EndCode> {
+        command
+            .addresses
+            .into_iter()
+            .map(|address| {
+                Ok(MemoryAreaItem {
+                    area_code: address.area_code,
+                    bytes: self
+                        .memory_area_read(header, MemoryAreaReadRequest { address, count: 1 })?,
+                })
+            })
+            .collect()
+    }
+
+    fn memory_area_fill(
+        &mut self,
+        _header: &Header,
+        _command: MemoryAreaFillRequest,
+    ) -> Result<(), EndCode> {
+        Err(EndCode::UNDEFINED_COMMAND)
+    }
+
+    fn memory_area_transfer(
+        &mut self,
+        _header: &Header,
+        _command: MemoryAreaTransferRequest,
+    ) -> Result<(), EndCode> {
+        Err(EndCode::UNDEFINED_COMMAND)
+    }
+
+    fn parameter_area_read(
+        &mut self,
+        _header: &Header,
+        _command: ParameterAreaReadRequest,
+    ) -> Result<ParameterAreaReadResponse, EndCode> {
+        Err(EndCode::UNDEFINED_COMMAND)
+    }
+
+    fn parameter_area_write(
+        &mut self,
+        _header: &Header,
+        _command: ParameterAreaWriteRequest,
+    ) -> Result<(), EndCode> {
+        Err(EndCode::UNDEFINED_COMMAND)
+    }
+
+    fn parameter_area_clear(
+        &mut self,
+        _header: &Header,
+        _command: ParameterAreaClearRequest,
+    ) -> Result<(), EndCode> {
+        Err(EndCode::UNDEFINED_COMMAND)
+    }
+
+    fn controller_data_read(
+        &mut self,
+        _header: &Header,
+        _command: ControllerDataReadRequest,
+    ) -> Result<ControllerDataReadResponse, EndCode> {
+        Err(EndCode::UNDEFINED_COMMAND)
+    }
+
+    fn controller_status_read(
+        &mut self,
+        _header: &Header,
+        _command: ControllerStatusReadRequest,
+    ) -> Result<ControllerStatusReadResponse, EndCode> {
+        Err(EndCode::UNDEFINED_COMMAND)
+    }
+
+    fn message_read(
+        &mut self,
+        _header: &Header,
+        _command: MessageReadRequest,
+    ) -> Result<MessageReadResponse, EndCode> {
+        Err(EndCode::UNDEFINED_COMMAND)
+    }
+
+    fn message_clear(
+        &mut self,
+        _header: &Header,
+        _command: MessageClearRequest,
+    ) -> Result<(), EndCode> {
+        Err(EndCode::UNDEFINED_COMMAND)
+    }
+
+    fn fal_message_read(
+        &mut self,
+        _header: &Header,
+        _command: FalMessageReadRequest,
+    ) -> Result<FalMessageReadResponse, EndCode> {
+        Err(EndCode::UNDEFINED_COMMAND)
+    }
+
+    fn loopback_test(
+        &mut self,
+        _header: &Header,
+        command: LoopbackTestRequest,
+    ) -> Result<LoopbackTestResponse, EndCode> {
+        Ok(LoopbackTestResponse {
+            bytes: command.bytes,
+        })
+    }
+
+    fn broadcast_test_results_read(
+        &mut self,
+        _header: &Header,
+        _command: BroadcastTestResultsReadRequest,
+    ) -> Result<BroadcastTestResultsReadResponse, EndCode> {
+        Err(EndCode::UNDEFINED_COMMAND)
+    }
+
+    /// No response is sent for this command, the end code is only logged.
+    fn broadcast_test_data_send(
+        &mut self,
+        _header: &Header,
+        _command: BroadcastTestDataSendRequest,
+    ) -> Result<(), EndCode> {
+        Err(EndCode::UNDEFINED_COMMAND)
+    }
+
+    fn cycle_time_read(
+        &mut self,
+        _header: &Header,
+        _command: CycleTimeReadRequest,
+    ) -> Result<CycleTimeReadResponse, EndCode> {
+        Err(EndCode::UNDEFINED_COMMAND)
+    }
+
+    fn cycle_time_initialize(
+        &mut self,
+        _header: &Header,
+        _command: CycleTimeInitializeRequest,
+    ) -> Result<(), EndCode> {
+        Err(EndCode::UNDEFINED_COMMAND)
+    }
+
+    fn connection_data_read(
+        &mut self,
+        _header: &Header,
+        _command: ConnectionDataReadRequest,
+    ) -> Result<ConnectionDataReadResponse, EndCode> {
+        Err(EndCode::UNDEFINED_COMMAND)
+    }
+
+    fn clock_read(
+        &mut self,
+        _header: &Header,
+        _command: ClockReadRequest,
+    ) -> Result<Clock, EndCode> {
+        Err(EndCode::UNDEFINED_COMMAND)
+    }
+
+    fn clock_write(
+        &mut self,
+        _header: &Header,
+        _command: ClockWriteRequest,
+    ) -> Result<(), EndCode> {
+        Err(EndCode::UNDEFINED_COMMAND)
+    }
+
+    fn name_set(&mut self, _header: &Header, _command: NameSetRequest) -> Result<(), EndCode> {
+        Err(EndCode::UNDEFINED_COMMAND)
+    }
+
+    fn name_delete(
+        &mut self,
+        _header: &Header,
+        _command: NameDeleteRequest,
+    ) -> Result<(), EndCode> {
+        Err(EndCode::UNDEFINED_COMMAND)
+    }
+
+    fn name_read(
+        &mut self,
+        _header: &Header,
+        _command: NameReadRequest,
+    ) -> Result<NameReadResponse, EndCode> {
+        Err(EndCode::UNDEFINED_COMMAND)
+    }
+}
+
+/// Decodes the request `frame`, executes it with `handler` and returns the response frame, if the
+/// request requires one. Commands that can not be decoded are answered with an end code instead of
+/// reaching the handler.
+pub fn dispatch<H: Handler + ?Sized>(
+    handler: &mut H,
+    frame: &[u8],
+) -> fins::Result<Option<Vec<u8>>> {
+    let request = Request::from_bytes(frame)?;
+    let header = &request.header;
+    let mut output = Vec::new();
+    let out = &mut output;
+
+    if let Err(end_code) = handler.intercept(&request) {
+        write_response_header(out, &request, end_code)?;
+        return Ok(response_if_required(&request, output));
+    }
+
+    match request.command_code {
+        [0x01, 0x01] => respond(out, &request, |c| handler.memory_area_read(header, c)),
+        [0x01, 0x02] => respond(out, &request, |c| handler.memory_area_write(header, c)),
+        [0x01, 0x03] => respond(out, &request, |c| handler.memory_area_fill(header, c)),
+        [0x01, 0x04] => respond(out, &request, |c| {
+            handler.multiple_memory_area_read(header, c)
+        }),
+        [0x01, 0x05] => respond(out, &request, |c| handler.memory_area_transfer(header, c)),
+        [0x02, 0x01] => respond(out, &request, |c| handler.parameter_area_read(header, c)),
+        [0x02, 0x02] => respond(out, &request, |c| handler.parameter_area_write(header, c)),
+        [0x02, 0x03] => respond(out, &request, |c| handler.parameter_area_clear(header, c)),
+        [0x05, 0x01] => respond(out, &request, |c| handler.controller_data_read(header, c)),
+        [0x05, 0x02] => respond(out, &request, |c| handler.connection_data_read(header, c)),
+        [0x06, 0x01] => respond(out, &request, |c| handler.controller_status_read(header, c)),
+        [0x06, 0x20] => match request.body.first() {
+            Some(0x00) => respond(out, &request, |c| handler.cycle_time_initialize(header, c)),
+            _ => respond(out, &request, |c| handler.cycle_time_read(header, c)),
+        },
+        [0x07, 0x01] => respond(out, &request, |c| handler.clock_read(header, c)),
+        [0x07, 0x02] => respond(out, &request, |c| handler.clock_write(header, c)),
+        [0x08, 0x01] => respond(out, &request, |c| handler.loopback_test(header, c)),
+        [0x08, 0x02] => respond(out, &request, |c| {
+            handler.broadcast_test_results_read(header, c)
+        }),
+        [0x08, 0x03] => respond(out, &request, |c| {
+            handler.broadcast_test_data_send(header, c)
+        }),
+        [0x09, 0x20] => match request.body.first().map(|b| b & 0xC0) {
+            Some(0x40) => respond(out, &request, |c| handler.message_clear(header, c)),
+            Some(0x80) => respond(out, &request, |c| handler.fal_message_read(header, c)),
+            _ => respond(out, &request, |c| handler.message_read(header, c)),
+        },
+        [0x26, 0x01] => respond(out, &request, |c| handler.name_set(header, c)),
+        [0x26, 0x02] => respond(out, &request, |c| handler.name_delete(header, c)),
+        [0x26, 0x03] => respond(out, &request, |c| handler.name_read(header, c)),
+        _ => write_response_header(out, &request, EndCode::UNDEFINED_COMMAND),
+    }?;
+
+    Ok(response_if_required(&request, output))
+}
+
+fn response_if_required(request: &Request, response: Vec<u8>) -> Option<Vec<u8>> {
+    if request.header.icf.requires_response() {
+        Some(response)
+    } else {
+        None
+    }
+}
+
+fn respond<C, F>(output: &mut Vec<u8>, request: &Request, execute: F) -> fins::Result<()>
+where
+    C: Command,
+    F: FnOnce(C) -> Result<C::Response, EndCode>,
+{
+    let response = match request.decode::<C>() {
+        Ok(command) => execute(command),
+        Err(error) => {
+            tracing::warn!("failed to decode command: {}", error);
+            Err(end_code_for(&error))
+        }
+    };
+    let start = output.len();
+    match write_response::<_, C>(output, request, &response) {
+        Err(Error::InvalidValue(error)) => {
+            tracing::warn!("failed to encode response: {}", error);
+            output.truncate(start);
+            write_response_header(output, request, EndCode::MEMORY_ERROR)
+        }
+        result => result,
+    }
+}
+
+fn end_code_for(error: &ProtocolViolation) -> EndCode {
+    match error {
+        ProtocolViolation::BodyTooShort { .. } => EndCode::COMMAND_TOO_SHORT,
+        ProtocolViolation::UnexpectedBodyLength { actual, expected } if actual > expected => {
+            EndCode::COMMAND_TOO_LONG
+        }
+        ProtocolViolation::UnexpectedBodyLength { .. } => EndCode::COMMAND_TOO_SHORT,
+        ProtocolViolation::InvalidMemoryAreaCode(_) => EndCode::NO_AREA_TYPE,
+        ProtocolViolation::InvalidParameterAreaCode(_)
+        | ProtocolViolation::InvalidMessageParameter(_)
+        | ProtocolViolation::InvalidCycleTimeParameter(_)
+        | ProtocolViolation::InvalidControllerDataParameter(_) => EndCode::INCORRECT_PARAMETER_CODE,
+        _ => EndCode::COMMAND_FORMAT_ERROR,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MemoryHandler;
+
+    fn handler(name: &str) -> MemoryHandler {
+        let mut handler = MemoryHandler::default();
+        handler.set_name(name);
+        handler
+    }
+
+    fn request_frame<C: Command>(command: &C) -> Vec<u8> {
+        let mut frame = vec![];
+        write_request(&mut frame, &Route::local(0x01, 0x02), 0x2A, command).unwrap();
+        frame
+    }
+
+    #[test]
+    fn dispatch_works() {
+        let response = dispatch(&mut handler("SIM"), &request_frame(&NameReadRequest))
+            .unwrap()
+            .unwrap();
+        let response = read_response(&NameReadRequest, &response).unwrap();
+        assert_eq!(response.header.sid, 0x2A);
+        assert_eq!(response.header.destination, MachineAddress::cpu(0, 0x02));
+        assert_eq!(response.header.source, MachineAddress::cpu(0, 0x01));
+        assert_eq!(response.body.name, "SIM");
+
+        let response = dispatch(&mut handler("SIM"), &request_frame(&NameDeleteRequest))
+            .unwrap()
+            .unwrap();
+        assert!(matches!(
+            read_response(&NameDeleteRequest, &response),
+            Err(Error::EndCode(EndCode::UNDEFINED_COMMAND))
+        ));
+    }
+
+    #[test]
+    fn dispatch_rejects_malformed_commands() {
+        let mut frame = request_frame(&NameReadRequest);
+        frame.push(0x00);
+        let response = dispatch(&mut handler("SIM"), &frame).unwrap().unwrap();
+        assert!(matches!(
+            read_response(&NameReadRequest, &response),
+            Err(Error::EndCode(EndCode::COMMAND_TOO_LONG))
+        ));
+    }
+
+    #[test]
+    fn dispatch_answers_invalid_responses_with_end_code() {
+        let frame = request_frame(&NameReadRequest);
+        let response = dispatch(&mut handler("TOO LONG NAME"), &frame)
+            .unwrap()
+            .unwrap();
+        assert!(matches!(
+            read_response(&NameReadRequest, &response),
+            Err(Error::EndCode(EndCode::MEMORY_ERROR))
+        ));
+    }
+
+    #[test]
+    fn dispatch_does_not_respond_to_broadcasts() {
+        let frame = request_frame(&BroadcastTestDataSendRequest {
+            bytes: vec![1, 2, 3],
+        });
+        assert!(dispatch(&mut handler("SIM"), &frame).unwrap().is_none());
+    }
+}
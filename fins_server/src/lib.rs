@@ -0,0 +1,9 @@
+mod handler;
+mod memory;
+mod server;
+mod udp_server;
+
+pub use handler::*;
+pub use memory::*;
+pub use server::*;
+pub use udp_server::*;
@@ -0,0 +1,347 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use fins::*;
+
+use crate::Handler;
+
+/// The words of the simulated memory areas. Areas are allocated when first accessed.
+#[derive(Debug, Default)]
+pub struct Memory {
+    areas: HashMap<MemoryAreaCode, Vec<u16>>,
+}
+
+impl Memory {
+    /// Largest number of items a memory area read returns, so that the response fits in a
+    /// single frame.
+    pub const MAX_READ_COUNT: u16 = 999;
+
+    /// The words of the area that `area` accesses.
+    pub fn area(&mut self, area: MemoryAreaCode) -> &mut [u16] {
+        self.areas
+            .entry(area.word_area())
+            .or_insert_with(|| vec![0; area.word_count()])
+    }
+
+    /// The range of words accessed by `count` items at `address`.
+    fn range(address: MemoryAddress, count: usize) -> Result<std::ops::Range<usize>, EndCode> {
+        let size = address.area_code.word_count();
+        let start = address.offset as usize;
+        if start >= size
+            || address.bits > 15
+            || (!address.area_code.is_bit_area() && address.bits != 0)
+        {
+            return Err(EndCode::ADDRESS_OUT_OF_RANGE);
+        }
+        let end = if address.area_code.is_bit_area() {
+            start + (address.bits as usize + count).div_ceil(16)
+        } else {
+            start + count
+        };
+        if end > size {
+            return Err(EndCode::ADDRESS_RANGE_EXCEEDED);
+        }
+        Ok(start..end)
+    }
+
+    /// Reads `count` items like a memory area read, at most [`Memory::MAX_READ_COUNT`].
+    pub fn read(&mut self, address: MemoryAddress, count: u16) -> Result<Vec<u8>, EndCode> {
+        if count > Self::MAX_READ_COUNT {
+            return Err(EndCode::COMMAND_TOO_LONG);
+        }
+        self.get(address, count)
+    }
+
+    fn get(&mut self, address: MemoryAddress, count: u16) -> Result<Vec<u8>, EndCode> {
+        let words = Self::range(address, count as usize)?;
+        let area = &self.area(address.area_code)[words];
+        if address.area_code.is_bit_area() {
+            Ok((0..count as usize)
+                .map(|i| {
+                    let bit = address.bits as usize + i;
+                    (area[bit / 16] >> (bit % 16)) as u8 & 1
+                })
+                .collect())
+        } else {
+            Ok(area.iter().flat_map(|word| word.to_be_bytes()).collect())
+        }
+    }
+
+    pub fn write(&mut self, address: MemoryAddress, bytes: &[u8]) -> Result<(), EndCode> {
+        let count = bytes.len() / address.area_code.item_size();
+        let words = Self::range(address, count)?;
+        let area = &mut self.area(address.area_code)[words];
+        if address.area_code.is_bit_area() {
+            for (i, &value) in bytes.iter().enumerate() {
+                let bit = address.bits as usize + i;
+                let mask = 1 << (bit % 16);
+                if value & 1 != 0 {
+                    area[bit / 16] |= mask;
+                } else {
+                    area[bit / 16] &= !mask;
+                }
+            }
+        } else {
+            for (word, bytes) in area.iter_mut().zip(bytes.chunks_exact(2)) {
+                *word = u16::from_be_bytes([bytes[0], bytes[1]]);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn fill(&mut self, address: MemoryAddress, count: u16, word: u16) -> Result<(), EndCode> {
+        if address.area_code.is_bit_area() {
+            return Err(EndCode::NO_AREA_TYPE);
+        }
+        let words = Self::range(address, count as usize)?;
+        for value in &mut self.area(address.area_code)[words] {
+            *value = word;
+        }
+        Ok(())
+    }
+
+    pub fn transfer(
+        &mut self,
+        source: MemoryAddress,
+        destination: MemoryAddress,
+        count: u16,
+    ) -> Result<(), EndCode> {
+        if source.area_code.is_bit_area() || destination.area_code.is_bit_area() {
+            return Err(EndCode::NO_AREA_TYPE);
+        }
+        let bytes = self.get(source, count)?;
+        self.write(destination, &bytes)
+    }
+
+    /// Loads words from `text`. Every line holds an address followed by the values of consecutive
+    /// words in decimal or `0x` prefixed hexadecimal, `#` starts a comment:
+    ///
+    /// ```text
+    /// # Recipe
+    /// D100 1 2 0x00FF
+    /// E2_0 42
+    /// ```
+    pub fn load(&mut self, text: &str) -> Result<(), String> {
+        for (index, line) in text.lines().enumerate() {
+            let error = |message: String| format!("line {}: {}", index + 1, message);
+            let mut fields = line.split('#').next().unwrap().split_whitespace();
+            let address = match fields.next() {
+                Some(address) => address
+                    .parse::<MemoryAddress>()
+                    .map_err(|e| error(e.to_string()))?,
+                None => continue,
+            };
+            if address.area_code.is_bit_area() {
+                return Err(error(format!("{:?} is not a word address", address)));
+            }
+            let bytes = fields
+                .map(|value| {
+                    let word = match value.strip_prefix("0x") {
+                        Some(hex) => u16::from_str_radix(hex, 16),
+                        None => value.parse::<u16>(),
+                    };
+                    word.map(u16::to_be_bytes)
+                        .map_err(|_| error(format!("invalid word {:?}", value)))
+                })
+                .collect::<Result<Vec<_>, _>>()?
+                .concat();
+            self.write(address, &bytes)
+                .map_err(|end_code| error(end_code.to_string()))?;
+        }
+        Ok(())
+    }
+}
+
+/// An access recorded by a [`MemoryHandler`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryAccess {
+    pub write: bool,
+    pub address: MemoryAddress,
+    pub count: u16,
+}
+
+/// Executes memory area commands on a shared [`Memory`] and records the reads and writes, for
+/// tests against a real server. Clones share the memory and the record.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryHandler {
+    memory: Arc<Mutex<Memory>>,
+    accesses: Arc<Mutex<Vec<MemoryAccess>>>,
+    name: String,
+    max_count: Option<u16>,
+    words_only: bool,
+}
+
+impl MemoryHandler {
+    pub fn memory(&self) -> MutexGuard<'_, Memory> {
+        self.memory.lock().unwrap()
+    }
+
+    pub fn accesses(&self) -> MutexGuard<'_, Vec<MemoryAccess>> {
+        self.accesses.lock().unwrap()
+    }
+
+    /// Sets the name returned by the name read.
+    pub fn set_name(&mut self, name: impl Into<String>) {
+        self.name = name.into();
+    }
+
+    /// Reads and writes of more than `max_count` items respond with
+    /// [`EndCode::COMMAND_TOO_LONG`].
+    pub fn set_max_count(&mut self, max_count: u16) {
+        self.max_count = Some(max_count);
+    }
+
+    /// Bit areas respond with [`EndCode::NO_AREA_TYPE`], like units that only access words.
+    pub fn set_words_only(&mut self, words_only: bool) {
+        self.words_only = words_only;
+    }
+
+    fn access(&self, write: bool, address: MemoryAddress, count: u16) -> Result<(), EndCode> {
+        self.accesses().push(MemoryAccess {
+            write,
+            address,
+            count,
+        });
+        if self.words_only && address.area_code.is_bit_area() {
+            return Err(EndCode::NO_AREA_TYPE);
+        }
+        match self.max_count {
+            Some(max_count) if count > max_count => Err(EndCode::COMMAND_TOO_LONG),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Handler for MemoryHandler {
+    fn memory_area_read(
+        &mut self,
+        _header: &Header,
+        command: MemoryAreaReadRequest,
+    ) -> Result<Vec<u8>, EndCode> {
+        self.access(false, command.address, command.count)?;
+        self.memory().read(command.address, command.count)
+    }
+
+    fn memory_area_write(
+        &mut self,
+        _header: &Header,
+        command: MemoryAreaWriteRequest,
+    ) -> Result<(), EndCode> {
+        self.access(true, command.address, command.count())?;
+        self.memory().write(command.address, &command.bytes)
+    }
+
+    fn memory_area_fill(
+        &mut self,
+        _header: &Header,
+        command: MemoryAreaFillRequest,
+    ) -> Result<(), EndCode> {
+        self.memory()
+            .fill(command.address, command.count, command.word)
+    }
+
+    fn memory_area_transfer(
+        &mut self,
+        _header: &Header,
+        command: MemoryAreaTransferRequest,
+    ) -> Result<(), EndCode> {
+        self.memory()
+            .transfer(command.source, command.destination, command.count)
+    }
+
+    fn name_read(
+        &mut self,
+        _header: &Header,
+        _command: NameReadRequest,
+    ) -> Result<NameReadResponse, EndCode> {
+        Ok(NameReadResponse {
+            name: self.name.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn address(text: &str) -> MemoryAddress {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn read_write_works() {
+        let mut memory = Memory::default();
+        memory
+            .write(address("D100"), &[0x12, 0x34, 0x56, 0x78])
+            .unwrap();
+        assert_eq!(
+            memory.read(address("D99"), 3).unwrap(),
+            [0x00, 0x00, 0x12, 0x34, 0x56, 0x78]
+        );
+
+        memory.write(address("D100.15"), &[0, 1]).unwrap();
+        assert_eq!(
+            memory.read(address("D100"), 2).unwrap(),
+            [0x12, 0x34, 0x56, 0x79]
+        );
+        assert_eq!(memory.read(address("D100.14"), 3).unwrap(), [0, 0, 1]);
+    }
+
+    #[test]
+    fn checks_ranges() {
+        let mut memory = Memory::default();
+        assert_eq!(
+            memory.read(address("W512"), 1),
+            Err(EndCode::ADDRESS_OUT_OF_RANGE)
+        );
+        assert_eq!(
+            memory.read(address("W511"), 2),
+            Err(EndCode::ADDRESS_RANGE_EXCEEDED)
+        );
+        assert_eq!(memory.read(address("W511.15"), 1).unwrap(), [0]);
+        assert_eq!(
+            memory.read(address("W511.15"), 2),
+            Err(EndCode::ADDRESS_RANGE_EXCEEDED)
+        );
+        assert_eq!(
+            memory.fill(address("W0.00"), 1, 0),
+            Err(EndCode::NO_AREA_TYPE)
+        );
+
+        // Responses have to fit in a frame.
+        assert_eq!(memory.read(address("D0"), 999).unwrap().len(), 1998);
+        assert_eq!(
+            memory.read(address("D0"), 2000),
+            Err(EndCode::COMMAND_TOO_LONG)
+        );
+    }
+
+    #[test]
+    fn fill_and_transfer_work() {
+        let mut memory = Memory::default();
+        memory.fill(address("H10"), 3, 0xBEEF).unwrap();
+        memory.transfer(address("H11"), address("E3_0"), 2).unwrap();
+        memory
+            .transfer(address("D0"), address("D1000"), 2000)
+            .unwrap();
+        assert_eq!(
+            memory.read(address("E3_0"), 3).unwrap(),
+            [0xBE, 0xEF, 0xBE, 0xEF, 0x00, 0x00]
+        );
+    }
+
+    #[test]
+    fn load_works() {
+        let mut memory = Memory::default();
+        memory
+            .load("# Recipe\nD100 1 0x00FF # speed\n\nCIO0 65535\n")
+            .unwrap();
+        assert_eq!(memory.read(address("D100"), 2).unwrap(), [0, 1, 0, 0xFF]);
+        assert_eq!(memory.read(address("CIO0.15"), 1).unwrap(), [1]);
+
+        assert_eq!(
+            memory.load("D100 1\nD101 x"),
+            Err("line 2: invalid word \"x\"".to_string())
+        );
+    }
+}
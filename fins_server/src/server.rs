@@ -0,0 +1,321 @@
+use std::collections::HashSet;
+use std::io::{Cursor, ErrorKind};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use fins_tcp::{ClientAddressFrame, CommandCode, ServerAddressFrame};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream, ToSocketAddrs};
+use tracing::{info, warn};
+
+use crate::{dispatch, Handler};
+
+/// FINS/TCP error code for a client that requests the node of another client.
+const NODE_IN_USE: u32 = 0x21;
+
+/// FINS/TCP error code for a client that requests the node of the server.
+const SAME_NODE: u32 = 0x24;
+
+/// FINS/TCP error code for a client that requests node 0 when every node is in use.
+const ALL_NODES_IN_USE: u32 = 0x25;
+
+/// A FINS/TCP server that executes the commands it receives with a [`Handler`].
+///
+/// The handler is cloned for every connection, state shared between connections should be kept
+/// behind an `Arc`.
+pub struct Server<H> {
+    listener: TcpListener,
+    server_node: u8,
+    handler: H,
+    response_delay: Duration,
+    nodes: Arc<Mutex<Nodes>>,
+}
+
+impl<H> Server<H>
+where
+    H: Handler + Clone + Send + 'static,
+{
+    /// Listens on `addr` as node `server_node`.
+    pub async fn bind<A: ToSocketAddrs>(
+        addr: A,
+        server_node: u8,
+        handler: H,
+    ) -> std::io::Result<Self> {
+        Ok(Self {
+            listener: TcpListener::bind(addr).await?,
+            server_node,
+            handler,
+            response_delay: Duration::ZERO,
+            nodes: Arc::default(),
+        })
+    }
+
+    pub fn local_addr(&self) -> std::io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Delays every response by `delay` to simulate the processing time of a PLC. Requests on a
+    /// connection are processed one at a time so the delays of pipelined requests add up.
+    pub fn set_response_delay(&mut self, delay: Duration) {
+        self.response_delay = delay;
+    }
+
+    /// Accepts connections until accepting fails. Every connection is served by its own task.
+    pub async fn run(self) -> std::io::Result<()> {
+        loop {
+            let (stream, peer_addr) = self.listener.accept().await?;
+            info!("connection accepted from {}", peer_addr);
+
+            let connection = Connection {
+                stream,
+                server_node: self.server_node,
+                client_node: 0,
+                handler: self.handler.clone(),
+                response_delay: self.response_delay,
+                nodes: self.nodes.clone(),
+            };
+
+            tokio::spawn(async move {
+                if let Err(error) = connection.run().await {
+                    warn!("connection with {} failed: {}", peer_addr, error);
+                }
+                info!("connection with {} closed", peer_addr);
+            });
+        }
+    }
+}
+
+/// The node after `node` in 1 to 254, skipping `server_node`.
+fn next_node(node: u8, server_node: u8) -> u8 {
+    let mut node = node;
+    loop {
+        node = node % 254 + 1;
+        if node != server_node {
+            return node;
+        }
+    }
+}
+
+/// The client nodes of the open connections.
+#[derive(Debug, Default)]
+struct Nodes {
+    in_use: HashSet<u8>,
+    /// The node last assigned to a client that requested node 0.
+    last_assigned: u8,
+}
+
+impl Nodes {
+    /// Reserves `requested`, or the next free node when `requested` is 0. Fails with the
+    /// FINS/TCP error code for the server to respond with.
+    fn reserve(&mut self, requested: u8, server_node: u8) -> Result<u8, u32> {
+        let node = if requested == 0 {
+            let node = (0..254)
+                .scan(self.last_assigned, |node, _| {
+                    *node = next_node(*node, server_node);
+                    Some(*node)
+                })
+                .find(|node| !self.in_use.contains(node))
+                .ok_or(ALL_NODES_IN_USE)?;
+            self.last_assigned = node;
+            node
+        } else if requested == server_node {
+            return Err(SAME_NODE);
+        } else if self.in_use.contains(&requested) {
+            return Err(NODE_IN_USE);
+        } else {
+            requested
+        };
+        self.in_use.insert(node);
+        Ok(node)
+    }
+}
+
+struct Connection<H> {
+    stream: TcpStream,
+    server_node: u8,
+    client_node: u8,
+    handler: H,
+    response_delay: Duration,
+    nodes: Arc<Mutex<Nodes>>,
+}
+
+impl<H: Handler> Connection<H> {
+    async fn run(mut self) -> fins_tcp::Result<()> {
+        self.stream.set_nodelay(true)?;
+
+        // Exchange nodes. A client that requests node 0 is assigned one.
+        let frame = match read_tcp_frame(&mut self.stream).await? {
+            Some(frame) => frame,
+            None => return Ok(()),
+        };
+        let ClientAddressFrame { client_node } =
+            ClientAddressFrame::read_from(&mut Cursor::new(&frame))?;
+        let reserved = self
+            .nodes
+            .lock()
+            .unwrap()
+            .reserve(client_node, self.server_node);
+        self.client_node = match reserved {
+            Ok(node) => node,
+            Err(error_code) => {
+                warn!("rejected client node {}", client_node);
+                return self.reject(error_code).await;
+            }
+        };
+
+        let result = self.serve().await;
+        self.nodes.lock().unwrap().in_use.remove(&self.client_node);
+        result
+    }
+
+    /// Responds to the client address frame with `error_code` and closes the connection.
+    async fn reject(mut self, error_code: u32) -> fins_tcp::Result<()> {
+        let mut output = Vec::new();
+        fins_tcp::Header {
+            length: 16,
+            command: CommandCode::ServerAddress,
+            error_code,
+        }
+        .write_to(&mut output)?;
+        output.extend([0; 8]);
+        self.stream.write_all(&output).await?;
+        Ok(())
+    }
+
+    async fn serve(&mut self) -> fins_tcp::Result<()> {
+        let mut output = Vec::new();
+        ServerAddressFrame {
+            client_node: self.client_node,
+            server_node: self.server_node,
+        }
+        .write_to(&mut output)?;
+        self.stream.write_all(&output).await?;
+
+        info!(
+            "client node {}, server node {}",
+            self.client_node, self.server_node
+        );
+
+        while let Some(frame) = read_tcp_frame(&mut self.stream).await? {
+            let request = fins_tcp::read_fins_frame(&mut Cursor::new(&frame))?;
+            let response = dispatch(&mut self.handler, &request)?;
+            delay(self.response_delay).await;
+            if let Some(response) = response {
+                output.clear();
+                fins_tcp::write_fins_frame(&mut output, &response)?;
+                self.stream.write_all(&output).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub(crate) async fn delay(duration: Duration) {
+    if !duration.is_zero() {
+        tokio::time::sleep(duration).await;
+    }
+}
+
+/// Reads a complete FINS/TCP frame, including its header. Returns `None` when the connection is
+/// closed before the frame starts.
+async fn read_tcp_frame(stream: &mut TcpStream) -> fins_tcp::Result<Option<Vec<u8>>> {
+    let header_size = std::mem::size_of::<fins_tcp::RawHeader>();
+    let mut frame = vec![0; header_size];
+    match stream.read_exact(&mut frame).await {
+        Ok(_) => {}
+        Err(error) if error.kind() == ErrorKind::UnexpectedEof => return Ok(None),
+        Err(error) => return Err(error.into()),
+    }
+
+    let header = fins_tcp::Header::read_from(&mut Cursor::new(&frame))?;
+    let body_size = header.body_size()?;
+    frame.resize(header_size + body_size, 0);
+    stream.read_exact(&mut frame[header_size..]).await?;
+
+    Ok(Some(frame))
+}
+
+#[cfg(test)]
+mod tests {
+    use fins::{LoopbackTestRequest, NameReadRequest};
+    use fins_client::Client;
+
+    use super::*;
+    use crate::MemoryHandler;
+
+    fn handler() -> MemoryHandler {
+        let mut handler = MemoryHandler::default();
+        handler.set_name("SIM");
+        handler
+    }
+
+    #[test]
+    fn next_node_works() {
+        assert_eq!(next_node(0, 0x01), 0x02);
+        assert_eq!(next_node(0x10, 0x01), 0x11);
+        assert_eq!(next_node(254, 0x01), 0x02);
+    }
+
+    #[test]
+    fn reserve_works() {
+        let mut nodes = Nodes::default();
+        assert_eq!(nodes.reserve(0, 0x01), Ok(0x02));
+        assert_eq!(nodes.reserve(0x03, 0x01), Ok(0x03));
+        assert_eq!(nodes.reserve(0, 0x01), Ok(0x04));
+        assert_eq!(nodes.reserve(0x03, 0x01), Err(NODE_IN_USE));
+        assert_eq!(nodes.reserve(0x01, 0x01), Err(SAME_NODE));
+
+        nodes.in_use.remove(&0x03);
+        assert_eq!(nodes.reserve(0x03, 0x01), Ok(0x03));
+
+        nodes.in_use.extend(1..=254);
+        assert_eq!(nodes.reserve(0, 0x01), Err(ALL_NODES_IN_USE));
+    }
+
+    #[tokio::test]
+    async fn rejects_oversized_frames() {
+        let server = Server::bind("127.0.0.1:0", 0x01, handler()).await.unwrap();
+        let addr = server.local_addr().unwrap();
+        tokio::spawn(server.run());
+
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        let mut frame = Vec::new();
+        fins_tcp::Header {
+            length: u32::MAX,
+            command: CommandCode::ClientAddress,
+            error_code: 0,
+        }
+        .write_to(&mut frame)
+        .unwrap();
+        stream.write_all(&frame).await.unwrap();
+
+        // The server closes the connection instead of waiting for 4 GiB.
+        let mut buffer = [0; 16];
+        assert_eq!(stream.read(&mut buffer).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn serves_client() {
+        let server = Server::bind("127.0.0.1:0", 0x01, handler()).await.unwrap();
+        let addr = server.local_addr().unwrap();
+        tokio::spawn(server.run());
+
+        let client = Client::connect(addr).await.unwrap();
+        assert_eq!(client.route().destination.node, 0x01);
+        assert_eq!(client.route().source.node, 0x02);
+
+        let name = client.execute(&NameReadRequest).await.unwrap();
+        assert_eq!(name.name, "SIM");
+
+        let bytes = vec![1, 2, 3, 4];
+        let echo = client
+            .execute(&LoopbackTestRequest {
+                bytes: bytes.clone(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(echo.bytes, bytes);
+    }
+}
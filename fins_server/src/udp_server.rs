@@ -0,0 +1,121 @@
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use fins_tcp::MAX_FINS_FRAME_SIZE;
+use tokio::net::{ToSocketAddrs, UdpSocket};
+use tracing::warn;
+
+use crate::server::delay;
+use crate::{dispatch, Handler};
+
+/// A FINS/UDP server that executes the commands it receives with a [`Handler`]. Every datagram
+/// carries one FINS frame, so unlike FINS/TCP there is no node exchange.
+pub struct UdpServer<H> {
+    socket: UdpSocket,
+    handler: H,
+    response_delay: Duration,
+}
+
+impl<H: Handler> UdpServer<H> {
+    pub async fn bind<A: ToSocketAddrs>(addr: A, handler: H) -> std::io::Result<Self> {
+        Ok(Self {
+            socket: UdpSocket::bind(addr).await?,
+            handler,
+            response_delay: Duration::ZERO,
+        })
+    }
+
+    pub fn local_addr(&self) -> std::io::Result<SocketAddr> {
+        self.socket.local_addr()
+    }
+
+    /// Delays every response by `delay`, see [`crate::Server::set_response_delay`].
+    pub fn set_response_delay(&mut self, delay: Duration) {
+        self.response_delay = delay;
+    }
+
+    /// Serves requests one at a time until receiving fails. Malformed and oversized frames are
+    /// dropped.
+    pub async fn run(mut self) -> std::io::Result<()> {
+        // One byte more than the largest frame tells oversized datagrams from truncated ones.
+        let mut buffer = vec![0; MAX_FINS_FRAME_SIZE + 1];
+        loop {
+            let (size, peer_addr) = self.socket.recv_from(&mut buffer).await?;
+            if size > MAX_FINS_FRAME_SIZE {
+                warn!("dropped oversized frame from {}", peer_addr);
+                continue;
+            }
+            let response = match dispatch(&mut self.handler, &buffer[..size]) {
+                Ok(response) => response,
+                Err(error) => {
+                    warn!("dropped frame from {}: {}", peer_addr, error);
+                    continue;
+                }
+            };
+            delay(self.response_delay).await;
+            if let Some(response) = response {
+                self.socket.send_to(&response, peer_addr).await?;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fins::{read_response, write_request, LoopbackTestRequest, Route};
+
+    use super::*;
+    use crate::MemoryHandler;
+
+    #[tokio::test]
+    async fn serves_datagrams() {
+        let server = UdpServer::bind("127.0.0.1:0", MemoryHandler::default())
+            .await
+            .unwrap();
+        let addr = server.local_addr().unwrap();
+        tokio::spawn(server.run());
+
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let command = LoopbackTestRequest {
+            bytes: vec![1, 2, 3],
+        };
+        let mut frame = vec![];
+        write_request(&mut frame, &Route::local(0x01, 0x02), 0x07, &command).unwrap();
+        socket.send_to(&frame, addr).await.unwrap();
+
+        let mut buffer = vec![0; MAX_FINS_FRAME_SIZE];
+        let size = socket.recv(&mut buffer).await.unwrap();
+        let response = read_response(&command, &buffer[..size]).unwrap();
+        assert_eq!(response.header.sid, 0x07);
+        assert_eq!(response.body.bytes, [1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn drops_oversized_datagrams() {
+        let server = UdpServer::bind("127.0.0.1:0", MemoryHandler::default())
+            .await
+            .unwrap();
+        let addr = server.local_addr().unwrap();
+        tokio::spawn(server.run());
+
+        // Truncated to the largest frame, this would be a valid loopback test.
+        let socket = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let command = LoopbackTestRequest {
+            bytes: vec![0; LoopbackTestRequest::MAX_BYTE_COUNT],
+        };
+        let mut frame = vec![];
+        write_request(&mut frame, &Route::local(0x01, 0x02), 0x06, &command).unwrap();
+        frame.resize(MAX_FINS_FRAME_SIZE + 1, 0);
+        socket.send_to(&frame, addr).await.unwrap();
+
+        let command = LoopbackTestRequest { bytes: vec![1] };
+        let mut frame = vec![];
+        write_request(&mut frame, &Route::local(0x01, 0x02), 0x07, &command).unwrap();
+        socket.send_to(&frame, addr).await.unwrap();
+
+        let mut buffer = vec![0; MAX_FINS_FRAME_SIZE];
+        let size = socket.recv(&mut buffer).await.unwrap();
+        let response = read_response(&command, &buffer[..size]).unwrap();
+        assert_eq!(response.header.sid, 0x07);
+    }
+}
@@ -0,0 +1,151 @@
+mod simulator;
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use fins::EndCode;
+use fins_server::{Memory, Server, UdpServer};
+use tracing::info;
+
+pub use simulator::*;
+
+const USAGE: &str = "\
+Usage: fins_simulator [OPTIONS]
+
+Options:
+    --tcp <ADDR>            FINS/TCP address to listen on [default: 0.0.0.0:9600]
+    --udp <ADDR>            FINS/UDP address to listen on [default: 0.0.0.0:9600]
+    --node <NODE>           Node of the simulated PLC [default: 1]
+    --load <FILE>           Preload memory, see below
+    --delay <MS>            Delay every response by MS milliseconds
+    --fail <CODE>=<END>     Respond to command CODE with end code END, like 0101=1103
+
+The preload file holds an address followed by word values on every line:
+
+    # Recipe
+    D100 1 2 0x00FF
+";
+
+struct Options {
+    tcp: String,
+    udp: String,
+    node: u8,
+    load: Option<String>,
+    delay: Duration,
+    faults: HashMap<[u8; 2], EndCode>,
+}
+
+fn parse_options() -> Result<Options, String> {
+    let mut options = Options {
+        tcp: "0.0.0.0:9600".to_string(),
+        udp: "0.0.0.0:9600".to_string(),
+        node: 1,
+        load: None,
+        delay: Duration::ZERO,
+        faults: HashMap::new(),
+    };
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("missing value for {}", arg));
+        match arg.as_str() {
+            "--tcp" => options.tcp = value()?,
+            "--udp" => options.udp = value()?,
+            "--node" => options.node = value()?.parse().map_err(|e| format!("--node: {}", e))?,
+            "--load" => options.load = Some(value()?),
+            "--delay" => {
+                let millis = value()?.parse().map_err(|e| format!("--delay: {}", e))?;
+                options.delay = Duration::from_millis(millis);
+            }
+            "--fail" => {
+                let value = value()?;
+                let (command_code, end_code) = parse_fault(&value)
+                    .ok_or(format!("--fail: expected <CODE>=<END> but got {:?}", value))?;
+                options.faults.insert(command_code, end_code);
+            }
+            "--help" | "-h" => return Err(String::new()),
+            _ => return Err(format!("unknown argument {:?}", arg)),
+        }
+    }
+
+    Ok(options)
+}
+
+/// Parses `0101=1103` into a command code and an end code.
+fn parse_fault(text: &str) -> Option<([u8; 2], EndCode)> {
+    let (command_code, end_code) = text.split_once('=')?;
+    let parse = |text: &str| {
+        if text.len() == 4 {
+            u16::from_str_radix(text, 16).ok().map(u16::to_be_bytes)
+        } else {
+            None
+        }
+    };
+    let [mrc, src] = parse(command_code)?;
+    let [mres, sres] = parse(end_code)?;
+    Some(([mrc, src], EndCode::new(mres, sres)))
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            std::env::var("RUST_LOG")
+                .unwrap_or_else(|_| "fins_simulator=info,fins_server=info".to_string()),
+        )
+        .init();
+
+    let options = match parse_options() {
+        Ok(options) => options,
+        Err(error) => {
+            if !error.is_empty() {
+                eprintln!("error: {}\n", error);
+            }
+            eprint!("{}", USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    let mut memory = Memory::default();
+    if let Some(path) = &options.load {
+        memory
+            .load(&std::fs::read_to_string(path)?)
+            .map_err(|error| format!("{}: {}", path, error))?;
+        info!("loaded memory from {}", path);
+    }
+    let simulator = Simulator::with_faults(memory, options.faults);
+
+    let mut tcp_server = Server::bind(&options.tcp, options.node, simulator.clone()).await?;
+    tcp_server.set_response_delay(options.delay);
+    let mut udp_server = UdpServer::bind(&options.udp, simulator).await?;
+    udp_server.set_response_delay(options.delay);
+    info!(
+        "simulating node {} on tcp://{} and udp://{}",
+        options.node,
+        tcp_server.local_addr()?,
+        udp_server.local_addr()?
+    );
+
+    tokio::select! {
+        result = tcp_server.run() => result?,
+        result = udp_server.run() => result?,
+        _ = tokio::signal::ctrl_c() => {}
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_fault_works() {
+        assert_eq!(
+            parse_fault("0101=1103"),
+            Some(([0x01, 0x01], EndCode::ADDRESS_OUT_OF_RANGE))
+        );
+        assert_eq!(parse_fault("0101"), None);
+        assert_eq!(parse_fault("01=1103"), None);
+    }
+}
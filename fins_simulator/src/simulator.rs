@@ -0,0 +1,386 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use chrono::{Datelike, Local, NaiveDate, NaiveDateTime, Timelike};
+use fins::*;
+use fins_server::{Handler, Memory};
+
+const MODEL: &str = "FINS SIMULATOR";
+
+/// A CPU unit that keeps its memory areas in memory. Clones share the same state so that every
+/// connection sees the same PLC.
+#[derive(Clone, Default)]
+pub struct Simulator {
+    state: Arc<Mutex<State>>,
+    faults: Arc<HashMap<[u8; 2], EndCode>>,
+}
+
+#[derive(Default)]
+struct State {
+    memory: Memory,
+    name: String,
+    /// Seconds between the simulated clock and the local clock.
+    clock_offset: i64,
+    broadcast_reception_count: u16,
+}
+
+impl Simulator {
+    /// A simulator that responds with the given end code to every command with the given command
+    /// code, instead of executing it.
+    pub fn with_faults(memory: Memory, faults: HashMap<[u8; 2], EndCode>) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(State {
+                memory,
+                ..State::default()
+            })),
+            faults: Arc::new(faults),
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap()
+    }
+}
+
+impl Handler for Simulator {
+    fn intercept(&mut self, request: &Request) -> Result<(), EndCode> {
+        match self.faults.get(&request.command_code) {
+            Some(&end_code) => Err(end_code),
+            None => Ok(()),
+        }
+    }
+
+    fn memory_area_read(
+        &mut self,
+        _header: &Header,
+        command: MemoryAreaReadRequest,
+    ) -> Result<Vec<u8>, EndCode> {
+        self.state().memory.read(command.address, command.count)
+    }
+
+    fn memory_area_write(
+        &mut self,
+        _header: &Header,
+        command: MemoryAreaWriteRequest,
+    ) -> Result<(), EndCode> {
+        self.state().memory.write(command.address, &command.bytes)
+    }
+
+    fn memory_area_fill(
+        &mut self,
+        _header: &Header,
+        command: MemoryAreaFillRequest,
+    ) -> Result<(), EndCode> {
+        self.state()
+            .memory
+            .fill(command.address, command.count, command.word)
+    }
+
+    fn memory_area_transfer(
+        &mut self,
+        _header: &Header,
+        command: MemoryAreaTransferRequest,
+    ) -> Result<(), EndCode> {
+        self.state()
+            .memory
+            .transfer(command.source, command.destination, command.count)
+    }
+
+    fn controller_data_read(
+        &mut self,
+        _header: &Header,
+        _command: ControllerDataReadRequest,
+    ) -> Result<ControllerDataReadResponse, EndCode> {
+        Ok(ControllerDataReadResponse {
+            model: MODEL.to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            area_data: [0; 12],
+        })
+    }
+
+    fn controller_status_read(
+        &mut self,
+        _header: &Header,
+        _command: ControllerStatusReadRequest,
+    ) -> Result<ControllerStatusReadResponse, EndCode> {
+        Ok(ControllerStatusReadResponse {
+            status: OperatingStatus::Run,
+            mode: OperatingMode::Monitor,
+            fatal_error: 0,
+            non_fatal_error: 0,
+            messages: 0,
+            fal_number: 0,
+            error_message: String::new(),
+        })
+    }
+
+    fn message_read(
+        &mut self,
+        _header: &Header,
+        command: MessageReadRequest,
+    ) -> Result<MessageReadResponse, EndCode> {
+        Ok(MessageReadResponse {
+            messages: (0..8)
+                .filter(|number| command.messages & (1 << number) != 0)
+                .map(|number| Message {
+                    number,
+                    text: String::new(),
+                })
+                .collect(),
+        })
+    }
+
+    fn message_clear(
+        &mut self,
+        _header: &Header,
+        _command: MessageClearRequest,
+    ) -> Result<(), EndCode> {
+        Ok(())
+    }
+
+    fn fal_message_read(
+        &mut self,
+        _header: &Header,
+        command: FalMessageReadRequest,
+    ) -> Result<FalMessageReadResponse, EndCode> {
+        Ok(FalMessageReadResponse {
+            number: command.number,
+            text: String::new(),
+        })
+    }
+
+    fn broadcast_test_results_read(
+        &mut self,
+        _header: &Header,
+        _command: BroadcastTestResultsReadRequest,
+    ) -> Result<BroadcastTestResultsReadResponse, EndCode> {
+        let mut state = self.state();
+        let reception_count = state.broadcast_reception_count;
+        state.broadcast_reception_count = 0;
+        Ok(BroadcastTestResultsReadResponse { reception_count })
+    }
+
+    fn broadcast_test_data_send(
+        &mut self,
+        _header: &Header,
+        _command: BroadcastTestDataSendRequest,
+    ) -> Result<(), EndCode> {
+        let mut state = self.state();
+        state.broadcast_reception_count = state.broadcast_reception_count.saturating_add(1);
+        Ok(())
+    }
+
+    fn cycle_time_read(
+        &mut self,
+        _header: &Header,
+        _command: CycleTimeReadRequest,
+    ) -> Result<CycleTimeReadResponse, EndCode> {
+        Ok(CycleTimeReadResponse {
+            average: Duration::from_millis(1),
+            max: Duration::from_millis(1),
+            min: Duration::from_millis(1),
+        })
+    }
+
+    fn cycle_time_initialize(
+        &mut self,
+        _header: &Header,
+        _command: CycleTimeInitializeRequest,
+    ) -> Result<(), EndCode> {
+        Ok(())
+    }
+
+    fn connection_data_read(
+        &mut self,
+        _header: &Header,
+        command: ConnectionDataReadRequest,
+    ) -> Result<ConnectionDataReadResponse, EndCode> {
+        let units = if command.unit_address == MachineAddress::CPU_UNIT {
+            vec![UnitModel {
+                unit_address: MachineAddress::CPU_UNIT,
+                model: MODEL.to_string(),
+            }]
+        } else {
+            vec![]
+        };
+        Ok(ConnectionDataReadResponse {
+            units,
+            includes_last_unit: true,
+        })
+    }
+
+    fn clock_read(
+        &mut self,
+        _header: &Header,
+        _command: ClockReadRequest,
+    ) -> Result<Clock, EndCode> {
+        let now = Local::now().naive_local() + chrono::Duration::seconds(self.state().clock_offset);
+        Ok(Clock {
+            year: (now.year() % 100) as u8,
+            month: now.month() as u8,
+            day: now.day() as u8,
+            hour: now.hour() as u8,
+            minute: now.minute() as u8,
+            second: now.second() as u8,
+            day_of_week: now.weekday().num_days_from_sunday() as u8,
+        })
+    }
+
+    fn clock_write(&mut self, _header: &Header, command: ClockWriteRequest) -> Result<(), EndCode> {
+        let Clock {
+            year,
+            month,
+            day,
+            hour,
+            minute,
+            second,
+            ..
+        } = command.clock;
+        let time: NaiveDateTime =
+            NaiveDate::from_ymd_opt(2000 + year as i32, month as u32, day as u32)
+                .and_then(|date| date.and_hms_opt(hour as u32, minute as u32, second as u32))
+                .ok_or(EndCode::INCORRECT_PARAMETER_CODE)?;
+        self.state().clock_offset = (time - Local::now().naive_local()).num_seconds();
+        Ok(())
+    }
+
+    fn name_set(&mut self, _header: &Header, command: NameSetRequest) -> Result<(), EndCode> {
+        self.state().name = command.name;
+        Ok(())
+    }
+
+    fn name_delete(
+        &mut self,
+        _header: &Header,
+        _command: NameDeleteRequest,
+    ) -> Result<(), EndCode> {
+        self.state().name.clear();
+        Ok(())
+    }
+
+    fn name_read(
+        &mut self,
+        _header: &Header,
+        _command: NameReadRequest,
+    ) -> Result<NameReadResponse, EndCode> {
+        Ok(NameReadResponse {
+            name: self.state().name.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use fins_client::Client;
+    use fins_server::Server;
+
+    use super::*;
+
+    async fn connect(simulator: Simulator, response_delay: Duration) -> Client {
+        let mut server = Server::bind("127.0.0.1:0", 0x01, simulator).await.unwrap();
+        server.set_response_delay(response_delay);
+        let addr = server.local_addr().unwrap();
+        tokio::spawn(server.run());
+        Client::connect(addr).await.unwrap()
+    }
+
+    fn address(text: &str) -> MemoryAddress {
+        text.parse().unwrap()
+    }
+
+    #[tokio::test]
+    async fn memory_commands_work() {
+        let client = connect(Simulator::default(), Duration::ZERO).await;
+
+        client
+            .execute(&MemoryAreaWriteRequest {
+                address: address("D100"),
+                bytes: vec![0x12, 0x34],
+            })
+            .await
+            .unwrap();
+        client
+            .execute(&MemoryAreaFillRequest {
+                address: address("D101"),
+                count: 2,
+                word: 0xFFFF,
+            })
+            .await
+            .unwrap();
+        client
+            .execute(&MemoryAreaTransferRequest {
+                source: address("D100"),
+                destination: address("W0"),
+                count: 3,
+            })
+            .await
+            .unwrap();
+        let bytes = client
+            .execute(&MemoryAreaReadRequest {
+                address: address("W0"),
+                count: 3,
+            })
+            .await
+            .unwrap();
+        assert_eq!(bytes, [0x12, 0x34, 0xFF, 0xFF, 0xFF, 0xFF]);
+
+        let status = client.execute(&ControllerStatusReadRequest).await.unwrap();
+        assert_eq!(status.status, OperatingStatus::Run);
+
+        // Reads that do not fit in a frame fail without losing the connection.
+        assert!(matches!(
+            client
+                .execute(&MemoryAreaReadRequest {
+                    address: address("D0"),
+                    count: 2000,
+                })
+                .await,
+            Err(fins_tcp::Error::EndCode(EndCode::COMMAND_TOO_LONG))
+        ));
+        assert!(client.is_connected());
+    }
+
+    #[tokio::test]
+    async fn clock_write_works() {
+        let client = connect(Simulator::default(), Duration::ZERO).await;
+        let clock = Clock {
+            year: 24,
+            month: 2,
+            day: 29,
+            hour: 13,
+            minute: 45,
+            second: 7,
+            day_of_week: 4,
+        };
+        client.execute(&ClockWriteRequest { clock }).await.unwrap();
+        let read = client.execute(&ClockReadRequest).await.unwrap();
+        assert_eq!((read.year, read.month, read.day), (24, 2, 29));
+        assert_eq!(read.day_of_week, 4);
+    }
+
+    #[tokio::test]
+    async fn injects_faults_and_latency() {
+        let mut faults = HashMap::new();
+        faults.insert([0x01, 0x01], EndCode::ADDRESS_RANGE_EXCEEDED);
+        let simulator = Simulator::with_faults(Memory::default(), faults);
+        let client = connect(simulator, Duration::from_millis(20)).await;
+
+        let command = MemoryAreaReadRequest {
+            address: address("D0"),
+            count: 1,
+        };
+        let start = Instant::now();
+        let first = client.send(&command).await.unwrap();
+        let second = client.send(&command).await.unwrap();
+        for pending in [first, second] {
+            assert!(matches!(
+                client.receive(&command, pending).await,
+                Err(fins_tcp::Error::EndCode(EndCode::ADDRESS_RANGE_EXCEEDED))
+            ));
+        }
+        assert!(start.elapsed() >= Duration::from_millis(40));
+    }
+}
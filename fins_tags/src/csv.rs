@@ -0,0 +1,49 @@
+/// Splits a line of comma separated values on `separator`. Fields may be quoted with `"`, and
+/// quotes within quoted fields are doubled.
+pub(crate) fn split_line(line: &str, separator: char) -> Result<Vec<String>, String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut chars = line.chars().peekable();
+    let mut quoted = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted => {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    field.push('"');
+                } else {
+                    quoted = false;
+                }
+            }
+            '"' if field.trim().is_empty() => {
+                field.clear();
+                quoted = true;
+            }
+            c if c == separator && !quoted => {
+                fields.push(field.trim().to_string());
+                field.clear();
+            }
+            c => field.push(c),
+        }
+    }
+    if quoted {
+        return Err("unterminated quote".to_string());
+    }
+    fields.push(field.trim().to_string());
+    Ok(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_line_works() {
+        assert_eq!(
+            split_line(r#"a, "b, ""c""" ,,d"#, ',').unwrap(),
+            ["a", r#"b, "c""#, "", "d"]
+        );
+        assert_eq!(split_line("a\tb", '\t').unwrap(), ["a", "b"]);
+        assert!(split_line(r#"a,"b"#, ',').is_err());
+    }
+}
@@ -0,0 +1,480 @@
+use fins::MemoryAddress;
+
+use crate::{csv, Access, DataType, Tag, TagDatabase};
+
+/// Tags imported from a symbol table exported by CX-Programmer, with the problems found.
+#[derive(Debug, Clone, Default)]
+pub struct Import {
+    /// The tags in the order of the export. Of symbols with the same name only the first is kept.
+    pub tags: Vec<Tag>,
+    pub issues: Vec<ImportIssue>,
+}
+
+/// A problem found while importing a symbol table. Lines are 1-based.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum ImportIssue {
+    /// The line could not be imported.
+    Skipped { line: usize, reason: String },
+    /// The symbol on `line` has the name of the symbol on `first_line` and was skipped.
+    DuplicateName {
+        name: String,
+        line: usize,
+        first_line: usize,
+    },
+    /// The memory of the two tags overlaps, which is likely a mistake.
+    Overlap { first: String, second: String },
+    /// The BOOL tag `bit` is a bit of the word tag `word`, like a BOOL in a CHANNEL. This is
+    /// usually intended.
+    BitOfWord { bit: String, word: String },
+}
+
+impl std::fmt::Display for ImportIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ImportIssue::Skipped { line, reason } => {
+                write!(f, "line {}: skipped, {}", line, reason)
+            }
+            ImportIssue::DuplicateName {
+                name,
+                line,
+                first_line,
+            } => write!(
+                f,
+                "line {}: skipped, {:?} is already defined on line {}",
+                line, name, first_line
+            ),
+            ImportIssue::Overlap { first, second } => {
+                write!(f, "{:?} overlaps {:?}", second, first)
+            }
+            ImportIssue::BitOfWord { bit, word } => {
+                write!(f, "{:?} is a bit of {:?}", bit, word)
+            }
+        }
+    }
+}
+
+impl Import {
+    /// A database of the imported tags.
+    pub fn into_database(self) -> crate::Result<TagDatabase> {
+        let mut database = TagDatabase::new();
+        for tag in self.tags {
+            database.insert(tag)?;
+        }
+        Ok(database)
+    }
+}
+
+/// Imports symbols copied from the symbol table of CX-Programmer or exported as CSV or text.
+/// Columns are separated by tabs or commas. When the first line names the columns, `Name`,
+/// `Data Type`, `Address` (or `Address / Value`) and `Comment` are used, otherwise these are
+/// expected in this order.
+///
+/// Addresses are written as in CX-Programmer, like `D1508`, `W10.03` or `10.03` for the CIO area.
+/// Arrays, like `INT[10]` or `ARRAY[0..9] OF INT`, become a tag for every element named
+/// `Name[0]`, `Name[1]` and so on. Symbols without an address or of unsupported types, like
+/// timers, `NUMBER` constants and BCD numbers, are skipped.
+pub fn import_cx_programmer(text: &str) -> Import {
+    let mut import = Import::default();
+    let separator = if text.contains('\t') { '\t' } else { ',' };
+    let mut columns = Columns::default();
+    let mut first_lines = std::collections::HashMap::new();
+    let mut header_checked = false;
+
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        if line.trim().is_empty() || line.trim_start().starts_with("//") {
+            continue;
+        }
+        let skipped = |reason: String| ImportIssue::Skipped {
+            line: line_number,
+            reason,
+        };
+        let fields = match csv::split_line(line, separator) {
+            Ok(fields) => fields,
+            Err(reason) => {
+                import.issues.push(skipped(reason));
+                continue;
+            }
+        };
+        if !header_checked {
+            header_checked = true;
+            if let Some(header) = Columns::from_header(&fields) {
+                columns = header;
+                continue;
+            }
+        }
+
+        let field = |index: usize| fields.get(index).map(String::as_str).unwrap_or("");
+        let name = field(columns.name);
+        let data_type = field(columns.data_type);
+        let address = field(columns.address);
+        if name.is_empty() {
+            import.issues.push(skipped("missing name".to_string()));
+            continue;
+        }
+        if address.is_empty() {
+            import
+                .issues
+                .push(skipped(format!("{:?} has no address", name)));
+            continue;
+        }
+
+        let tags = match parse_type(data_type).and_then(|(data_type, count)| {
+            let address = parse_address(address)?;
+            symbol_tags(name, data_type, count, address)
+        }) {
+            Ok(tags) => tags,
+            Err(reason) => {
+                import
+                    .issues
+                    .push(skipped(format!("{:?}: {}", name, reason)));
+                continue;
+            }
+        };
+
+        if let Some(&first_line) = first_lines.get(name) {
+            import.issues.push(ImportIssue::DuplicateName {
+                name: name.to_string(),
+                line: line_number,
+                first_line,
+            });
+            continue;
+        }
+        first_lines.insert(name.to_string(), line_number);
+
+        let description = columns.comment.map(field).unwrap_or("");
+        import.tags.extend(tags.into_iter().map(|mut tag| {
+            tag.description = description.to_string();
+            tag
+        }));
+    }
+
+    import.issues.extend(overlaps(&import.tags));
+    import
+}
+
+/// Indices of the columns of a symbol table.
+struct Columns {
+    name: usize,
+    data_type: usize,
+    address: usize,
+    comment: Option<usize>,
+}
+
+impl Default for Columns {
+    fn default() -> Self {
+        Self {
+            name: 0,
+            data_type: 1,
+            address: 2,
+            comment: Some(3),
+        }
+    }
+}
+
+impl Columns {
+    fn from_header(fields: &[String]) -> Option<Self> {
+        let column = |names: &[&str]| {
+            fields
+                .iter()
+                .position(|field| names.iter().any(|name| field.eq_ignore_ascii_case(name)))
+        };
+        Some(Self {
+            name: column(&["Name", "Symbol"])?,
+            data_type: column(&["Data Type", "Type"])?,
+            address: column(&["Address / Value", "Address/Value", "Address"])?,
+            comment: column(&["Comment"]),
+        })
+    }
+}
+
+/// Parses a CX-Programmer data type into the type of the elements and the number of elements.
+fn parse_type(text: &str) -> Result<(DataType, usize), String> {
+    let upper = text.trim().to_ascii_uppercase();
+
+    // ARRAY[0..9] OF INT
+    if let Some(rest) = upper.strip_prefix("ARRAY[") {
+        let (range, element) = rest
+            .split_once("] OF ")
+            .ok_or_else(|| format!("invalid array type {:?}", text))?;
+        let (first, last) = range
+            .split_once("..")
+            .and_then(|(first, last)| {
+                Some((first.parse::<usize>().ok()?, last.parse::<usize>().ok()?))
+            })
+            .filter(|(first, last)| first <= last)
+            .ok_or_else(|| format!("invalid array range {:?}", text))?;
+        let (data_type, count) = parse_type(element)?;
+        return Ok((data_type, count * (last - first + 1)));
+    }
+
+    // STRING[10] and STRING(10) are strings of 10 bytes, INT[10] is an array of 10 INTs.
+    let (base, size) = match upper.find(['[', '(']) {
+        Some(start) => {
+            let size = upper[start + 1..]
+                .strip_suffix([']', ')'])
+                .and_then(|size| size.trim().parse::<usize>().ok())
+                .filter(|&size| size > 0)
+                .ok_or_else(|| format!("invalid size in {:?}", text))?;
+            (upper[..start].trim(), Some(size))
+        }
+        None => (upper.as_str(), None),
+    };
+    let data_type = match base {
+        "BOOL" => DataType::Bool,
+        "INT" => DataType::Int,
+        "UINT" => DataType::UInt,
+        "DINT" => DataType::DInt,
+        "UDINT" => DataType::UDInt,
+        "REAL" => DataType::Real,
+        "CHANNEL" | "WORD" => DataType::Word,
+        "DWORD" => DataType::DWord,
+        "STRING" => {
+            let length = size.ok_or("STRING needs a length, like STRING[10]")?;
+            return Ok((DataType::String(length), 1));
+        }
+        _ => return Err(format!("unsupported data type {:?}", text)),
+    };
+    Ok((data_type, size.unwrap_or(1)))
+}
+
+/// Parses an address in CX-Programmer notation, where CIO addresses have no prefix.
+fn parse_address(text: &str) -> Result<MemoryAddress, String> {
+    let text = text.trim();
+    let address = if text.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("CIO{}", text)
+    } else {
+        text.to_string()
+    };
+    address
+        .parse()
+        .map_err(|e: fins::ParseMemoryAddressError| e.to_string())
+}
+
+/// The tags of a symbol of `count` elements of `data_type` stored from `address`.
+fn symbol_tags(
+    name: &str,
+    data_type: DataType,
+    count: usize,
+    address: MemoryAddress,
+) -> Result<Vec<Tag>, String> {
+    // A BOOL symbol at a word address refers to bit 0.
+    let address = match (data_type.is_bit(), address.area_code.is_bit_area()) {
+        (true, false) => MemoryAddress {
+            area_code: address.area_code.bit_area(),
+            ..address
+        },
+        (false, true) => return Err(format!("{} needs a word address", data_type)),
+        _ => address,
+    };
+    if address.offset_by(count * data_type.item_count()).is_none() {
+        return Err(format!("{:?} does not fit in its area", address));
+    }
+    Ok((0..count)
+        .map(|index| Tag {
+            name: if count == 1 {
+                name.to_string()
+            } else {
+                format!("{}[{}]", name, index)
+            },
+            address: address.offset_by(index * data_type.item_count()).unwrap(),
+            data_type,
+            scaling: None,
+            access: Access::ReadWrite,
+            description: String::new(),
+        })
+        .collect())
+}
+
+/// The pairs of tags whose memory overlaps, comparing every tag to all tags before it in memory
+/// that it overlaps.
+fn overlaps(tags: &[Tag]) -> Vec<ImportIssue> {
+    let bit_range = |tag: &Tag| {
+        let start = tag.address.offset as usize * 16 + tag.address.bits as usize;
+        let length = if tag.data_type.is_bit() {
+            1
+        } else {
+            16 * tag.item_count()
+        };
+        (
+            tag.address.area_code.word_area() as u8,
+            start,
+            start + length,
+        )
+    };
+    let mut ranges = tags
+        .iter()
+        .map(|tag| (bit_range(tag), tag))
+        .collect::<Vec<_>>();
+    ranges.sort_by_key(|&(range, tag)| (range, tag.name.as_str()));
+
+    let mut issues = Vec::new();
+    // The tags before the current one that may still overlap the tags after it.
+    let mut active = Vec::<((u8, usize, usize), &Tag)>::new();
+    for &((area, start, end), tag) in &ranges {
+        active
+            .retain(|&((active_area, _, active_end), _)| active_area == area && start < active_end);
+        for &(_, first) in &active {
+            issues.push(match (first.data_type.is_bit(), tag.data_type.is_bit()) {
+                (false, true) => ImportIssue::BitOfWord {
+                    bit: tag.name.clone(),
+                    word: first.name.clone(),
+                },
+                (true, false) => ImportIssue::BitOfWord {
+                    bit: first.name.clone(),
+                    word: tag.name.clone(),
+                },
+                _ => ImportIssue::Overlap {
+                    first: first.name.clone(),
+                    second: tag.name.clone(),
+                },
+            });
+        }
+        active.push(((area, start, end), tag));
+    }
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn address(text: &str) -> MemoryAddress {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn import_works() {
+        let import = import_cx_programmer(
+            "Name\tData Type\tAddress / Value\tComment\n\
+             HoldToRun.Instruction.Assign.SequenceNumber\tUINT\tD1508\tSequence number\n\
+             Alarms.Status.Alarm_NoCommunication\tBOOL\tD2420.01\t\n\
+             Alarms.Status\tCHANNEL\tD2420\t\n\
+             Start\tBOOL\t0.03\t\n\
+             Recipe\tINT[3]\tD100\t\n\
+             Lamps\tARRAY[0..1] OF BOOL\tW0.15\t\n\
+             Operator\tSTRING[5]\tD200\t\n\
+             Recipe\tINT\tD300\t\n\
+             MaxSpeed\tNUMBER\t1500\t\n\
+             Timer\tTIMER\tT0000\t\n\
+             Overlapping\tDINT\tD101\t\n",
+        );
+
+        let tags = import
+            .tags
+            .iter()
+            .map(|tag| (tag.name.as_str(), tag.address, tag.data_type))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            tags,
+            [
+                (
+                    "HoldToRun.Instruction.Assign.SequenceNumber",
+                    address("D1508"),
+                    DataType::UInt
+                ),
+                (
+                    "Alarms.Status.Alarm_NoCommunication",
+                    address("D2420.01"),
+                    DataType::Bool
+                ),
+                ("Alarms.Status", address("D2420"), DataType::Word),
+                ("Start", address("CIO0.03"), DataType::Bool),
+                ("Recipe[0]", address("D100"), DataType::Int),
+                ("Recipe[1]", address("D101"), DataType::Int),
+                ("Recipe[2]", address("D102"), DataType::Int),
+                ("Lamps[0]", address("W0.15"), DataType::Bool),
+                ("Lamps[1]", address("W1.00"), DataType::Bool),
+                ("Operator", address("D200"), DataType::String(5)),
+                ("Overlapping", address("D101"), DataType::DInt),
+            ]
+        );
+        assert_eq!(import.tags[0].description, "Sequence number");
+
+        let issues = import.issues;
+        assert_eq!(
+            issues[0],
+            ImportIssue::DuplicateName {
+                name: "Recipe".to_string(),
+                line: 9,
+                first_line: 6,
+            }
+        );
+        assert!(matches!(&issues[1], ImportIssue::Skipped { line: 10, .. }));
+        assert!(matches!(&issues[2], ImportIssue::Skipped { line: 11, .. }));
+        assert_eq!(
+            issues[3..],
+            [
+                ImportIssue::Overlap {
+                    first: "Recipe[1]".to_string(),
+                    second: "Overlapping".to_string(),
+                },
+                ImportIssue::Overlap {
+                    first: "Overlapping".to_string(),
+                    second: "Recipe[2]".to_string(),
+                },
+                ImportIssue::BitOfWord {
+                    bit: "Alarms.Status.Alarm_NoCommunication".to_string(),
+                    word: "Alarms.Status".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn overlaps_works() {
+        let import = import_cx_programmer(
+            "Long\tSTRING[6]\tD0\n\
+             Longer\tSTRING[8]\tD1\n\
+             Short\tINT\tD2\n\
+             Status\tWORD\tD10\n\
+             First\tBOOL\tD10.00\n\
+             Other\tBOOL\tD10.00\n\
+             Apart\tINT\tW2\n",
+        );
+        assert_eq!(
+            import.issues,
+            [
+                ImportIssue::Overlap {
+                    first: "Long".to_string(),
+                    second: "Longer".to_string(),
+                },
+                ImportIssue::Overlap {
+                    first: "Long".to_string(),
+                    second: "Short".to_string(),
+                },
+                ImportIssue::Overlap {
+                    first: "Longer".to_string(),
+                    second: "Short".to_string(),
+                },
+                ImportIssue::Overlap {
+                    first: "First".to_string(),
+                    second: "Other".to_string(),
+                },
+                ImportIssue::BitOfWord {
+                    bit: "First".to_string(),
+                    word: "Status".to_string(),
+                },
+                ImportIssue::BitOfWord {
+                    bit: "Other".to_string(),
+                    word: "Status".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_type_works() {
+        assert_eq!(parse_type("int"), Ok((DataType::Int, 1)));
+        assert_eq!(parse_type("REAL[4]"), Ok((DataType::Real, 4)));
+        assert_eq!(parse_type("STRING(8)"), Ok((DataType::String(8), 1)));
+        assert_eq!(
+            parse_type("ARRAY[1..3] OF STRING[4]"),
+            Ok((DataType::String(4), 3))
+        );
+        assert!(parse_type("STRING").is_err());
+        assert!(parse_type("LREAL").is_err());
+        assert!(parse_type("UINT_BCD").is_err());
+        assert!(parse_type("UDINT_BCD").is_err());
+    }
+}
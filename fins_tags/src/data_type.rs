@@ -0,0 +1,200 @@
+use crate::{Error, Value};
+
+/// How the value of a tag is stored in PLC memory. Values of 32 bits store their least
+/// significant word at the lowest address, like the PLC does.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum DataType {
+    /// A single bit, addressed in a bit area.
+    Bool,
+    Int,
+    UInt,
+    DInt,
+    UDInt,
+    Real,
+    Word,
+    DWord,
+    /// ASCII text of at most this many bytes, two bytes per word with the first in the most
+    /// significant byte. Shorter text is padded with zero bytes.
+    String(usize),
+}
+
+impl DataType {
+    /// Number of items read or written, bits for [`DataType::Bool`] and words otherwise.
+    pub fn item_count(&self) -> usize {
+        match self {
+            DataType::Bool => 1,
+            DataType::Int | DataType::UInt | DataType::Word => 1,
+            DataType::DInt | DataType::UDInt | DataType::Real | DataType::DWord => 2,
+            DataType::String(length) => length.div_ceil(2),
+        }
+    }
+
+    /// Whether the value is a bit rather than words.
+    pub fn is_bit(&self) -> bool {
+        matches!(self, DataType::Bool)
+    }
+
+    /// Decodes the bytes of a memory area read of [`DataType::item_count`] items. Fails when
+    /// there are fewer bytes.
+    pub fn decode(&self, bytes: &[u8]) -> crate::Result<Value> {
+        let expected = match self {
+            DataType::Bool => 1,
+            _ => 2 * self.item_count(),
+        };
+        if bytes.len() < expected {
+            return Err(Error::ValueTooShort {
+                data_type: *self,
+                actual: bytes.len(),
+                expected,
+            });
+        }
+
+        let word = |index: usize| u16::from_be_bytes([bytes[2 * index], bytes[2 * index + 1]]);
+        let dword = || (word(1) as u32) << 16 | word(0) as u32;
+        Ok(match self {
+            DataType::Bool => Value::Bool(bytes[0] & 1 != 0),
+            DataType::Int => Value::Int(word(0) as i16 as i64),
+            DataType::UInt | DataType::Word => Value::Int(word(0) as i64),
+            DataType::DInt => Value::Int(dword() as i32 as i64),
+            DataType::UDInt | DataType::DWord => Value::Int(dword() as i64),
+            DataType::Real => Value::Float(f32::from_bits(dword()) as f64),
+            DataType::String(length) => {
+                let bytes = &bytes[..*length];
+                let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+                Value::String(String::from_utf8_lossy(&bytes[..end]).into_owned())
+            }
+        })
+    }
+
+    /// Encodes `value` as the bytes of a memory area write. Returns `None` when the value does not
+    /// fit this type.
+    pub fn encode(&self, value: &Value) -> Option<Vec<u8>> {
+        let int = |min: i64, max: i64| match *value {
+            Value::Int(value) if (min..=max).contains(&value) => Some(value),
+            _ => None,
+        };
+        let dword = |value: u32| {
+            let [a, b, c, d] = value.to_be_bytes();
+            vec![c, d, a, b]
+        };
+        Some(match self {
+            DataType::Bool => match *value {
+                Value::Bool(value) => vec![value as u8],
+                _ => vec![int(0, 1)? as u8],
+            },
+            DataType::Int => (int(i16::MIN as i64, i16::MAX as i64)? as i16)
+                .to_be_bytes()
+                .to_vec(),
+            DataType::UInt | DataType::Word => {
+                (int(0, u16::MAX as i64)? as u16).to_be_bytes().to_vec()
+            }
+            DataType::DInt => dword(int(i32::MIN as i64, i32::MAX as i64)? as u32),
+            DataType::UDInt | DataType::DWord => dword(int(0, u32::MAX as i64)? as u32),
+            DataType::Real => match *value {
+                Value::Float(value) => dword((value as f32).to_bits()),
+                Value::Int(value) => dword((value as f32).to_bits()),
+                _ => return None,
+            },
+            DataType::String(length) => match value {
+                Value::String(text) if text.is_ascii() && text.len() <= *length => {
+                    let mut bytes = text.as_bytes().to_vec();
+                    bytes.resize(2 * self.item_count(), 0);
+                    bytes
+                }
+                _ => return None,
+            },
+        })
+    }
+}
+
+/// Parses the names used in tag definitions, like `INT` or `STRING[20]`, in any case.
+impl std::str::FromStr for DataType {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let upper = text.trim().to_ascii_uppercase();
+        Ok(match upper.as_str() {
+            "BOOL" => DataType::Bool,
+            "INT" => DataType::Int,
+            "UINT" => DataType::UInt,
+            "DINT" => DataType::DInt,
+            "UDINT" => DataType::UDInt,
+            "REAL" => DataType::Real,
+            "WORD" => DataType::Word,
+            "DWORD" => DataType::DWord,
+            _ => match upper
+                .strip_prefix("STRING[")
+                .and_then(|rest| rest.strip_suffix(']'))
+                .and_then(|length| length.parse().ok())
+            {
+                Some(length) if length > 0 => DataType::String(length),
+                _ => return Err(format!("unknown data type {:?}", text)),
+            },
+        })
+    }
+}
+
+impl std::fmt::Display for DataType {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            DataType::Bool => write!(f, "BOOL"),
+            DataType::Int => write!(f, "INT"),
+            DataType::UInt => write!(f, "UINT"),
+            DataType::DInt => write!(f, "DINT"),
+            DataType::UDInt => write!(f, "UDINT"),
+            DataType::Real => write!(f, "REAL"),
+            DataType::Word => write!(f, "WORD"),
+            DataType::DWord => write!(f, "DWORD"),
+            DataType::String(length) => write!(f, "STRING[{}]", length),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_decode_works() {
+        for (data_type, value, bytes) in [
+            (DataType::Bool, Value::Bool(true), vec![0x01]),
+            (DataType::Int, Value::Int(-2), vec![0xFF, 0xFE]),
+            (DataType::UInt, Value::Int(1508), vec![0x05, 0xE4]),
+            (
+                DataType::DInt,
+                Value::Int(-65536),
+                vec![0x00, 0x00, 0xFF, 0xFF],
+            ),
+            (
+                DataType::Real,
+                Value::Float(1.5),
+                vec![0x00, 0x00, 0x3F, 0xC0],
+            ),
+            (
+                DataType::String(5),
+                Value::String("ABC".to_string()),
+                vec![b'A', b'B', b'C', 0, 0, 0],
+            ),
+        ] {
+            assert_eq!(data_type.encode(&value).as_ref(), Some(&bytes));
+            assert_eq!(data_type.decode(&bytes).unwrap(), value);
+            assert!(matches!(
+                data_type.decode(&bytes[..bytes.len() - 1]),
+                Err(Error::ValueTooShort { .. })
+            ));
+        }
+        assert_eq!(DataType::UInt.encode(&Value::Int(-1)), None);
+        assert_eq!(
+            DataType::String(2).encode(&Value::String("ABC".to_string())),
+            None
+        );
+    }
+
+    #[test]
+    fn from_str_works() {
+        assert_eq!("dint".parse(), Ok(DataType::DInt));
+        assert_eq!("STRING[20]".parse(), Ok(DataType::String(20)));
+        assert!("STRING[0]".parse::<DataType>().is_err());
+        assert_eq!(DataType::String(20).to_string(), "STRING[20]");
+    }
+}
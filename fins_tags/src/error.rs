@@ -0,0 +1,83 @@
+use std::time::Duration;
+
+use crate::DataType;
+
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// A tag definition could not be parsed. `line` is 1-based, 0 when unknown.
+    Parse {
+        line: usize,
+        message: String,
+    },
+    /// Two tags have the same name.
+    DuplicateTag(String),
+    UnknownTag(String),
+    /// The tag may not be written.
+    ReadOnly(String),
+    /// The tag may not be read.
+    WriteOnly(String),
+    /// The value can not be stored in the data type of the tag.
+    InvalidValue {
+        tag: String,
+        data_type: DataType,
+    },
+    /// Fewer bytes were read than a value of `data_type` takes.
+    ValueTooShort {
+        data_type: DataType,
+        actual: usize,
+        expected: usize,
+    },
+    /// The tag can not be polled at this rate or with this deadband.
+    InvalidSubscription {
+        tag: String,
+        rate: Duration,
+        deadband: f64,
+    },
+    Client(fins_tcp::Error),
+}
+
+impl From<fins_tcp::Error> for Error {
+    fn from(error: fins_tcp::Error) -> Self {
+        Self::Client(error)
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Parse { line: 0, message } => write!(f, "{}", message),
+            Self::Parse { line, message } => write!(f, "line {}: {}", line, message),
+            Self::DuplicateTag(name) => write!(f, "Tag {:?} is defined more than once!", name),
+            Self::UnknownTag(name) => write!(f, "Unknown tag {:?}!", name),
+            Self::ReadOnly(name) => write!(f, "Tag {:?} is read only!", name),
+            Self::WriteOnly(name) => write!(f, "Tag {:?} is write only!", name),
+            Self::InvalidValue { tag, data_type } => {
+                write!(f, "Value does not fit tag {:?} of type {}!", tag, data_type)
+            }
+            Self::ValueTooShort {
+                data_type,
+                actual,
+                expected,
+            } => write!(
+                f,
+                "Read {} bytes for a value of type {} but expected {} bytes!",
+                actual, data_type, expected
+            ),
+            Self::InvalidSubscription {
+                tag,
+                rate,
+                deadband,
+            } => write!(
+                f,
+                "Tag {:?} can not be polled every {:?} with deadband {}!",
+                tag, rate, deadband
+            ),
+            Self::Client(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+pub type Result<T, E = Error> = ::std::result::Result<T, E>;
@@ -0,0 +1,16 @@
+mod csv;
+mod cx_programmer;
+mod data_type;
+mod error;
+mod subscriptions;
+mod tag;
+mod tag_database;
+mod value;
+
+pub use cx_programmer::*;
+pub use data_type::*;
+pub use error::*;
+pub use subscriptions::*;
+pub use tag::*;
+pub use tag_database::*;
+pub use value::*;
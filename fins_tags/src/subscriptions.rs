@@ -0,0 +1,392 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use fins::MemoryAddress;
+use fins_client::{Client, ReadPlan, ReadPlanner};
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+
+use crate::{Access, DataType, Error, Tag, Value};
+
+/// Number of events buffered before polling waits for the receiver.
+const EVENT_CAPACITY: usize = 256;
+
+/// An event reported by [`Subscriptions`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum SubscriptionEvent {
+    /// The value of the tag named `name` changed. `old` is the value reported before, `None` for
+    /// the first read.
+    Changed {
+        name: String,
+        old: Option<Value>,
+        new: Value,
+    },
+    /// Reading the tags polled every `rate` took `elapsed`, so that the next poll was late.
+    Overrun { rate: Duration, elapsed: Duration },
+    /// Reading the tags polled every `rate` failed. Reported once until a read succeeds again.
+    Failed { rate: Duration, error: String },
+}
+
+struct Subscription {
+    tag: Tag,
+    deadband: f64,
+}
+
+/// Polls tags at fixed rates and reports the changes of their values.
+///
+/// Tags with the same rate form a group which is read with the requests planned by the planner.
+/// Every group polls in its own task, so that the requests of all groups share the window of the
+/// client and a slow group does not delay the others.
+pub struct Subscriptions {
+    client: Arc<Client>,
+    planner: ReadPlanner,
+    groups: BTreeMap<Duration, Vec<Subscription>>,
+}
+
+impl Subscriptions {
+    pub fn new(client: Arc<Client>) -> Self {
+        Self {
+            client,
+            planner: ReadPlanner::new(),
+            groups: BTreeMap::new(),
+        }
+    }
+
+    /// Sets the planner of the reads of every group, see [`ReadPlanner`].
+    pub fn set_planner(&mut self, planner: ReadPlanner) {
+        self.planner = planner;
+    }
+
+    /// Polls `tag` every `rate`, which has to be positive. Changes of numeric values smaller than
+    /// `deadband` are not reported, a deadband of 0 reports every change.
+    pub fn subscribe(&mut self, tag: Tag, rate: Duration, deadband: f64) -> crate::Result<()> {
+        if rate == Duration::ZERO || deadband.is_nan() || deadband < 0.0 {
+            return Err(Error::InvalidSubscription {
+                tag: tag.name,
+                rate,
+                deadband,
+            });
+        }
+        if !tag.access.can_read() {
+            return Err(Error::WriteOnly(tag.name));
+        }
+        self.groups
+            .entry(rate)
+            .or_default()
+            .push(Subscription { tag, deadband });
+        Ok(())
+    }
+
+    /// Polls the value of `data_type` at `address` every `rate`. Changes are reported with the
+    /// address as name, like `D100`.
+    pub fn subscribe_address(
+        &mut self,
+        address: MemoryAddress,
+        data_type: DataType,
+        rate: Duration,
+    ) -> crate::Result<()> {
+        if data_type.is_bit() != address.area_code.is_bit_area() {
+            return Err(Error::Parse {
+                line: 0,
+                message: format!(
+                    "{} can not be stored at {:?}, BOOL needs a bit address and other types a word address",
+                    data_type, address
+                ),
+            });
+        }
+        let tag = Tag {
+            name: format!("{:?}", address),
+            address,
+            data_type,
+            scaling: None,
+            access: Access::Read,
+            description: String::new(),
+        };
+        self.subscribe(tag, rate, 0.0)
+    }
+
+    /// Starts polling and returns the events. Polling stops when the receiver is dropped. Fails
+    /// when the tags of a group do not fit in their areas.
+    pub fn start(self) -> crate::Result<mpsc::Receiver<SubscriptionEvent>> {
+        let mut groups = Vec::with_capacity(self.groups.len());
+        for (rate, subscriptions) in self.groups {
+            let plan = self.planner.plan(
+                &subscriptions
+                    .iter()
+                    .map(|subscription| (subscription.tag.address, subscription.tag.item_count()))
+                    .collect::<Vec<_>>(),
+            )?;
+            groups.push((rate, plan, subscriptions));
+        }
+
+        let (events, receiver) = mpsc::channel(EVENT_CAPACITY);
+        for (rate, plan, subscriptions) in groups {
+            tokio::spawn(poll(
+                Arc::clone(&self.client),
+                rate,
+                plan,
+                subscriptions,
+                events.clone(),
+            ));
+        }
+        Ok(receiver)
+    }
+}
+
+async fn poll(
+    client: Arc<Client>,
+    rate: Duration,
+    plan: ReadPlan,
+    subscriptions: Vec<Subscription>,
+    events: mpsc::Sender<SubscriptionEvent>,
+) {
+    let mut reported = vec![None; subscriptions.len()];
+    let mut failing = false;
+    let mut next_poll = Instant::now();
+
+    loop {
+        let started = Instant::now();
+        let mut changes = Vec::new();
+        let values = client
+            .read_plan(&plan)
+            .await
+            .map_err(Error::from)
+            .and_then(|bytes| {
+                subscriptions
+                    .iter()
+                    .zip(bytes)
+                    .map(|(subscription, bytes)| subscription.tag.decode(&bytes))
+                    .collect::<crate::Result<Vec<_>>>()
+            });
+        match values {
+            Ok(values) => {
+                failing = false;
+                for ((subscription, new), old) in
+                    subscriptions.iter().zip(values).zip(&mut reported)
+                {
+                    if changed(old.as_ref(), &new, subscription.deadband) {
+                        changes.push(SubscriptionEvent::Changed {
+                            name: subscription.tag.name.clone(),
+                            old: old.replace(new.clone()),
+                            new,
+                        });
+                    }
+                }
+            }
+            Err(error) => {
+                if !failing {
+                    failing = true;
+                    changes.push(SubscriptionEvent::Failed {
+                        rate,
+                        error: error.to_string(),
+                    });
+                }
+            }
+        }
+
+        next_poll += rate;
+        let now = Instant::now();
+        if now > next_poll {
+            changes.push(SubscriptionEvent::Overrun {
+                rate,
+                elapsed: now - started,
+            });
+            next_poll = now;
+        }
+
+        for event in changes {
+            if events.send(event).await.is_err() {
+                return;
+            }
+        }
+        // Stop polling as soon as the receiver is dropped, not at the next change.
+        tokio::select! {
+            _ = tokio::time::sleep_until(next_poll) => {}
+            _ = events.closed() => return,
+        }
+    }
+}
+
+/// Whether `new` differs from `old` by at least `deadband`, or at all for values that are not
+/// numbers.
+fn changed(old: Option<&Value>, new: &Value, deadband: f64) -> bool {
+    match old {
+        None => true,
+        Some(old) => match (old.to_f64(), new.to_f64()) {
+            (Some(old), Some(new)) if deadband > 0.0 => (new - old).abs() >= deadband,
+            _ => old != new,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fins::MemoryAreaCode;
+    use fins_server::{MemoryHandler, Server};
+
+    use super::*;
+
+    async fn connect(handler: MemoryHandler, response_delay: Duration) -> Arc<Client> {
+        let mut server = Server::bind("127.0.0.1:0", 0x01, handler).await.unwrap();
+        server.set_response_delay(response_delay);
+        let addr = server.local_addr().unwrap();
+        tokio::spawn(server.run());
+        Arc::new(Client::connect(addr).await.unwrap())
+    }
+
+    fn address(text: &str) -> MemoryAddress {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn changed_works() {
+        assert!(changed(None, &Value::Int(0), 5.0));
+        assert!(!changed(Some(&Value::Int(0)), &Value::Int(4), 5.0));
+        assert!(changed(Some(&Value::Int(0)), &Value::Int(-5), 5.0));
+        assert!(changed(Some(&Value::Int(0)), &Value::Int(1), 0.0));
+        assert!(!changed(Some(&Value::Bool(true)), &Value::Bool(true), 0.0));
+        let string = |text: &str| Value::String(text.to_string());
+        assert!(changed(Some(&string("a")), &string("b"), 5.0));
+    }
+
+    #[tokio::test]
+    async fn reports_changes() {
+        let handler = MemoryHandler::default();
+        let client = connect(handler.clone(), Duration::ZERO).await;
+
+        let rate = Duration::from_millis(10);
+        let mut subscriptions = Subscriptions::new(client);
+        let level = Tag {
+            name: "Tank.Level".to_string(),
+            address: address("D100"),
+            data_type: DataType::Int,
+            scaling: None,
+            access: Access::Read,
+            description: String::new(),
+        };
+        subscriptions.subscribe(level, rate, 5.0).unwrap();
+        subscriptions
+            .subscribe_address(address("D200.03"), DataType::Bool, rate * 2)
+            .unwrap();
+        let mut events = subscriptions.start().unwrap();
+
+        let mut first = vec![events.recv().await.unwrap(), events.recv().await.unwrap()];
+        first.sort_by_key(|event| format!("{:?}", event));
+        assert_eq!(
+            first,
+            [
+                SubscriptionEvent::Changed {
+                    name: "D200.3".to_string(),
+                    old: None,
+                    new: Value::Bool(false)
+                },
+                SubscriptionEvent::Changed {
+                    name: "Tank.Level".to_string(),
+                    old: None,
+                    new: Value::Int(0)
+                },
+            ]
+        );
+
+        // Changes within the deadband are not reported.
+        handler.memory().area(MemoryAreaCode::D)[100] = 3;
+        tokio::time::sleep(rate * 5).await;
+        handler.memory().area(MemoryAreaCode::D)[100] = 10;
+        assert_eq!(
+            events.recv().await.unwrap(),
+            SubscriptionEvent::Changed {
+                name: "Tank.Level".to_string(),
+                old: Some(Value::Int(0)),
+                new: Value::Int(10)
+            }
+        );
+
+        handler.memory().area(MemoryAreaCode::D)[200] = 0b1000;
+        assert_eq!(
+            events.recv().await.unwrap(),
+            SubscriptionEvent::Changed {
+                name: "D200.3".to_string(),
+                old: Some(Value::Bool(false)),
+                new: Value::Bool(true)
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn subscribe_checks_arguments() {
+        let handler = MemoryHandler::default();
+        let mut subscriptions = Subscriptions::new(connect(handler, Duration::ZERO).await);
+        let tag = |name: &str| Tag {
+            name: name.to_string(),
+            address: address("D100"),
+            data_type: DataType::Int,
+            scaling: None,
+            access: Access::Read,
+            description: String::new(),
+        };
+        let rate = Duration::from_millis(10);
+        for (rate, deadband) in [(Duration::ZERO, 0.0), (rate, -1.0), (rate, f64::NAN)] {
+            assert!(matches!(
+                subscriptions.subscribe(tag("Tank.Level"), rate, deadband),
+                Err(Error::InvalidSubscription { .. })
+            ));
+        }
+        assert!(matches!(
+            subscriptions.subscribe_address(address("D100"), DataType::Bool, rate),
+            Err(Error::Parse { .. })
+        ));
+        assert!(subscriptions.groups.is_empty());
+    }
+
+    #[tokio::test]
+    async fn reports_overruns() {
+        let handler = MemoryHandler::default();
+        let client = connect(handler, Duration::from_millis(30)).await;
+
+        let rate = Duration::from_millis(10);
+        let mut subscriptions = Subscriptions::new(client);
+        subscriptions
+            .subscribe_address(address("D0"), DataType::Word, rate)
+            .unwrap();
+        let mut events = subscriptions.start().unwrap();
+
+        assert!(matches!(
+            events.recv().await.unwrap(),
+            SubscriptionEvent::Changed { .. }
+        ));
+        match events.recv().await.unwrap() {
+            SubscriptionEvent::Overrun {
+                rate: overrun_rate,
+                elapsed,
+            } => {
+                assert_eq!(overrun_rate, rate);
+                assert!(elapsed >= Duration::from_millis(30));
+            }
+            event => panic!("unexpected {:?}", event),
+        }
+    }
+
+    #[tokio::test]
+    async fn stops_when_dropped() {
+        let handler = MemoryHandler::default();
+        let client = connect(handler, Duration::ZERO).await;
+
+        let mut subscriptions = Subscriptions::new(Arc::clone(&client));
+        subscriptions
+            .subscribe_address(address("D0"), DataType::Word, Duration::from_secs(3600))
+            .unwrap();
+        let mut events = subscriptions.start().unwrap();
+        assert!(events.recv().await.is_some());
+
+        // The value never changes, the task stops while waiting for the next poll.
+        drop(events);
+        tokio::time::timeout(Duration::from_secs(1), async {
+            while Arc::strong_count(&client) > 1 {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+        })
+        .await
+        .unwrap();
+    }
+}
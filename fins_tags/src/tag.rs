@@ -0,0 +1,170 @@
+use fins::MemoryAddress;
+use serde::Deserialize;
+
+use crate::{DataType, Error, Value};
+
+/// Whether a tag may be read, written or both.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Access {
+    Read,
+    Write,
+    ReadWrite,
+}
+
+impl Access {
+    pub fn can_read(&self) -> bool {
+        matches!(self, Access::Read | Access::ReadWrite)
+    }
+
+    pub fn can_write(&self) -> bool {
+        matches!(self, Access::Write | Access::ReadWrite)
+    }
+}
+
+/// Parses `r`, `w` and `rw`, or `read`, `write` and `read_write`, in any case.
+impl std::str::FromStr for Access {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text.trim().to_ascii_lowercase().as_str() {
+            "r" | "read" => Ok(Access::Read),
+            "w" | "write" => Ok(Access::Write),
+            "rw" | "read_write" => Ok(Access::ReadWrite),
+            _ => Err(format!("unknown access {:?}", text)),
+        }
+    }
+}
+
+/// Converts raw values to engineering units as `raw * factor + offset`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Scaling {
+    pub factor: f64,
+    pub offset: f64,
+}
+
+/// A named value in PLC memory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tag {
+    pub name: String,
+    pub address: MemoryAddress,
+    pub data_type: DataType,
+    /// Scaled tags have [`Value::Float`] values.
+    pub scaling: Option<Scaling>,
+    pub access: Access,
+    pub description: String,
+}
+
+impl Tag {
+    /// Number of items to read or write at the address of the tag.
+    pub fn item_count(&self) -> usize {
+        self.data_type.item_count()
+    }
+
+    /// The value of the tag given the bytes read from its address.
+    pub fn decode(&self, bytes: &[u8]) -> crate::Result<Value> {
+        let value = self.data_type.decode(bytes)?;
+        Ok(match (self.scaling, value.to_f64()) {
+            (Some(Scaling { factor, offset }), Some(raw)) => Value::Float(raw * factor + offset),
+            _ => value,
+        })
+    }
+
+    /// The bytes to write to the address of the tag to store `value`.
+    pub fn encode(&self, value: &Value) -> crate::Result<Vec<u8>> {
+        let raw = match (self.scaling, value.to_f64()) {
+            (Some(Scaling { factor, offset }), Some(value)) => {
+                let raw = (value - offset) / factor;
+                match self.data_type {
+                    DataType::Real => Value::Float(raw),
+                    _ => Value::Int(raw.round() as i64),
+                }
+            }
+            _ => value.clone(),
+        };
+        self.data_type
+            .encode(&raw)
+            .ok_or_else(|| Error::InvalidValue {
+                tag: self.name.clone(),
+                data_type: self.data_type,
+            })
+    }
+}
+
+/// A tag as written in a tag file, before its address and data type are parsed.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TagDefinition {
+    pub name: String,
+    pub address: String,
+    #[serde(rename = "type")]
+    pub data_type: String,
+    pub scale: Option<f64>,
+    pub offset: Option<f64>,
+    pub access: Option<String>,
+    pub description: Option<String>,
+}
+
+impl TagDefinition {
+    pub fn resolve(&self) -> Result<Tag, String> {
+        let address = self
+            .address
+            .parse::<MemoryAddress>()
+            .map_err(|e| e.to_string())?;
+        let data_type = self.data_type.parse::<DataType>()?;
+        if data_type.is_bit() != address.area_code.is_bit_area() {
+            return Err(format!(
+                "{} can not be stored at {:?}, BOOL needs a bit address and other types a word address",
+                data_type, address
+            ));
+        }
+        let scaling = match (self.scale, self.offset) {
+            (None, None) => None,
+            (factor, offset) => Some(Scaling {
+                factor: factor.unwrap_or(1.0),
+                offset: offset.unwrap_or(0.0),
+            }),
+        };
+        let access = match &self.access {
+            Some(access) => access.parse()?,
+            None => Access::ReadWrite,
+        };
+        Ok(Tag {
+            name: self.name.clone(),
+            address,
+            data_type,
+            scaling,
+            access,
+            description: self.description.clone().unwrap_or_default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn definition(address: &str, data_type: &str) -> TagDefinition {
+        TagDefinition {
+            name: "Tank.Level".to_string(),
+            address: address.to_string(),
+            data_type: data_type.to_string(),
+            scale: Some(0.1),
+            offset: None,
+            access: None,
+            description: None,
+        }
+    }
+
+    #[test]
+    fn resolve_works() {
+        let tag = definition("D100", "INT").resolve().unwrap();
+        assert_eq!(tag.address, "D100".parse().unwrap());
+        assert_eq!(tag.access, Access::ReadWrite);
+        assert_eq!(tag.decode(&[0x00, 0x7B]).unwrap(), Value::Float(12.3));
+        assert_eq!(tag.encode(&Value::Float(12.3)).unwrap(), [0x00, 0x7B]);
+
+        assert!(definition("D100", "BOOL").resolve().is_err());
+        assert!(definition("D100.01", "INT").resolve().is_err());
+        assert!(definition("D100", "LREAL").resolve().is_err());
+    }
+}
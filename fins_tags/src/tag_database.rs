@@ -0,0 +1,337 @@
+use std::collections::HashMap;
+
+use fins_client::{Client, ReadPlanner};
+use serde::Deserialize;
+
+use crate::{csv, Error, Tag, TagDefinition, Value};
+
+/// Tags by name.
+#[derive(Debug, Default, Clone)]
+pub struct TagDatabase {
+    tags: Vec<Tag>,
+    indices: HashMap<String, usize>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TagFile {
+    #[serde(default)]
+    tag: Vec<TagDefinition>,
+}
+
+impl TagDatabase {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads tags from TOML with a `[[tag]]` table for every tag:
+    ///
+    /// ```toml
+    /// [[tag]]
+    /// name = "Tank.Level"
+    /// address = "D100"
+    /// type = "INT"
+    /// scale = 0.1
+    /// access = "r"
+    /// ```
+    pub fn from_toml(text: &str) -> crate::Result<Self> {
+        let file: TagFile = toml::from_str(text).map_err(|e| Error::Parse {
+            line: 0,
+            message: e.to_string(),
+        })?;
+        let mut database = Self::new();
+        for definition in &file.tag {
+            let tag = definition.resolve().map_err(|message| Error::Parse {
+                line: 0,
+                message: format!("tag {:?}: {}", definition.name, message),
+            })?;
+            database.insert(tag)?;
+        }
+        Ok(database)
+    }
+
+    /// Loads tags from comma separated values. The first line names the columns, which are the
+    /// keys of the TOML format. Empty lines and lines starting with `#` are skipped:
+    ///
+    /// ```text
+    /// name,address,type,scale,access
+    /// Tank.Level,D100,INT,0.1,r
+    /// ```
+    pub fn from_csv(text: &str) -> crate::Result<Self> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line))
+            .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'));
+        let parse_error = |line: usize| move |message: String| Error::Parse { line, message };
+
+        let (line, header) = lines.next().ok_or_else(|| Error::Parse {
+            line: 0,
+            message: "missing header".to_string(),
+        })?;
+        let columns = csv::split_line(header, ',').map_err(parse_error(line))?;
+        let column = |name: &str| columns.iter().position(|column| column == name);
+        let required = |name: &str| {
+            column(name).ok_or_else(|| Error::Parse {
+                line,
+                message: format!("missing column {:?}", name),
+            })
+        };
+        let (name, address, data_type) =
+            (required("name")?, required("address")?, required("type")?);
+        let (scale, offset, access, description) = (
+            column("scale"),
+            column("offset"),
+            column("access"),
+            column("description"),
+        );
+
+        let mut database = Self::new();
+        for (line, text) in lines {
+            let fields = csv::split_line(text, ',').map_err(parse_error(line))?;
+            let field = |index: Option<usize>| {
+                index
+                    .and_then(|index| fields.get(index))
+                    .filter(|field| !field.is_empty())
+                    .cloned()
+            };
+            let number = |index: Option<usize>| {
+                field(index)
+                    .map(|field| {
+                        field
+                            .parse::<f64>()
+                            .map_err(|_| format!("invalid number {:?}", field))
+                    })
+                    .transpose()
+            };
+            let tag = (|| {
+                TagDefinition {
+                    name: field(Some(name)).ok_or("missing name")?,
+                    address: field(Some(address)).ok_or("missing address")?,
+                    data_type: field(Some(data_type)).ok_or("missing type")?,
+                    scale: number(scale)?,
+                    offset: number(offset)?,
+                    access: field(access),
+                    description: field(description),
+                }
+                .resolve()
+            })()
+            .map_err(parse_error(line))?;
+            database.insert(tag).map_err(|error| Error::Parse {
+                line,
+                message: error.to_string(),
+            })?;
+        }
+        Ok(database)
+    }
+
+    /// Adds `tag`, which fails when a tag with the same name exists.
+    pub fn insert(&mut self, tag: Tag) -> crate::Result<()> {
+        if self.indices.contains_key(&tag.name) {
+            return Err(Error::DuplicateTag(tag.name));
+        }
+        self.indices.insert(tag.name.clone(), self.tags.len());
+        self.tags.push(tag);
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Tag> {
+        self.indices.get(name).map(|&index| &self.tags[index])
+    }
+
+    /// The tags in the order in which they were added.
+    pub fn tags(&self) -> &[Tag] {
+        &self.tags
+    }
+
+    fn tag(&self, name: &str) -> crate::Result<&Tag> {
+        self.get(name)
+            .ok_or_else(|| Error::UnknownTag(name.to_string()))
+    }
+
+    /// Reads the value of the tag named `name`. BOOL tags are read through their word when the
+    /// unit does not support their bit area, see [`Client::read_bits`].
+    pub async fn read(&self, client: &Client, name: &str) -> crate::Result<Value> {
+        let tag = self.tag(name)?;
+        if !tag.access.can_read() {
+            return Err(Error::WriteOnly(tag.name.clone()));
+        }
+        let bytes = if tag.data_type.is_bit() {
+            fins::encode_bit_items(&client.read_bits(tag.address, 1).await?)
+        } else {
+            client.read_range(tag.address, tag.item_count()).await?
+        };
+        tag.decode(&bytes)
+    }
+
+    /// Reads the values of the tags named `names` with the requests planned by `planner`, which
+    /// are far fewer than the tags when tags are close together.
+    pub async fn read_many(
+        &self,
+        client: &Client,
+        planner: &ReadPlanner,
+        names: &[&str],
+    ) -> crate::Result<Vec<Value>> {
+        let tags = names
+            .iter()
+            .map(|name| {
+                let tag = self.tag(name)?;
+                if !tag.access.can_read() {
+                    return Err(Error::WriteOnly(tag.name.clone()));
+                }
+                Ok(tag)
+            })
+            .collect::<crate::Result<Vec<_>>>()?;
+        let plan = planner.plan(
+            &tags
+                .iter()
+                .map(|tag| (tag.address, tag.item_count()))
+                .collect::<Vec<_>>(),
+        )?;
+        let bytes = client.read_plan(&plan).await?;
+        tags.iter()
+            .zip(bytes)
+            .map(|(tag, bytes)| tag.decode(&bytes))
+            .collect()
+    }
+
+    /// Writes `value` to the tag named `name`. BOOL tags are written through their word when the
+    /// unit does not support their bit area, see [`Client::write_bits`].
+    pub async fn write(&self, client: &Client, name: &str, value: &Value) -> crate::Result<()> {
+        let tag = self.tag(name)?;
+        if !tag.access.can_write() {
+            return Err(Error::ReadOnly(tag.name.clone()));
+        }
+        let bytes = tag.encode(value)?;
+        if tag.data_type.is_bit() {
+            client
+                .write_bits(tag.address, &fins::decode_bit_items(&bytes))
+                .await?;
+        } else {
+            client.write_range(tag.address, &bytes).await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fins::MemoryAreaCode;
+    use fins_server::{MemoryHandler, Server};
+
+    use super::*;
+    use crate::{Access, DataType};
+
+    const TOML: &str = r#"
+[[tag]]
+name = "HoldToRun.Instruction.Assign.SequenceNumber"
+address = "D1508"
+type = "UINT"
+
+[[tag]]
+name = "Alarms.Status.Alarm_NoCommunication"
+address = "D2420.01"
+type = "BOOL"
+access = "r"
+
+[[tag]]
+name = "Tank.Level"
+address = "D10"
+type = "DINT"
+scale = 0.01
+description = "Level in m"
+"#;
+
+    #[test]
+    fn from_toml_works() {
+        let database = TagDatabase::from_toml(TOML).unwrap();
+        assert_eq!(database.tags().len(), 3);
+        let alarm = database.get("Alarms.Status.Alarm_NoCommunication").unwrap();
+        assert_eq!(alarm.address, "D2420.01".parse().unwrap());
+        assert_eq!(alarm.data_type, DataType::Bool);
+        assert_eq!(alarm.access, Access::Read);
+
+        assert!(matches!(
+            TagDatabase::from_toml("[[tag]]\nname = \"A\"\naddress = \"X1\"\ntype = \"INT\"\n"),
+            Err(Error::Parse { .. })
+        ));
+    }
+
+    #[test]
+    fn from_csv_works() {
+        let database = TagDatabase::from_csv(
+            "# Press line\n\
+             name,type,address,scale,description\n\
+             Tank.Level,DINT,D10,0.01,\"Level, in m\"\n\
+             \n\
+             Tank.Valve,BOOL,W0.03,,\n",
+        )
+        .unwrap();
+        let level = database.get("Tank.Level").unwrap();
+        assert_eq!(level.description, "Level, in m");
+        assert_eq!(level.scaling.unwrap().factor, 0.01);
+        assert_eq!(database.get("Tank.Valve").unwrap().scaling, None);
+
+        match TagDatabase::from_csv("name,address,type\nA,D1,INT\nA,D2,INT\n") {
+            Err(Error::Parse { line: 3, .. }) => {}
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(TagDatabase::from_csv("name,address\nA,D1\n").is_err());
+    }
+
+    #[tokio::test]
+    async fn read_write_works() {
+        let handler = MemoryHandler::default();
+        handler.memory().area(MemoryAreaCode::D)[2420] = 0b10;
+        let server = Server::bind("127.0.0.1:0", 0x01, handler).await.unwrap();
+        let addr = server.local_addr().unwrap();
+        tokio::spawn(server.run());
+        let client = Client::connect(addr).await.unwrap();
+
+        let database = TagDatabase::from_toml(TOML).unwrap();
+        database
+            .write(&client, "Tank.Level", &Value::Float(-12.34))
+            .await
+            .unwrap();
+        assert_eq!(
+            database.read(&client, "Tank.Level").await.unwrap(),
+            Value::Float(-12.34)
+        );
+        assert_eq!(
+            database
+                .read(&client, "Alarms.Status.Alarm_NoCommunication")
+                .await
+                .unwrap(),
+            Value::Bool(true)
+        );
+        assert_eq!(
+            database
+                .read_many(
+                    &client,
+                    &ReadPlanner::new(),
+                    &[
+                        "Alarms.Status.Alarm_NoCommunication",
+                        "Tank.Level",
+                        "HoldToRun.Instruction.Assign.SequenceNumber"
+                    ]
+                )
+                .await
+                .unwrap(),
+            [Value::Bool(true), Value::Float(-12.34), Value::Int(0)]
+        );
+        assert!(matches!(
+            database
+                .write(
+                    &client,
+                    "Alarms.Status.Alarm_NoCommunication",
+                    &Value::Bool(false)
+                )
+                .await,
+            Err(Error::ReadOnly(_))
+        ));
+        assert!(matches!(
+            database.read(&client, "Tank.Volume").await,
+            Err(Error::UnknownTag(_))
+        ));
+    }
+}
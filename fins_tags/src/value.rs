@@ -0,0 +1,31 @@
+/// The value of a tag.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    String(String),
+}
+
+impl Value {
+    /// The value as a number, `true` being 1.
+    pub fn to_f64(&self) -> Option<f64> {
+        match *self {
+            Value::Bool(value) => Some(value as u8 as f64),
+            Value::Int(value) => Some(value as f64),
+            Value::Float(value) => Some(value),
+            Value::String(_) => None,
+        }
+    }
+}
+
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Value::Bool(value) => value.fmt(f),
+            Value::Int(value) => value.fmt(f),
+            Value::Float(value) => value.fmt(f),
+            Value::String(value) => write!(f, "{:?}", value),
+        }
+    }
+}
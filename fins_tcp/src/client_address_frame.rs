@@ -4,9 +4,7 @@ use std::{
     io::{Read, Write},
 };
 
-use crate::{
-    assert_command, assert_header_length, assert_no_error, CommandCode, Header, RawHeader, Result,
-};
+use crate::{assert_command, assert_header_length, assert_no_error, CommandCode, Header, Result};
 
 #[derive(Debug, Eq, PartialEq)]
 pub struct ClientAddressFrame {
@@ -48,15 +46,6 @@ impl ClientAddressFrame {
     }
 }
 
-#[derive(Default)]
-#[repr(C, packed)]
-struct RawClientAddressFrame {
-    header: RawHeader,
-    body: RawClientAddressBody,
-}
-
-unsafe_impl_raw!(RawClientAddressFrame);
-
 #[derive(Default)]
 #[repr(C, packed)]
 struct RawClientAddressBody {
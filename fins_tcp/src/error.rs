@@ -5,6 +5,20 @@ use crate::ProtocolViolation;
 pub enum Error {
     ProtocolViolation(ProtocolViolation),
     Io(std::io::Error),
+    /// The server responded with an end code other than normal completion.
+    EndCode(fins::EndCode),
+    /// A value of the command can not be encoded.
+    InvalidValue(String),
+    /// The connection was lost before the response arrived. The command may or may not have been
+    /// executed.
+    Disconnected,
+}
+
+impl Error {
+    /// Whether sending the command again may succeed, for example after the client reconnected.
+    pub fn is_retriable(&self) -> bool {
+        matches!(self, Self::Disconnected)
+    }
 }
 
 impl From<ProtocolViolation> for Error {
@@ -32,6 +46,8 @@ impl From<fins::Error> for Error {
                 Self::ProtocolViolation(ProtocolViolation::from(e))
             }
             fins::Error::Io(e) => Self::Io(e),
+            fins::Error::EndCode(e) => Self::EndCode(e),
+            fins::Error::InvalidValue(e) => Self::InvalidValue(e),
         }
     }
 }
@@ -41,6 +57,9 @@ impl std::fmt::Display for Error {
         match self {
             Self::ProtocolViolation(e) => e.fmt(f),
             Self::Io(e) => e.fmt(f),
+            Self::EndCode(e) => e.fmt(f),
+            Self::InvalidValue(e) => write!(f, "Invalid FINS value: {}", e),
+            Self::Disconnected => write!(f, "Connection lost before the response arrived!"),
         }
     }
 }
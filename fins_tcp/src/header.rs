@@ -6,6 +6,12 @@ use fins_util::{u32be, unsafe_impl_raw, ReadExt, WriteExt};
 
 const FINS: [u8; 4] = *b"FINS";
 
+/// Smallest FINS frame, a header and command code.
+pub const MIN_FINS_FRAME_SIZE: usize = 12;
+
+/// Largest FINS frame, a header and command code followed by 2000 bytes of data.
+pub const MAX_FINS_FRAME_SIZE: usize = 2012;
+
 #[derive(Debug, Default, Copy, Clone)]
 #[repr(C, packed)]
 pub struct RawHeader {
@@ -29,6 +35,16 @@ impl Header {
         Ok(Self::from_raw(reader.read_raw::<RawHeader>()?)?)
     }
 
+    /// The number of bytes following the header. The length is counted from the command field
+    /// and may not exceed that of a frame carrying the largest FINS frame.
+    pub fn body_size(&self) -> Result<usize, ProtocolViolation> {
+        let header_size = std::mem::size_of::<RawHeader>();
+        match (self.length as usize).checked_sub(header_size - 8) {
+            Some(size) if size <= MAX_FINS_FRAME_SIZE => Ok(size),
+            _ => Err(ProtocolViolation::UnexpectedFrameLength(self.length)),
+        }
+    }
+
     pub fn write_to<W: Write>(&self, writer: &mut W) -> crate::Result<()> {
         writer.write_raw(&self.to_raw())?;
         Ok(())
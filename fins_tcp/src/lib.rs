@@ -4,34 +4,78 @@ mod error;
 mod header;
 mod protocol_violation;
 mod server_address_frame;
+mod session;
 
 use std::io::{Read, Write};
 
 pub use client_address_frame::*;
 pub use command_code::*;
 pub use error::*;
-use fins::{MachineAddress, MemoryAreaReadRequest};
-use fins_util::ReadExt;
+use fins::{Command, MachineAddress, MemoryAreaReadRequest, Route};
 pub use header::*;
 pub use protocol_violation::*;
 pub use server_address_frame::*;
+pub use session::*;
 
-pub fn write_memory_area_read_request<W: Write>(
+/// Writes the FINS/TCP frame for `command` sent along `route`.
+pub fn write_command<W: Write, C: Command>(
     writer: &mut W,
-    request: &MemoryAreaReadRequest,
+    route: &Route,
+    service_id: u8,
+    command: &C,
 ) -> crate::Result<()> {
     Header {
         command: CommandCode::Fins,
-        length: 8 + MemoryAreaReadRequest::byte_size() as u32,
+        length: 8 + fins::request_byte_size(command) as u32,
         error_code: 0,
     }
     .write_to(writer)?;
 
-    request.write_to(writer)?;
+    fins::write_request(writer, route, service_id, command)?;
 
     Ok(())
 }
 
+/// Writes a FINS/TCP frame carrying the FINS `frame`.
+pub fn write_fins_frame<W: Write>(writer: &mut W, frame: &[u8]) -> crate::Result<()> {
+    Header {
+        command: CommandCode::Fins,
+        length: 8 + frame.len() as u32,
+        error_code: 0,
+    }
+    .write_to(writer)?;
+    writer.write_all(frame)?;
+
+    Ok(())
+}
+
+/// Reads a FINS/TCP frame carrying a FINS frame and returns the FINS frame.
+pub fn read_fins_frame<R: Read>(reader: &mut R) -> crate::Result<Vec<u8>> {
+    let header = Header::read_from(reader)?;
+
+    assert_command(header.command, CommandCode::Fins)?;
+    assert_no_error(header.error_code)?;
+
+    let byte_count = header.body_size()?;
+    if byte_count < MIN_FINS_FRAME_SIZE {
+        return Err(ProtocolViolation::UnexpectedFrameLength(header.length).into());
+    }
+
+    let mut bytes = vec![0; byte_count];
+    reader.read_exact(&mut bytes[..])?;
+
+    Ok(bytes)
+}
+
+/// Reads the FINS/TCP frame carrying the response to `command`.
+pub fn read_response<R: Read, C: Command>(
+    reader: &mut R,
+    command: &C,
+) -> crate::Result<fins::Response<C::Response>> {
+    let frame = read_fins_frame(reader)?;
+    Ok(fins::read_response(command, &frame)?)
+}
+
 pub struct MemoryAreaReadResponse {
     pub src_addr: MachineAddress,
     pub dst_addr: MachineAddress,
@@ -41,60 +85,31 @@ pub struct MemoryAreaReadResponse {
 
 pub fn read_memory_area_read_response<R: Read>(
     reader: &mut R,
+    session: &Session,
+    request: &MemoryAreaReadRequest,
 ) -> crate::Result<MemoryAreaReadResponse> {
-    let Header {
-        length,
-        command,
-        error_code,
-    } = Header::read_from(reader)?;
-
-    assert_command(command, CommandCode::Fins)?;
-    assert_no_error(error_code)?;
-
+    let frame = read_fins_frame(reader)?;
+    let fins::MemoryAreaReadResponse {
+        header,
+        end_code,
+        bytes,
+    } = fins::MemoryAreaReadResponse::from_bytes(&frame)?;
     let fins::Header {
-        icf,
-        gct: _,
         destination,
         source,
         sid,
-    } = reader.read_raw::<fins::RawHeader>()?.deserialize()?;
-
-    assert_eq!(icf, fins::InformationControlField::ResponseWithResponse);
-    // assert_eq!(
-    //     destination,
-    //     fins::MachineAddress {
-    //         network: 0,
-    //         node: self.client_node,
-    //         unit: 0
-    //     }
-    // );
-    // assert_eq!(
-    //     source,
-    //     fins::MachineAddress {
-    //         network: 0,
-    //         node: self.server_node,
-    //         unit: 0
-    //     }
-    // );
-
-    let fins::RawResponseHeader {
-        mrc,
-        src,
-        mres,
-        sres,
-    } = reader.read_raw::<fins::RawResponseHeader>()?;
-
-    assert_eq!([mrc, src], [0x01, 0x01]);
-    assert_eq!([mres, sres], [0x00, 0x00]);
-
-    let byte_count = length
-        - (std::mem::size_of::<RawHeader>() as u32 - 8)
-        - std::mem::size_of::<fins::RawHeader>() as u32
-        - std::mem::size_of::<fins::RawResponseHeader>() as u32;
-
-    let mut bytes = Vec::with_capacity(byte_count as usize);
-    bytes.resize(byte_count as usize, 0);
-    reader.read_exact(&mut bytes[..])?;
+        ..
+    } = header;
+    session.check_response(
+        &session
+            .route()
+            .header(fins::InformationControlField::RequestWithResponse, sid),
+        &header,
+    )?;
+    if !end_code.is_normal_completion() {
+        return Err(Error::EndCode(end_code));
+    }
+    request.decode_response(&bytes)?;
 
     Ok(MemoryAreaReadResponse {
         src_addr: source,
@@ -116,17 +131,16 @@ mod tests {
     fn works() {
         let mut buffer = vec![];
         let mut cursor = Cursor::new(&mut buffer);
-        write_memory_area_read_request(
+        write_command(
             &mut cursor,
+            &Route::local(0xD3, 0xFB),
+            1,
             &MemoryAreaReadRequest {
-                server_node: 0xD3,
-                client_node: 0xFB,
                 address: MemoryAddress {
                     area_code: MemoryAreaCode::D,
                     offset: 1500,
                     bits: 0,
                 },
-                service_id: 1,
                 count: 16,
             },
         )
@@ -151,4 +165,32 @@ mod tests {
             ]
         )
     }
+
+    #[test]
+    fn read_fins_frame_checks_length() {
+        let frame = |length: u32| {
+            let mut buffer = vec![];
+            Header {
+                command: CommandCode::Fins,
+                length,
+                error_code: 0,
+            }
+            .write_to(&mut buffer)
+            .unwrap();
+            buffer.resize(buffer.len() + 4096, 0);
+            buffer
+        };
+        let read = |length: u32| read_fins_frame(&mut Cursor::new(frame(length)));
+
+        assert_eq!(read(8 + 12).unwrap().len(), 12);
+        assert_eq!(read(8 + 2012).unwrap().len(), 2012);
+        for &length in &[0, 7, 8 + 11, 8 + 2013, u32::MAX] {
+            assert!(matches!(
+                read(length),
+                Err(Error::ProtocolViolation(
+                    ProtocolViolation::UnexpectedFrameLength(actual)
+                )) if actual == length
+            ));
+        }
+    }
 }
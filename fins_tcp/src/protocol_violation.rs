@@ -10,11 +10,23 @@ pub enum ProtocolViolation {
         expected: u32,
     },
     UnexpectedError(u32),
+    /// The length in a FINS/TCP header is too short or too long for the frame.
+    UnexpectedFrameLength(u32),
     UnexpectedCommand {
         actual: CommandCode,
         expected: CommandCode,
     },
     Fins(fins::ProtocolViolation),
+    /// A response was not addressed to the client of the session.
+    UnexpectedDestination {
+        actual: fins::MachineAddress,
+        expected: fins::MachineAddress,
+    },
+    /// A response did not come from the unit the request was sent to.
+    UnexpectedSource {
+        actual: fins::MachineAddress,
+        expected: fins::MachineAddress,
+    },
 }
 
 impl From<fins::ProtocolViolation> for ProtocolViolation {
@@ -36,14 +48,60 @@ impl std::fmt::Display for ProtocolViolation {
                 "Received FINS/TCP frame with unknown command {}!",
                 command.to_u32()
             ),
-            Self::UnexpectedHeaderLength { .. } => todo!(),
-            Self::UnexpectedError(_) => todo!(),
-            Self::UnexpectedCommand { .. } => todo!(),
+            Self::UnexpectedHeaderLength { actual, expected } => write!(
+                f,
+                "Received FINS/TCP frame with length {} but expected {}!",
+                actual, expected
+            ),
+            Self::UnexpectedError(code) => match error_description(*code) {
+                Some(description) => write!(
+                    f,
+                    "Received FINS/TCP error code 0x{:08X}: {}!",
+                    code, description
+                ),
+                None => write!(f, "Received FINS/TCP error code 0x{:08X}!", code),
+            },
+            Self::UnexpectedFrameLength(length) => write!(
+                f,
+                "Received FINS/TCP frame with unexpected length {}!",
+                length
+            ),
+            Self::UnexpectedCommand { actual, expected } => write!(
+                f,
+                "Received FINS/TCP frame with command {} but expected {}!",
+                actual, expected
+            ),
             Self::Fins(e) => e.fmt(f),
+            Self::UnexpectedDestination { actual, expected } => write!(
+                f,
+                "Received response addressed to {:?} but expected {:?}!",
+                actual, expected
+            ),
+            Self::UnexpectedSource { actual, expected } => write!(
+                f,
+                "Received response from {:?} but expected {:?}!",
+                actual, expected
+            ),
         }
     }
 }
 
+/// Describes the FINS/TCP error codes that units send.
+fn error_description(code: u32) -> Option<&'static str> {
+    Some(match code {
+        0x01 => "the header is not FINS",
+        0x02 => "the data length is too long",
+        0x03 => "the command is not supported",
+        0x20 => "all connections are in use",
+        0x21 => "the client node is already connected",
+        0x22 => "the client node is protected",
+        0x23 => "the client node is out of range",
+        0x24 => "the client node is the server node",
+        0x25 => "all client nodes are in use",
+        _ => return None,
+    })
+}
+
 pub(crate) fn assert_header_length(actual: u32, expected: u32) -> Result<(), ProtocolViolation> {
     if actual == expected {
         Ok(())
@@ -86,5 +144,29 @@ mod tests {
             ),
             "Received incorrect magic string 0x01523299 but expected 0x46494E53 (\"FINS\")!"
         );
+        assert_eq!(
+            ProtocolViolation::UnexpectedHeaderLength {
+                actual: 8,
+                expected: 16
+            }
+            .to_string(),
+            "Received FINS/TCP frame with length 8 but expected 16!"
+        );
+        assert_eq!(
+            ProtocolViolation::UnexpectedError(0x21).to_string(),
+            "Received FINS/TCP error code 0x00000021: the client node is already connected!"
+        );
+        assert_eq!(
+            ProtocolViolation::UnexpectedError(0x99).to_string(),
+            "Received FINS/TCP error code 0x00000099!"
+        );
+        assert_eq!(
+            ProtocolViolation::UnexpectedCommand {
+                actual: CommandCode::Fins,
+                expected: CommandCode::ServerAddress
+            }
+            .to_string(),
+            "Received FINS/TCP frame with command Fins but expected ServerAddress!"
+        );
     }
 }
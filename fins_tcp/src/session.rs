@@ -0,0 +1,135 @@
+use fins::{Header, MachineAddress, Route};
+
+use crate::{ProtocolViolation, ServerAddressFrame};
+
+/// The nodes negotiated by the address frames at the start of a FINS/TCP connection. Frames
+/// exchanged over the connection must carry them.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Session {
+    pub client_node: u8,
+    pub server_node: u8,
+}
+
+impl From<ServerAddressFrame> for Session {
+    fn from(frame: ServerAddressFrame) -> Self {
+        Self {
+            client_node: frame.client_node,
+            server_node: frame.server_node,
+        }
+    }
+}
+
+impl Session {
+    /// The route to the CPU unit of the server.
+    pub const fn route(&self) -> Route {
+        Route::local(self.server_node, self.client_node)
+    }
+
+    /// Writes the client node into the source of a request header, and the server node into its
+    /// destination when that is node 0 of the local network, the connected unit. Other
+    /// destinations are left as is so that the server can relay requests to them.
+    pub fn stamp(&self, request: &mut Header) {
+        request.source.node = self.client_node;
+        if request.destination.network == MachineAddress::LOCAL_NETWORK
+            && request.destination.node == 0
+        {
+            request.destination.node = self.server_node;
+        }
+    }
+
+    /// Checks that `response` comes from the unit that `request` was sent to and is addressed to
+    /// the unit that sent it. A request for node 0 is answered by the server node.
+    pub fn check_response(
+        &self,
+        request: &Header,
+        response: &Header,
+    ) -> Result<(), ProtocolViolation> {
+        let expected_destination = MachineAddress {
+            node: self.client_node,
+            ..request.source
+        };
+        if response.destination != expected_destination {
+            return Err(ProtocolViolation::UnexpectedDestination {
+                actual: response.destination,
+                expected: expected_destination,
+            });
+        }
+
+        let expected_source = match request.destination.node {
+            0 => MachineAddress {
+                node: self.server_node,
+                ..request.destination
+            },
+            _ => request.destination,
+        };
+        if response.source != expected_source {
+            return Err(ProtocolViolation::UnexpectedSource {
+                actual: response.source,
+                expected: expected_source,
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fins::InformationControlField;
+
+    use super::*;
+
+    #[test]
+    fn check_response_works() {
+        let session = Session {
+            client_node: 0x02,
+            server_node: 0x01,
+        };
+        let mut request =
+            Route::local(0x00, 0xFB).header(InformationControlField::RequestWithResponse, 0x07);
+        session.stamp(&mut request);
+        assert_eq!(request.source.node, 0x02);
+        assert_eq!(request.destination.node, 0x01);
+
+        let response = request.response();
+        assert!(session.check_response(&request, &response).is_ok());
+
+        let mut response = request.response();
+        response.destination.node = 0x03;
+        assert!(matches!(
+            session.check_response(&request, &response),
+            Err(ProtocolViolation::UnexpectedDestination { .. })
+        ));
+
+        let mut response = request.response();
+        response.source = MachineAddress::cpu_bus_unit(0x00, 0x01, 0x00).unwrap();
+        assert!(matches!(
+            session.check_response(&request, &response),
+            Err(ProtocolViolation::UnexpectedSource { .. })
+        ));
+    }
+
+    #[test]
+    fn stamp_keeps_other_destinations() {
+        let session = Session {
+            client_node: 0x02,
+            server_node: 0x01,
+        };
+        let source = MachineAddress::cpu(MachineAddress::LOCAL_NETWORK, 0xFB);
+        for destination in [
+            MachineAddress::cpu(0x03, 0x10),
+            MachineAddress::cpu(0x03, 0x00),
+            MachineAddress::cpu(MachineAddress::LOCAL_NETWORK, 0x05),
+            MachineAddress::cpu(
+                MachineAddress::LOCAL_NETWORK,
+                MachineAddress::BROADCAST_NODE,
+            ),
+        ] {
+            let mut request = Route::new(destination, source)
+                .header(InformationControlField::RequestWithResponse, 0x07);
+            session.stamp(&mut request);
+            assert_eq!(request.source.node, 0x02);
+            assert_eq!(request.destination, destination);
+        }
+    }
+}
@@ -0,0 +1,43 @@
+/// Decodes a binary coded decimal value. Returns `None` when a nibble is not a decimal digit.
+pub const fn from_bcd_u32(bcd: u32) -> Option<u32> {
+    let mut value = 0;
+    let mut shift = 32;
+    while shift > 0 {
+        shift -= 4;
+        let digit = (bcd >> shift) & 0xF;
+        if digit > 9 {
+            return None;
+        }
+        value = value * 10 + digit;
+    }
+    Some(value)
+}
+
+/// Encodes a value as binary coded decimal. Returns `None` when the value has more than 8 digits.
+pub const fn to_bcd_u32(mut value: u32) -> Option<u32> {
+    if value > 99_999_999 {
+        return None;
+    }
+    let mut bcd = 0;
+    let mut shift = 0;
+    while value > 0 {
+        bcd |= (value % 10) << shift;
+        value /= 10;
+        shift += 4;
+    }
+    Some(bcd)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        assert_eq!(from_bcd_u32(0x0000_0650), Some(650));
+        assert_eq!(to_bcd_u32(650), Some(0x0000_0650));
+        assert_eq!(to_bcd_u32(99_999_999), Some(0x9999_9999));
+        assert_eq!(from_bcd_u32(0x0000_00A0), None);
+        assert_eq!(to_bcd_u32(100_000_000), None);
+    }
+}
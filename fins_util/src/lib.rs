@@ -1,6 +1,8 @@
+mod bcd;
 mod num;
 mod raw;
 
+pub use bcd::*;
 pub use num::*;
 pub use raw::*;
 
@@ -31,6 +31,18 @@ macro_rules! impl_ints {
             }
         }
 
+        unsafe impl $crate::Raw for $TE {
+            #[inline]
+            fn as_bytes(&self) -> &[u8] {
+                unsafe { ::std::slice::from_raw_parts(self as *const Self as *const u8, ::std::mem::size_of::<Self>()) }
+            }
+
+            #[inline]
+            fn as_bytes_mut(&mut self) -> &mut [u8] {
+                unsafe { ::std::slice::from_raw_parts_mut(self as *mut Self as *mut u8, ::std::mem::size_of::<Self>()) }
+            }
+        }
+
         impl From<$N> for $TE {
             #[inline(always)]
             fn from(val: $N) -> Self {
@@ -65,25 +77,7 @@ impl_ints!(i128be, i128le, i128, [u8; 16], to_i128, from_i128);
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::mem::{align_of, size_of};
-
-    macro_rules! offset_of {
-        ($Struct:path, $field:ident) => {{
-            unsafe {
-                let u = ::std::mem::MaybeUninit::<$Struct>::uninit();
-
-                // Use pattern-matching to avoid accidentally going through Deref.
-                let &$Struct { $field: ref f, .. } = &*u.as_ptr();
-
-                let o = (f as *const _ as usize).wrapping_sub(&u as *const _ as usize);
-
-                // Triple check that we are within `u` still.
-                debug_assert!((0..=::std::mem::size_of_val(&u)).contains(&o));
-
-                o
-            }
-        }};
-    }
+    use std::mem::{align_of, offset_of, size_of};
 
     #[test]
     fn types_are_aligned_to_their_native_counterparts() {
@@ -96,7 +90,10 @@ mod tests {
         assert_eq!(8, align_of::<i64be>());
         assert_eq!(8, align_of::<i64le>());
 
-        assert_eq!(8, align_of::<i128>());
+        // Rust 1.77 raised the alignment of 128-bit integers on x86 from 8 to 16 bytes to match
+        // the C ABI.
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        assert_eq!(16, align_of::<i128>());
         assert_eq!(align_of::<i128>(), align_of::<i128be>());
         assert_eq!(align_of::<i128>(), align_of::<i128le>());
 
@@ -109,7 +106,10 @@ mod tests {
         assert_eq!(8, align_of::<u64be>());
         assert_eq!(8, align_of::<u64le>());
 
-        assert_eq!(8, align_of::<u128>());
+        // Rust 1.77 raised the alignment of 128-bit integers on x86 from 8 to 16 bytes to match
+        // the C ABI.
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        assert_eq!(16, align_of::<u128>());
         assert_eq!(align_of::<u128>(), align_of::<u128be>());
         assert_eq!(align_of::<u128>(), align_of::<u128le>());
     }
@@ -3,12 +3,29 @@ use std::{
     mem::MaybeUninit,
 };
 
+/// Types that can be viewed as their byte representation.
+///
+/// # Safety
+///
+/// Implementors must have no padding and accept any bit pattern, see `unsafe_impl_raw!`.
 pub unsafe trait Raw: Sized {
     fn as_bytes(&self) -> &[u8];
 
     fn as_bytes_mut(&mut self) -> &mut [u8];
 }
 
+unsafe impl Raw for u8 {
+    #[inline]
+    fn as_bytes(&self) -> &[u8] {
+        std::slice::from_ref(self)
+    }
+
+    #[inline]
+    fn as_bytes_mut(&mut self) -> &mut [u8] {
+        std::slice::from_mut(self)
+    }
+}
+
 pub trait WriteExt {
     fn write_raw<T: Raw>(&mut self, raw: &T) -> std::io::Result<()>;
 }
@@ -32,7 +49,6 @@ impl<R: Read> ReadExt for R {
                 std::mem::size_of::<T>(),
             );
             self.read_exact(bytes)?;
-            std::mem::forget(bytes);
             Ok(value.assume_init())
         }
     }